@@ -2,7 +2,7 @@ use crate::block_spacing::BlockSpacingOverrides;
 use crate::inline_style::InlineStyleOverrides;
 use crate::list_marker::{PrettyListStyle, UniformListMarker};
 use clap::builder::PossibleValue;
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::fmt;
 use std::path::PathBuf;
 
@@ -21,9 +21,13 @@ mdv is a terminal-based markdown viewer that renders markdown files with syntax
 Examples:
   mdv README.md                    # View a markdown file
   mdv help                         # Browse the full help
+  mdv toc README.md                # Print the heading outline
   mdv -t monokai README.md         # Use monokai theme
   mdv --monitor README.md          # Monitor file for changes
   mdv --html README.md             # Output HTML instead of terminal formatting
+  mdv --export svg README.md       # Print the rendered output as an SVG image
+  mdv --format json README.md      # Print the document tree as JSON
+  mdv --format text README.md      # Print plain text for emails and commit messages
   mdv -E README.md                 # Render embedded HTML in terminal output
   cat README.md | mdv              # Read from stdin
 "#
@@ -46,7 +50,7 @@ pub struct Cli {
     pub hide_comments: bool,
 
     /// Control how YAML front matter at the beginning of a document is displayed
-    #[arg(long = "front-matter", value_enum, value_name = "MODE", default_value = "hidden", help_heading = "Output and flow", display_order = 10)]
+    #[arg(long = "front-matter", value_enum, value_name = "MODE", default_value = "hidden", global = true, help_heading = "Output and flow", display_order = 10)]
     pub front_matter: Option<FrontMatterMode>,
 
     /// Render raw HTML fragments as terminal-formatted content
@@ -57,9 +61,21 @@ pub struct Cli {
     #[arg(short = 'N', long = "line-numbers", num_args = 0..=1, value_name = "MODE", value_enum, hide_possible_values = true, help_heading = "Output and flow", display_order = 7, long_help = LINE_NUMBERS_LONG_HELP,)]
     pub line_numbers: Option<Option<LineNumberOptions>>,
 
+    /// Draw local PNG, JPEG and GIF images inline instead of showing text markers
+    #[arg(long = "images", num_args = 0..=1, value_name = "MODE", value_enum, default_missing_value = "auto", help_heading = "Output and flow", display_order = 7, long_help = IMAGES_LONG_HELP,)]
+    pub images: Option<ImageMode>,
+
     /// Print HTML version instead of terminal formatting
-    #[arg(long = "html", help_heading = "Output and flow", display_order = 5)]
-    pub do_html: bool,
+    #[arg(long = "html", num_args = 0..=1, value_name = "MODE", value_enum, default_missing_value = "fragment", help_heading = "Output and flow", display_order = 5, long_help = HTML_LONG_HELP,)]
+    pub html: Option<HtmlMode>,
+
+    /// Export the rendered terminal output, colors and links included, as an HTML page or SVG image
+    #[arg(long = "export", value_name = "FORMAT", value_enum, conflicts_with = "html", help_heading = "Output and flow", display_order = 5)]
+    pub export: Option<ExportFormat>,
+
+    /// Print the document as a JSON tree or as plain text without escape sequences
+    #[arg(long = "format", value_name = "FORMAT", value_enum, conflicts_with_all = ["html", "export"], help_heading = "Output and flow", display_order = 5)]
+    pub format: Option<OutputFormat>,
 
     /// Show output in the built-in pager instead of printing everything at once
     #[arg(short = 'p', long = "pager", help_heading = "Output and flow", display_order = 0)]
@@ -69,6 +85,18 @@ pub struct Cli {
     #[arg(short = 'i', long = "interactive", conflicts_with = "pager", help_heading = "Output and flow", display_order = 1)]
     pub interactive: bool,
 
+    /// Show the Markdown source beside the rendered output in the pager, scrolled together
+    #[arg(long = "split", conflicts_with_all = ["interactive", "html", "format"], help_heading = "Output and flow", display_order = 1)]
+    pub split: bool,
+
+    /// Compare the document with an older version of it, highlighting changed blocks
+    #[arg(long = "diff", value_name = "OLD", requires = "filename", conflicts_with_all = ["diff_git", "interactive", "split", "html", "format", "monitor_file"], help_heading = "Output and flow", display_order = 2)]
+    pub diff: Option<String>,
+
+    /// Compare the document with its version at a git revision, read with the local git
+    #[arg(long = "diff-git", value_name = "REV", requires = "filename", conflicts_with_all = ["interactive", "split", "html", "format", "monitor_file"], help_heading = "Output and flow", display_order = 2)]
+    pub diff_git: Option<String>,
+
     /// Fix columns to this width
     #[arg(short = 'c', long = "cols", help_heading = "Layout and wrapping", display_order = 11)]
     pub cols: Option<usize>,
@@ -164,9 +192,13 @@ pub struct Cli {
     pub pretty_table: bool,
 
     /// Display from given substring of the file
-    #[arg(long = "from", value_name = "TEXT", help_heading = "Output and flow", display_order = 3)]
+    #[arg(long = "from", value_name = "TEXT", global = true, help_heading = "Output and flow", display_order = 3)]
     pub from_txt: Option<String>,
 
+    /// Render only the section under a heading path (e.g. `Install > Linux`) or anchor slug
+    #[arg(long = "section", value_name = "HEADING", global = true, conflicts_with = "from_txt", help_heading = "Output and flow", display_order = 3)]
+    pub section: Option<String>,
+
     /// Render document starting from the end while preserving layout
     #[arg(short = 'r', long = "reverse", help_heading = "Output and flow", display_order = 4)]
     pub reverse: bool,
@@ -262,6 +294,10 @@ pub enum FrontMatterMode {
 mod callouts;
 mod code_blocks;
 mod commands;
+mod export;
+mod format;
+mod html;
+mod images;
 mod layout;
 mod line_numbers;
 mod links;
@@ -269,7 +305,11 @@ mod margins;
 
 pub use callouts::{CalloutStyle, CalloutStyleConfig, CheckboxShape, PrettyDefinitionStyle};
 pub use code_blocks::{CodeBlockStyle, CodeBlockStyleConfig, CodeWrapIndent};
-pub use commands::CliCommand;
+pub use commands::{CliCommand, TocArgs, TocFormat};
+pub use export::ExportFormat;
+pub use format::OutputFormat;
+pub use html::HtmlMode;
+pub use images::ImageMode;
 pub use layout::{HeadingLayout, TableWrapMode, TextWrapMode};
 pub use line_numbers::{LineNumberOptions, LineNumberTarget};
 pub use links::{FootnoteStyle, LinkStyle, LinkTruncationStyle, MissingFootnoteStyle};
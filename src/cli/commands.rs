@@ -1,7 +1,31 @@
 use super::*;
 
-#[derive(Debug, Clone, Copy, Subcommand)]
+#[derive(Debug, Clone, Subcommand)]
 pub enum CliCommand {
     /// Show the full help in the built-in pager
     Help,
+    /// Print the heading outline of a document with anchor slugs
+    Toc(TocArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TocArgs {
+    /// Path to markdown file (use '-' for stdin)
+    #[arg(value_name = "FILE")]
+    pub filename: Option<String>,
+
+    /// Output format of the outline
+    #[arg(long = "format", value_enum, default_value = "plain")]
+    pub format: TocFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TocFormat {
+    /// Indented heading tree with slugs
+    #[default]
+    Plain,
+    /// Nested Markdown list of anchor links
+    Markdown,
+    /// Nested JSON array of headings
+    Json,
 }
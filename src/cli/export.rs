@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    #[value(help = "HTML page that reproduces the rendered terminal output")]
+    AnsiHtml,
+    #[value(help = "SVG image of the rendered terminal output")]
+    Svg,
+}
@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[value(help = "Block and inline tree of the preprocessed document with source lines")]
+    Json,
+    #[value(help = "Rendered layout without escape sequences, with links listed at the end")]
+    Text,
+}
@@ -1,5 +1,9 @@
 pub(super) const LINE_NUMBERS_LONG_HELP: &str = "Show row numbers in terminal and pager output\nWithout a value, number every rendered row without a separator\n\nPossible values:\n- source:    Number physical Markdown source lines instead of rendered rows\n- separator: Display a separator after each rendered row number\n\nExamples:\n  --line-numbers separator\n  --line-numbers source\n  --line-numbers \"source;separator\"";
 
+pub(super) const HTML_LONG_HELP: &str = "Print HTML version instead of terminal formatting\nWithout a value, print a bare HTML fragment\n\nPossible values:\n- fragment:   HTML fragment of the document body\n- standalone: Self-contained page with CSS from the active theme, highlighted\n              code, callout boxes and a table of contents";
+
+pub(super) const IMAGES_LONG_HELP: &str = "Draw local PNG, JPEG and GIF images inline instead of showing text markers\nWithout a value, detect the Kitty, iTerm2 or Sixel protocol and fall back to\nUnicode half-blocks; remote images and unsupported formats keep their markers\nThe pager and interactive mode always use half-blocks\n\nPossible values:\n- auto:   Detect the graphics protocol supported by the terminal\n- kitty:  Kitty graphics protocol (kitty, Ghostty)\n- iterm:  iTerm2 inline images (iTerm2, WezTerm)\n- sixel:  Sixel graphics (foot, mlterm, xterm -ti vt340)\n- blocks: Unicode half-block characters in true color\n- off:    Show text markers such as [IMAGE] instead of pictures";
+
 pub(super) const CODE_LINE_NUMBERS_LONG_HELP: &str = "Number rows inside code blocks\nWithout a value, number every wrapped terminal row without a separator\n\nPossible values:\n- source:    Number physical code lines instead of wrapped terminal rows\n- separator: Display a separator after each code line number\n\nExamples:\n  --code-line-numbers separator\n  --code-line-numbers source\n  --code-line-numbers \"source;separator\"";
 
 pub(super) const SYNTAXES_DIR_LONG_HELP: &str = "Directory containing custom .sublime-syntax files\nFiles are loaded recursively on top of the embedded syntax set\nCustom entries take precedence";
@@ -0,0 +1,10 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum HtmlMode {
+    #[default]
+    #[value(help = "HTML fragment of the document body")]
+    Fragment,
+    #[value(help = "Self-contained page styled with the active theme")]
+    Standalone,
+}
@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImageMode {
+    #[default]
+    #[value(help = "Show text markers such as [IMAGE] instead of pictures")]
+    Off,
+    #[value(help = "Detect the graphics protocol supported by the terminal")]
+    Auto,
+    #[value(help = "Kitty graphics protocol (kitty, Ghostty)")]
+    Kitty,
+    #[value(help = "iTerm2 inline images (iTerm2, WezTerm)")]
+    Iterm,
+    #[value(help = "Sixel graphics (foot, mlterm, xterm -ti vt340)")]
+    Sixel,
+    #[value(help = "Unicode half-block characters in true color")]
+    Blocks,
+}
+
+impl ImageMode {
+    /// Adjusts the mode to where the output goes.
+    ///
+    /// The pager redraws its screen from text, so graphics protocols give way to
+    /// half-blocks there; detection is skipped when stdout is not a terminal.
+    pub(crate) fn for_output(self, paged: bool, terminal: bool) -> Self {
+        match self {
+            Self::Off => Self::Off,
+            _ if paged => Self::Blocks,
+            Self::Auto if !terminal => Self::Off,
+            mode => mode,
+        }
+    }
+}
@@ -64,6 +64,9 @@ fn help_groups_options_in_task_oriented_order() {
         "Output and flow:",
         "--pager",
         "--interactive",
+        "--split",
+        "--diff",
+        "--diff-git",
         "--monitor",
         "--from",
         "--reverse",
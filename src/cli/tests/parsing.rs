@@ -150,3 +150,29 @@ fn interactive_flag_parses() {
 fn interactive_conflicts_with_pager() {
     assert!(Cli::try_parse_from(["mdv", "--interactive", "--pager"]).is_err());
 }
+
+#[test]
+fn split_flag_parses_and_conflicts_with_other_outputs() {
+    let cli = Cli::parse_from(["mdv", "--split", "README.md"]);
+    assert!(cli.split);
+
+    assert!(Cli::try_parse_from(["mdv", "--split", "--interactive"]).is_err());
+    assert!(Cli::try_parse_from(["mdv", "--split", "--html"]).is_err());
+}
+
+#[test]
+fn diff_flags_take_the_old_version_and_need_a_document() {
+    let cli = Cli::parse_from(["mdv", "--diff", "old.md", "new.md"]);
+    assert_eq!(cli.diff.as_deref(), Some("old.md"));
+    assert_eq!(cli.filename.as_deref(), Some("new.md"));
+
+    let cli = Cli::parse_from(["mdv", "--diff-git", "HEAD~1", "--", "README.md"]);
+    assert_eq!(cli.diff_git.as_deref(), Some("HEAD~1"));
+    assert_eq!(cli.filename.as_deref(), Some("README.md"));
+
+    assert!(Cli::try_parse_from(["mdv", "--diff", "old.md"]).is_err());
+    assert!(
+        Cli::try_parse_from(["mdv", "--diff", "old.md", "--diff-git", "HEAD", "new.md"]).is_err()
+    );
+    assert!(Cli::try_parse_from(["mdv", "--diff-git", "HEAD", "--split", "new.md"]).is_err());
+}
@@ -0,0 +1,259 @@
+use crate::config::Config;
+use crate::editor::split_command;
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Length of the pieces GNU screen passes through one DCS string each.
+const SCREEN_CHUNK_LENGTH: usize = 76;
+
+/// Commands tried in auto mode, each when its display variable is set.
+const COPY_COMMANDS: &[(&str, &str)] = &[
+    ("WAYLAND_DISPLAY", "wl-copy"),
+    ("DISPLAY", "xclip -selection clipboard"),
+    ("DISPLAY", "xsel --clipboard --input"),
+];
+
+/// Where copied text is sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardMode {
+    /// Pick a backend from the environment.
+    #[default]
+    Auto,
+    /// System clipboard of the local machine.
+    System,
+    /// OSC 52 sequence that asks the terminal to set its clipboard.
+    Osc52,
+    /// External program that reads the text on standard input.
+    Command,
+}
+
+/// Backend that took the copied text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClipboardBackend {
+    System,
+    Osc52,
+    /// External program, by its file name.
+    Command(String),
+}
+
+impl fmt::Display for ClipboardBackend {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => formatter.write_str("system clipboard"),
+            Self::Osc52 => formatter.write_str("OSC 52"),
+            Self::Command(program) => formatter.write_str(program),
+        }
+    }
+}
+
+/// Terminal multiplexer that OSC 52 sequences have to pass through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    fn detect() -> Option<Self> {
+        if env::var_os("TMUX").is_some() {
+            Some(Self::Tmux)
+        } else if env::var_os("STY").is_some() {
+            Some(Self::Screen)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Clipboard {
+    mode: ClipboardMode,
+    command: Option<String>,
+}
+
+impl Clipboard {
+    pub(crate) fn from_config(config: &Config) -> Self {
+        Self {
+            mode: config.clipboard,
+            command: config
+                .clipboard_command
+                .as_deref()
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// Copies `text` and returns the backend that took it.
+    pub(crate) fn copy(&self, text: &str) -> Result<ClipboardBackend> {
+        match self.mode {
+            ClipboardMode::Auto => self.copy_auto(text),
+            ClipboardMode::System => copy_system(text),
+            ClipboardMode::Osc52 => copy_osc52(text),
+            ClipboardMode::Command => {
+                let command = self
+                    .command
+                    .as_deref()
+                    .context("clipboard is set to command but clipboard_command is empty")?;
+                run_command(command, text)
+            }
+        }
+    }
+
+    /// Uses the configured command when there is one. Otherwise a remote session goes
+    /// straight to OSC 52, because its system clipboard is not the user's; a local one
+    /// tries the system clipboard and the known copy commands first.
+    fn copy_auto(&self, text: &str) -> Result<ClipboardBackend> {
+        if let Some(command) = &self.command {
+            return run_command(command, text);
+        }
+        if !is_remote_session() {
+            if let Ok(backend) = copy_system(text) {
+                return Ok(backend);
+            }
+            let detected = COPY_COMMANDS
+                .iter()
+                .filter(|(variable, _)| env::var_os(variable).is_some())
+                .find_map(|(_, command)| run_command(command, text).ok());
+            if let Some(backend) = detected {
+                return Ok(backend);
+            }
+        }
+        copy_osc52(text)
+    }
+}
+
+fn is_remote_session() -> bool {
+    env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some()
+}
+
+fn copy_system(text: &str) -> Result<ClipboardBackend> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .context("System clipboard is unavailable")?;
+    Ok(ClipboardBackend::System)
+}
+
+fn copy_osc52(text: &str) -> Result<ClipboardBackend> {
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(osc52_sequence(text, Multiplexer::detect()).as_bytes())
+        .and_then(|()| stdout.flush())
+        .context("Failed to write clipboard sequence")?;
+    Ok(ClipboardBackend::Osc52)
+}
+
+/// OSC 52 sequence for `text`, wrapped so that the multiplexer passes it on to the
+/// outer terminal.
+fn osc52_sequence(text: &str, multiplexer: Option<Multiplexer>) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    match multiplexer {
+        None => sequence,
+        Some(Multiplexer::Tmux) => {
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        }
+        // The sequence is ASCII, so byte chunks are valid strings.
+        Some(Multiplexer::Screen) => sequence
+            .as_bytes()
+            .chunks(SCREEN_CHUNK_LENGTH)
+            .map(|chunk| format!("\x1bP{}\x1b\\", String::from_utf8_lossy(chunk)))
+            .collect(),
+    }
+}
+
+fn run_command(command: &str, text: &str) -> Result<ClipboardBackend> {
+    let args = split_command(command)
+        .filter(|args| !args.is_empty())
+        .with_context(|| format!("Invalid clipboard command: {command}"))?;
+    let program = Path::new(&args[0]).file_name().map_or_else(
+        || args[0].clone(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let mut child = Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to {program}"))?;
+    }
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for {program}"))?;
+    if !status.success() {
+        bail!("{program} exited with {status}");
+    }
+    Ok(ClipboardBackend::Command(program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_sequences_pass_through_multiplexers() {
+        assert_eq!(osc52_sequence("hi", None), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", Some(Multiplexer::Tmux)),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+
+        let text = "x".repeat(100);
+        let screen = osc52_sequence(&text, Some(Multiplexer::Screen));
+        let plain = osc52_sequence(&text, None);
+        let chunks: Vec<&str> = screen
+            .split("\x1b\\")
+            .filter(|chunk| !chunk.is_empty())
+            .collect();
+        assert_eq!(chunks.len(), plain.len().div_ceil(SCREEN_CHUNK_LENGTH));
+        assert!(chunks.iter().all(|chunk| chunk.starts_with("\x1bP")));
+        assert_eq!(
+            chunks.iter().map(|chunk| &chunk[2..]).collect::<String>(),
+            plain
+        );
+    }
+
+    #[test]
+    fn command_mode_reports_the_program_and_its_failures() {
+        let clipboard = |command: Option<&str>| Clipboard {
+            mode: ClipboardMode::Command,
+            command: command.map(str::to_string),
+        };
+
+        assert!(clipboard(None).copy("text").is_err());
+        #[cfg(unix)]
+        {
+            assert_eq!(
+                clipboard(Some("/bin/sh -c 'cat > /dev/null'"))
+                    .copy("text")
+                    .unwrap(),
+                ClipboardBackend::Command("sh".to_string())
+            );
+            let error = clipboard(Some("sh -c 'exit 3'")).copy("text").unwrap_err();
+            assert!(error.to_string().starts_with("sh exited with"));
+        }
+    }
+
+    #[test]
+    fn clipboard_settings_parse_from_yaml() {
+        let config: Config =
+            serde_yaml::from_str("clipboard: osc52\nclipboard_command: \" wl-copy \"\n").unwrap();
+        let clipboard = Clipboard::from_config(&config);
+
+        assert_eq!(clipboard.mode, ClipboardMode::Osc52);
+        assert_eq!(clipboard.command.as_deref(), Some("wl-copy"));
+        assert!(serde_yaml::from_str::<Config>("clipboard: pasteboard\n").is_err());
+    }
+}
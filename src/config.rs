@@ -2,13 +2,16 @@ use crate::block_spacing::BlockSpacingOverrides;
 use crate::callout::{CustomCalloutStyle, parse_custom_callouts};
 use crate::cli::{
     CalloutStyleConfig, CheckboxShape, Cli, CodeBlockStyleConfig, CodeWrapIndent, FootnoteStyle,
-    FrontMatterMode, HeadingLayout, HorizontalMargins, LineNumberOptions, LineNumberTarget,
-    LinkStyle, LinkTruncationStyle, MissingFootnoteStyle, PrettyDefinitionStyle, TableWrapMode,
-    TextWrapMode,
+    FrontMatterMode, HeadingLayout, HorizontalMargins, ImageMode, LineNumberOptions,
+    LineNumberTarget, LinkStyle, LinkTruncationStyle, MissingFootnoteStyle, PrettyDefinitionStyle,
+    TableWrapMode, TextWrapMode,
 };
+use crate::clipboard::ClipboardMode;
 use crate::custom_code_block::{CustomCodeBlock, parse_custom_code_blocks};
 use crate::error::MdvError;
 use crate::inline_style::InlineStyleOverrides;
+use crate::interactive::browser::{BrowserSort, BrowserView};
+use crate::keybindings::KeyBindingOverrides;
 use crate::list_marker::{ListMarkerConfig, PrettyListStyle, UniformListMarker};
 use crate::preset;
 use anyhow::Result;
@@ -147,6 +150,19 @@ pub struct Config {
     pub hide_comments: bool,
     pub front_matter: FrontMatterMode,
     pub render_html: bool,
+    pub images: ImageMode,
+    /// Directory of the rendered document, used to resolve relative image paths.
+    #[serde(skip)]
+    pub(crate) document_dir: Option<PathBuf>,
+    /// Records the source line of every rendered line for the pager's source search.
+    #[serde(skip)]
+    pub(crate) source_map: bool,
+    /// Renders the document beside its Markdown source for `--split`.
+    #[serde(skip)]
+    pub(crate) split_view: bool,
+    /// Old version of the document that `--diff` and `--diff-git` compare against.
+    #[serde(skip)]
+    pub(crate) diff_base: Option<String>,
     #[serde(
         default,
         deserialize_with = "deserialize_line_numbers",
@@ -213,6 +229,20 @@ pub struct Config {
 
     // Content filtering
     pub from_text: Option<String>,
+    pub section: Option<String>,
+
+    // Interactive key bindings
+    pub keybindings: KeyBindingOverrides,
+
+    // Interactive browser
+    pub browser_preview: bool,
+    pub browser_metadata: Vec<String>,
+    pub browser_sort: BrowserSort,
+    pub browser_view: BrowserView,
+
+    // Clipboard
+    pub clipboard: ClipboardMode,
+    pub clipboard_command: Option<String>,
 
     // File paths
     #[serde(skip)]
@@ -223,6 +253,11 @@ pub struct Config {
     pub config_dir: Option<PathBuf>,
 }
 
+/// Front matter keys shown on browser cards when the config names none.
+fn default_browser_metadata() -> Vec<String> {
+    ["tags", "status", "owner"].map(String::from).into()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -244,6 +279,11 @@ impl Default for Config {
             hide_comments: false,
             front_matter: FrontMatterMode::Hidden,
             render_html: false,
+            images: ImageMode::Off,
+            document_dir: None,
+            source_map: false,
+            split_view: false,
+            diff_base: None,
             line_numbers: None,
             code_line_numbers: None,
             code_line_number_width: 0,
@@ -278,6 +318,14 @@ impl Default for Config {
             footnote_style: FootnoteStyle::Endnotes,
             missing_footnote_style: MissingFootnoteStyle::Show,
             from_text: None,
+            section: None,
+            keybindings: KeyBindingOverrides::default(),
+            browser_preview: false,
+            browser_metadata: default_browser_metadata(),
+            browser_sort: BrowserSort::Path,
+            browser_view: BrowserView::List,
+            clipboard: ClipboardMode::Auto,
+            clipboard_command: None,
             config_file: None,
             config_dir: None,
         }
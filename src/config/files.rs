@@ -1,4 +1,5 @@
 use super::*;
+use anyhow::Context;
 
 impl Config {
     pub(crate) fn write_default_config(cli: &Cli, matches: &ArgMatches) -> Result<PathBuf> {
@@ -62,7 +63,9 @@ impl Config {
                         break;
                     }
                     Err(e) => {
-                        log::warn!("Failed to load config from {:?}: {}", path, e);
+                        // A bad chord or conflict stops mdv; other errors leave the defaults.
+                        Self::check_keybindings(&path)?;
+                        eprintln!("Warning: ignoring config file: {e:#}");
                     }
                 }
             }
@@ -100,14 +103,80 @@ impl Config {
         Ok(paths)
     }
 
+    /// Sets the top-level `key` of the config file at `path` to `value`, keeping the other
+    /// lines and the comment after the old value; a missing key is appended.
+    pub(crate) fn save_value(path: &Path, key: &str, value: impl Serialize) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value = serde_yaml::to_string(&value)?;
+        let value = value.trim_end();
+        let mut found = false;
+        let mut lines: Vec<String> = content
+            .lines()
+            .map(|line| {
+                match line
+                    .strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix(':'))
+                {
+                    Some(rest) if !found => {
+                        found = true;
+                        replace_value(key, rest, value)
+                    }
+                    _ => line.to_string(),
+                }
+            })
+            .collect();
+        if !found {
+            lines.push(format!("{key}: {value}"));
+        }
+        std::fs::write(path, lines.join("\n") + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Validates the `keybindings` section on its own, so that an invalid binding is
+    /// reported even when the rest of the file is ignored.
+    fn check_keybindings(path: &Path) -> Result<()> {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Ok(());
+        };
+        let Ok(serde_yaml::Value::Mapping(mut file)) = serde_yaml::from_str(&content) else {
+            return Ok(());
+        };
+        let Some(bindings) = file.remove("keybindings") else {
+            return Ok(());
+        };
+        KeyBindingOverrides::deserialize(bindings).map_err(|error| {
+            anyhow::Error::from(MdvError::ConfigParseError(format!(
+                "Invalid keybindings in config file {}: {error}",
+                path.display()
+            )))
+        })?;
+        Ok(())
+    }
+
     pub(super) fn load_from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
 
-        serde_yaml::from_str::<Self>(&content).map_err(|_| {
+        serde_yaml::from_str::<Self>(&content).map_err(|error| {
             anyhow::Error::from(MdvError::ConfigParseError(format!(
-                "Failed to parse YAML config file: {}",
+                "Failed to parse YAML config file {}: {error}",
                 path.display()
             )))
         })
     }
 }
+
+/// `key: value` line that keeps the comment of the old value at its column.
+fn replace_value(key: &str, rest: &str, value: &str) -> String {
+    let line = format!("{key}: {value}");
+    let old_value = rest.trim_start();
+    let comment = old_value
+        .find(char::is_whitespace)
+        .map_or("", |end| old_value[end..].trim_start());
+    if comment.is_empty() {
+        return line;
+    }
+    let column = key.len() + 1 + rest.len() - comment.len();
+    let padding = column.saturating_sub(line.len()).max(1);
+    format!("{line}{}{comment}", " ".repeat(padding))
+}
@@ -171,6 +171,10 @@ impl Config {
             config.render_html = true;
         }
 
+        if let Some(mode) = cli.images {
+            config.images = mode;
+        }
+
         if let Some(options) = cli.line_numbers {
             config.line_numbers = Some(options.unwrap_or_default());
         }
@@ -239,6 +243,12 @@ impl Config {
             config.from_text = Some(from_text.clone());
         }
 
+        if let Some(section) = &cli.section
+            && arg_has_user_value(matches, "section")
+        {
+            config.section = Some(section.clone());
+        }
+
         if cli.reverse {
             config.reverse = true;
         }
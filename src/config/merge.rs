@@ -63,6 +63,9 @@ impl Config {
         if other.render_html {
             self.render_html = true;
         }
+        if !matches!(other.images, ImageMode::Off) {
+            self.images = other.images;
+        }
         if other.line_numbers.is_some() {
             self.line_numbers = other.line_numbers;
         }
@@ -155,6 +158,35 @@ impl Config {
             self.from_text = other.from_text;
         }
 
+        if other.section.is_some() {
+            self.section = other.section;
+        }
+        self.keybindings.merge(&other.keybindings);
+
+        if other.browser_preview {
+            self.browser_preview = true;
+        }
+
+        if other.browser_metadata != default_browser_metadata() {
+            self.browser_metadata.clone_from(&other.browser_metadata);
+        }
+
+        if other.browser_sort != BrowserSort::Path {
+            self.browser_sort = other.browser_sort;
+        }
+
+        if other.browser_view != BrowserView::List {
+            self.browser_view = other.browser_view;
+        }
+
+        if !matches!(other.clipboard, ClipboardMode::Auto) {
+            self.clipboard = other.clipboard;
+        }
+
+        if other.clipboard_command.is_some() {
+            self.clipboard_command = other.clipboard_command;
+        }
+
         if other.reverse {
             self.reverse = true;
         }
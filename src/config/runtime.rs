@@ -41,10 +41,11 @@ impl Config {
     }
 
     pub(crate) fn source_line_numbers_enabled(&self) -> bool {
-        matches!(
-            self.line_numbers,
-            Some(options) if options.target == LineNumberTarget::Source
-        )
+        self.source_map
+            || matches!(
+                self.line_numbers,
+                Some(options) if options.target == LineNumberTarget::Source
+            )
     }
 
     pub fn validate_horizontal_margins(&self) -> Result<()> {
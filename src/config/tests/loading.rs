@@ -90,6 +90,51 @@ fn config_rejects_legacy_pretty_list_boolean() {
     assert!(Config::load_from_file(&config_path).is_err());
 }
 
+#[test]
+fn invalid_config_file_fails_loading_with_the_parse_error() {
+    let _env_lock = env_lock();
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, "keybindings:\n  pager:\n    copy: r\n")
+        .expect("write config file");
+
+    let (cli, matches) = parse_cli_from(vec![
+        OsString::from("mdv"),
+        OsString::from("--config-file"),
+        temp_dir.path().as_os_str().to_owned(),
+    ]);
+
+    let error = Config::from_cli(&cli, &matches)
+        .expect_err("invalid config should fail")
+        .to_string();
+    assert!(
+        error.contains("key 'r' is bound to both 'copy' and 'reload'"),
+        "{error}"
+    );
+}
+
+#[test]
+fn config_file_with_other_parse_errors_falls_back_to_defaults() {
+    let _env_lock = env_lock();
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(
+        &config_path,
+        "no_colors: true\npretty_list: true\nkeybindings:\n  pager:\n    copy: c\n",
+    )
+    .expect("write config file");
+
+    let (cli, matches) = parse_cli_from(vec![
+        OsString::from("mdv"),
+        OsString::from("--config-file"),
+        temp_dir.path().as_os_str().to_owned(),
+    ]);
+
+    let config = Config::from_cli(&cli, &matches).expect("load config");
+    assert!(!config.no_colors);
+    assert_eq!(config.config_file, None);
+}
+
 #[test]
 fn config_accepts_pretty_list_style_and_uniform_marker() {
     let _env_lock = env_lock();
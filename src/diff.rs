@@ -0,0 +1,263 @@
+use std::ops::Range;
+
+mod document;
+mod git;
+
+pub(crate) use document::{DiffKind, DocumentDiff};
+pub(crate) use git::read_git_revision;
+
+/// Largest product of the differing middle lengths compared item by item; longer
+/// middles are reported as one change instead.
+const MAX_COMPARISONS: usize = 4_000_000;
+
+/// Stretch of two sequences that is either equal on both sides or differs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DiffRun {
+    Same {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+    /// Items of `old` replaced by items of `new`; either side may be empty.
+    Changed {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+}
+
+/// Compares `old` with `new` by their longest common subsequence.
+pub(crate) fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffRun> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+    let mut runs = RunBuilder::default();
+    runs.same(0..prefix, 0..prefix);
+    let middle_old = &old[prefix..old_end];
+    let middle_new = &new[prefix..new_end];
+    if middle_old.len().saturating_mul(middle_new.len()) > MAX_COMPARISONS {
+        runs.changed(prefix..old_end, prefix..new_end);
+    } else {
+        let (mut old_start, mut new_start) = (prefix, prefix);
+        for (old_index, new_index) in common_subsequence(middle_old, middle_new) {
+            let (old_index, new_index) = (old_index + prefix, new_index + prefix);
+            runs.changed(old_start..old_index, new_start..new_index);
+            runs.same(old_index..old_index + 1, new_index..new_index + 1);
+            (old_start, new_start) = (old_index + 1, new_index + 1);
+        }
+        runs.changed(old_start..old_end, new_start..new_end);
+    }
+    runs.same(old_end..old.len(), new_end..new.len());
+    runs.runs
+}
+
+/// Index in the new sequence of the item at `old` in the old one. Changed items map to
+/// the start of their replacement.
+pub(crate) fn follow(runs: &[DiffRun], old: usize) -> usize {
+    for run in runs {
+        match run {
+            DiffRun::Same { old: range, new } if range.contains(&old) => {
+                return new.start + (old - range.start);
+            }
+            DiffRun::Changed { old: range, new } if range.contains(&old) => return new.start,
+            _ => {}
+        }
+    }
+    runs.last().map_or(old, |run| match run {
+        DiffRun::Same { new, .. } | DiffRun::Changed { new, .. } => new.end,
+    })
+}
+
+/// Index pairs of a longest common subsequence, in order.
+fn common_subsequence<T: PartialEq>(old: &[T], new: &[T]) -> Vec<(usize, usize)> {
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for old_index in (0..old.len()).rev() {
+        for new_index in (0..new.len()).rev() {
+            lengths[old_index * width + new_index] = if old[old_index] == new[new_index] {
+                lengths[(old_index + 1) * width + new_index + 1] + 1
+            } else {
+                lengths[(old_index + 1) * width + new_index]
+                    .max(lengths[old_index * width + new_index + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut old_index, mut new_index) = (0, 0);
+    while old_index < old.len() && new_index < new.len() {
+        if old[old_index] == new[new_index] {
+            pairs.push((old_index, new_index));
+            old_index += 1;
+            new_index += 1;
+        } else if lengths[(old_index + 1) * width + new_index]
+            >= lengths[old_index * width + new_index + 1]
+        {
+            old_index += 1;
+        } else {
+            new_index += 1;
+        }
+    }
+    pairs
+}
+
+/// Collects runs, merging neighbours of the same kind and dropping empty ones.
+#[derive(Default)]
+struct RunBuilder {
+    runs: Vec<DiffRun>,
+}
+
+impl RunBuilder {
+    fn same(&mut self, old: Range<usize>, new: Range<usize>) {
+        if old.is_empty() {
+            return;
+        }
+        if let Some(DiffRun::Same {
+            old: last_old,
+            new: last_new,
+        }) = self.runs.last_mut()
+        {
+            last_old.end = old.end;
+            last_new.end = new.end;
+        } else {
+            self.runs.push(DiffRun::Same { old, new });
+        }
+    }
+
+    fn changed(&mut self, old: Range<usize>, new: Range<usize>) {
+        if old.is_empty() && new.is_empty() {
+            return;
+        }
+        if let Some(DiffRun::Changed {
+            old: last_old,
+            new: last_new,
+        }) = self.runs.last_mut()
+        {
+            last_old.end = old.end;
+            last_new.end = new.end;
+        } else {
+            self.runs.push(DiffRun::Changed { old, new });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::markdown::MarkdownProcessor;
+
+    #[test]
+    fn runs_cover_both_sequences_in_order() {
+        let old = ["a", "b", "c", "d", "e"];
+        let new = ["a", "x", "c", "e", "f"];
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                DiffRun::Same {
+                    old: 0..1,
+                    new: 0..1
+                },
+                DiffRun::Changed {
+                    old: 1..2,
+                    new: 1..2
+                },
+                DiffRun::Same {
+                    old: 2..3,
+                    new: 2..3
+                },
+                DiffRun::Changed {
+                    old: 3..4,
+                    new: 3..3
+                },
+                DiffRun::Same {
+                    old: 4..5,
+                    new: 3..4
+                },
+                DiffRun::Changed {
+                    old: 5..5,
+                    new: 4..5
+                },
+            ]
+        );
+        assert!(
+            diff(&old, &old)
+                .iter()
+                .all(|run| matches!(run, DiffRun::Same { .. }))
+        );
+    }
+
+    #[test]
+    fn document_diff_keeps_edited_table_rows_inside_their_table() {
+        let processor = MarkdownProcessor::new(&Config::default());
+        let old = "| a | b |\n|---|---|\n| 1 | 2 |\n\nGone.\n";
+        let new = "| a | b |\n|---|---|\n| 1 | 3 |\n";
+        let document = DocumentDiff::new(&processor, old, new);
+
+        assert_eq!(
+            document.source,
+            "| a | b |\n|---|---|\n| 1 | 2 |\n| 1 | 3 |\n\nGone.\n"
+        );
+        assert_eq!(
+            document.kinds,
+            vec![
+                None,
+                None,
+                Some(DiffKind::Deleted),
+                Some(DiffKind::Modified),
+                None,
+                Some(DiffKind::Deleted),
+            ]
+        );
+        assert_eq!(
+            document.new_lines,
+            vec![Some(1), Some(2), None, Some(3), None, None]
+        );
+    }
+
+    #[test]
+    fn document_diff_marks_unpaired_blocks_of_a_run_as_added() {
+        let processor = MarkdownProcessor::new(&Config::default());
+        let old = "Intro.\n\nOld text.\n";
+        let new = "Intro.\n\nNew text.\n\nNew.\n";
+        let document = DocumentDiff::new(&processor, old, new);
+
+        assert_eq!(
+            document.source,
+            "Intro.\n\nOld text.\n\nNew text.\n\nNew.\n"
+        );
+        assert_eq!(
+            document.kinds,
+            vec![
+                None,
+                None,
+                Some(DiffKind::Deleted),
+                None,
+                Some(DiffKind::Modified),
+                None,
+                Some(DiffKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn follow_maps_old_items_past_insertions_and_deletions() {
+        let old = ["intro", "body", "end"];
+        let new = ["added", "added", "intro", "end"];
+        let runs = diff(&old, &new);
+
+        assert_eq!(follow(&runs, 0), 2);
+        assert_eq!(follow(&runs, 1), 3);
+        assert_eq!(follow(&runs, 2), 3);
+        assert_eq!(follow(&runs, 9), 4);
+    }
+}
@@ -0,0 +1,96 @@
+use super::{DiffRun, diff};
+use crate::markdown::{MarkdownProcessor, SourceBlock};
+
+/// How a line of a compared document differs from the old version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiffKind {
+    Added,
+    Deleted,
+    Modified,
+}
+
+/// New version of a document with the blocks removed from the old version put back
+/// where they were, and every line tagged with how it changed.
+#[derive(Debug, Default)]
+pub(crate) struct DocumentDiff {
+    pub(crate) source: String,
+    /// Change of each line of `source`; `None` for unchanged lines.
+    pub(crate) kinds: Vec<Option<DiffKind>>,
+    /// Line of the new version, counted from 1, for each line of `source`.
+    pub(crate) new_lines: Vec<Option<usize>>,
+    /// Whether the last block may be continued by a directly following line of text.
+    open_ended: bool,
+    /// Version and line that directly follow the last block in its own document.
+    next_line: Option<(bool, usize)>,
+}
+
+impl DocumentDiff {
+    /// Compares `old` with `new` block by block over their parsed events.
+    pub(crate) fn new(processor: &MarkdownProcessor, old: &str, new: &str) -> Self {
+        let old_blocks = processor.source_blocks(old);
+        let new_blocks = processor.source_blocks(new);
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+
+        let mut document = Self::default();
+        for run in diff(&old_blocks, &new_blocks) {
+            match run {
+                DiffRun::Same { new, .. } => {
+                    for block in &new_blocks[new] {
+                        document.push_block(block, &new_lines, None, true);
+                    }
+                }
+                DiffRun::Changed { old, new } => {
+                    // Pairing old and new blocks keeps an edited table row or list
+                    // item inside its table or list. Blocks past the shorter side
+                    // have no partner, so they were added or deleted.
+                    for index in 0..old.len().max(new.len()) {
+                        if let Some(block) = old_blocks[old.clone()].get(index) {
+                            document.push_block(block, &old_lines, Some(DiffKind::Deleted), false);
+                        }
+                        if let Some(block) = new_blocks[new.clone()].get(index) {
+                            let kind = if index < old.len() {
+                                DiffKind::Modified
+                            } else {
+                                DiffKind::Added
+                            };
+                            document.push_block(block, &new_lines, Some(kind), true);
+                        }
+                    }
+                }
+            }
+        }
+        document
+    }
+
+    fn push_block(
+        &mut self,
+        block: &SourceBlock,
+        lines: &[&str],
+        kind: Option<DiffKind>,
+        from_new: bool,
+    ) {
+        let is_blank = |line: usize| lines.get(line).is_none_or(|line| line.trim().is_empty());
+        let adjacent = self.next_line == Some((from_new, block.lines.start));
+        let blank_before = block.lines.start > 0 && is_blank(block.lines.start - 1);
+        if !self.source.is_empty() && (blank_before || (self.open_ended && !adjacent)) {
+            self.push_line("", None, None);
+        }
+        let mut end = block.lines.end;
+        while end > block.lines.start && is_blank(end - 1) {
+            end -= 1;
+        }
+        for (line, text) in lines[..end].iter().enumerate().skip(block.lines.start) {
+            self.push_line(text, kind, from_new.then_some(line + 1));
+        }
+        self.open_ended = block.open_ended && end == block.lines.end;
+        self.next_line = Some((from_new, block.lines.end));
+    }
+
+    fn push_line(&mut self, text: &str, kind: Option<DiffKind>, new_line: Option<usize>) {
+        self.source.push_str(text);
+        self.source.push('\n');
+        self.kinds.push(kind);
+        self.new_lines.push(new_line);
+    }
+}
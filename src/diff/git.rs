@@ -0,0 +1,32 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Reads `file` as it was at `revision` with the local `git` binary.
+pub(crate) fn read_git_revision(revision: &str, file: &str) -> Result<String> {
+    let path = Path::new(file);
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .with_context(|| format!("Not a file path: {file}"))?;
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .arg("show")
+        .arg(format!("{revision}:./{}", name.to_string_lossy()))
+        .output()
+        .with_context(|| format!("Failed to read {revision}:{file} with git"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read {revision}:{file} with git: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut content = String::from_utf8(output.stdout)
+        .with_context(|| format!("{revision}:{file} is not valid UTF-8"))?;
+    crate::strip_leading_bom(&mut content);
+    Ok(content)
+}
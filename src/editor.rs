@@ -273,12 +273,12 @@ impl EditorCommand {
 }
 
 #[cfg(windows)]
-fn split_command(raw: &str) -> Option<Vec<String>> {
+pub(crate) fn split_command(raw: &str) -> Option<Vec<String>> {
     Some(winsplit::split(raw))
 }
 
 #[cfg(not(windows))]
-fn split_command(raw: &str) -> Option<Vec<String>> {
+pub(crate) fn split_command(raw: &str) -> Option<Vec<String>> {
     shell_words::split(raw).ok()
 }
 
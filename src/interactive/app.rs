@@ -1,5 +1,7 @@
 use super::browser::{BrowserState, FilterState};
+use super::preview::{PreviewPane, preview_layout};
 use crate::config::Config;
+use crate::keybindings::{KeyAction, KeyBindings, KeyScope};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
 use std::path::PathBuf;
@@ -7,7 +9,9 @@ use std::path::PathBuf;
 pub(super) enum AppAction {
     None,
     Quit,
-    OpenPager(PathBuf),
+    /// Documents to open as pager tabs, the index of the one to show first, and the
+    /// source line to start it at.
+    OpenPager(Vec<PathBuf>, usize, Option<usize>),
     OpenEditor(PathBuf),
     Suspend,
 }
@@ -15,6 +19,10 @@ pub(super) enum AppAction {
 pub(super) struct App {
     pub(super) config: Config,
     pub(super) browser: BrowserState,
+    pub(super) keys: KeyBindings,
+    pub(super) preview: PreviewPane,
+    /// Whether the preview pane is on; it still needs a wide enough terminal.
+    pub(super) show_preview: bool,
     pub(super) width: u16,
     pub(super) height: u16,
 }
@@ -22,19 +30,50 @@ pub(super) struct App {
 impl App {
     pub(super) fn new(root: PathBuf, config: Config, width: u16, height: u16) -> Self {
         Self {
+            keys: config.keybindings.resolve(KeyScope::Browser),
+            preview: PreviewPane::new(&config),
+            show_preview: config.browser_preview,
+            browser: BrowserState::new(
+                root,
+                height,
+                config.browser_sort,
+                config.browser_view,
+                config.config_file.clone(),
+            ),
             config,
-            browser: BrowserState::new(root, height),
             width,
             height,
         }
     }
 
-    pub(super) fn tick(&mut self) {
+    /// Polls the background work. Returns whether the watcher changed the list.
+    pub(super) fn tick(&mut self) -> bool {
         self.browser.poll_discovery();
+        let watched = self.browser.poll_watcher();
+        self.browser.poll_content_search();
+        let selection = self
+            .preview_layout()
+            .and_then(|(_, width)| Some((self.browser.selected_document()?, width)));
+        self.preview.select(selection);
+        self.preview.poll();
+        watched
     }
 
     pub(super) fn is_loading(&self) -> bool {
         !self.browser.is_loaded()
+            || self.browser.is_searching_contents()
+            || self.preview.is_pending()
+    }
+
+    pub(super) fn is_watching(&self) -> bool {
+        self.browser.is_watching()
+    }
+
+    /// Widths of the document list and the preview pane while the pane is shown.
+    pub(super) fn preview_layout(&self) -> Option<(u16, u16)> {
+        self.show_preview
+            .then(|| preview_layout(self.width))
+            .flatten()
     }
 
     pub(super) fn resize(&mut self, width: u16, height: u16) {
@@ -54,10 +93,6 @@ impl App {
             if key.code == KeyCode::Char('z') {
                 return AppAction::Suspend;
             }
-            if key.code == KeyCode::Char('f') {
-                self.browser.begin_filter();
-                return AppAction::None;
-            }
         }
 
         self.handle_browser_key(key)
@@ -90,6 +125,7 @@ impl App {
     }
 
     pub(super) fn after_pager(&mut self, result: Result<()>) {
+        self.browser.reload_reading();
         if let Err(error) = result {
             self.browser
                 .add_error(format!("Failed to open pager: {error:#}"));
@@ -110,32 +146,43 @@ impl App {
             return AppAction::None;
         }
 
+        if let Some(action) = self.keys.action(&key) {
+            return self.run_action(action);
+        }
         match key.code {
-            KeyCode::Char('q') => return AppAction::Quit,
             KeyCode::Esc => self.browser.cancel_filter(),
-            KeyCode::Char('r') => self.browser.refresh(),
-            KeyCode::Up | KeyCode::Char('k') => self.browser.move_up(),
-            KeyCode::Down | KeyCode::Char('j') => self.browser.move_down(),
-            KeyCode::Home | KeyCode::Char('g') => self.browser.go_top(),
-            KeyCode::End | KeyCode::Char('G') => self.browser.go_bottom(),
-            KeyCode::Left | KeyCode::Char('h' | 'b' | 'u') | KeyCode::PageUp => {
-                self.browser.page_back()
-            }
-            KeyCode::Right | KeyCode::Char('l' | 'f' | 'd') | KeyCode::PageDown => {
-                self.browser.page_forward()
-            }
             KeyCode::Tab | KeyCode::BackTab | KeyCode::Char('L' | 'H') => {
                 self.browser.next_section()
             }
-            KeyCode::Char('/') => self.browser.begin_filter(),
-            KeyCode::Char('?') => self.browser.toggle_help(),
             KeyCode::Char('!') => self.browser.open_error(),
-            KeyCode::Char('e' | 'E' | 'у' | 'У') => {
+            KeyCode::Char(' ') => self.browser.toggle_mark(),
+            KeyCode::Enter => return self.open_selected_document(),
+            _ => {}
+        }
+        AppAction::None
+    }
+
+    fn run_action(&mut self, action: KeyAction) -> AppAction {
+        match action {
+            KeyAction::Quit => return AppAction::Quit,
+            KeyAction::Reload => self.browser.refresh(),
+            KeyAction::ScrollUp => self.browser.move_up(),
+            KeyAction::ScrollDown => self.browser.move_down(),
+            KeyAction::Top => self.browser.go_top(),
+            KeyAction::Bottom => self.browser.go_bottom(),
+            KeyAction::PageUp => self.browser.page_back(),
+            KeyAction::PageDown => self.browser.page_forward(),
+            KeyAction::Filter => self.browser.begin_filter(),
+            KeyAction::SearchContents => self.browser.begin_content_search(),
+            KeyAction::Help => self.browser.toggle_help(),
+            KeyAction::Preview => self.show_preview = !self.show_preview,
+            KeyAction::Sort => self.browser.cycle_sort(),
+            KeyAction::Tree => self.browser.toggle_view(),
+            KeyAction::Edit => {
                 if let Some(document) = self.browser.selected_document() {
                     return AppAction::OpenEditor(document.path.clone());
                 }
             }
-            KeyCode::Enter => return self.open_selected_document(),
             _ => {}
         }
         AppAction::None
@@ -170,11 +217,16 @@ impl App {
         AppAction::None
     }
 
-    fn open_selected_document(&self) -> AppAction {
+    fn open_selected_document(&mut self) -> AppAction {
+        if self.browser.toggle_selected_directory() {
+            return AppAction::None;
+        }
+        let line = self.browser.selected_line();
         self.browser
-            .selected_document()
-            .map(|document| AppAction::OpenPager(document.path.clone()))
-            .unwrap_or(AppAction::None)
+            .take_documents_to_open()
+            .map_or(AppAction::None, |(paths, active)| {
+                AppAction::OpenPager(paths, active, line)
+            })
     }
 }
 
@@ -183,30 +235,60 @@ mod tests {
     use super::super::discovery::DocumentEntry;
     use super::*;
 
-    #[test]
-    fn enter_returns_the_selected_document_for_the_existing_pager() {
-        let browser = BrowserState::for_test(vec![DocumentEntry::for_test("README.md")], 24);
-        let mut app = App {
-            config: Config::default(),
+    fn test_app(browser: BrowserState, config: Config) -> App {
+        App {
+            keys: config.keybindings.resolve(KeyScope::Browser),
+            preview: PreviewPane::new(&config),
+            show_preview: false,
+            config,
             browser,
             width: 80,
             height: 24,
-        };
+        }
+    }
+
+    #[test]
+    fn enter_returns_the_selected_document_for_the_existing_pager() {
+        let browser = BrowserState::for_test(vec![DocumentEntry::for_test("README.md")], 24);
+        let mut app = test_app(browser, Config::default());
 
         let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
 
-        assert!(matches!(action, AppAction::OpenPager(path) if path.as_os_str() == "README.md"));
+        assert!(matches!(
+            action,
+            AppAction::OpenPager(paths, 0, None) if paths == [PathBuf::from("README.md")]
+        ));
+    }
+
+    #[test]
+    fn enter_opens_marked_documents_as_tabs() {
+        let browser = BrowserState::for_test(
+            vec![
+                DocumentEntry::for_test("ADR.md"),
+                DocumentEntry::for_test("CHANGELOG.md"),
+                DocumentEntry::for_test("README.md"),
+            ],
+            24,
+        );
+        let mut app = test_app(browser, Config::default());
+        let space = KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        app.handle_key(space);
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        let action = app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        let expected = [PathBuf::from("ADR.md"), PathBuf::from("README.md")];
+        assert!(matches!(
+            action,
+            AppAction::OpenPager(paths, 1, None) if paths == expected
+        ));
+        assert!(!app.browser.is_marked(&app.browser.documents()[0]));
     }
 
     #[test]
     fn control_f_starts_filtering() {
         let browser = BrowserState::for_test(vec![DocumentEntry::for_test("README.md")], 24);
-        let mut app = App {
-            config: Config::default(),
-            browser,
-            width: 80,
-            height: 24,
-        };
+        let mut app = test_app(browser, Config::default());
 
         app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
 
@@ -223,12 +305,7 @@ mod tests {
             ],
             24,
         );
-        let mut app = App {
-            config: Config::default(),
-            browser,
-            width: 80,
-            height: 24,
-        };
+        let mut app = test_app(browser, Config::default());
         app.browser.begin_filter();
         app.browser.set_filter("readme");
         app.browser.confirm_filter();
@@ -242,4 +319,36 @@ mod tests {
         assert_eq!(app.browser.filter_state(), FilterState::Applied);
         assert_eq!(app.browser.query(), "readme");
     }
+
+    #[test]
+    fn configured_bindings_replace_default_browser_keys() {
+        let browser = BrowserState::for_test(
+            vec![
+                DocumentEntry::for_test("ADR.md"),
+                DocumentEntry::for_test("README.md"),
+            ],
+            24,
+        );
+        let config = Config {
+            keybindings: serde_yaml::from_str("browser:\n  scroll_down: n\n  quit: ctrl+q\n")
+                .unwrap(),
+            ..Config::default()
+        };
+        let mut app = test_app(browser, config);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+        let selected = |app: &App| {
+            app.browser
+                .selected_document()
+                .map(|entry| entry.path.clone())
+        };
+        assert_eq!(selected(&app), Some(PathBuf::from("ADR.md")));
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert_eq!(selected(&app), Some(PathBuf::from("README.md")));
+
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(matches!(action, AppAction::None));
+        let action = app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL));
+        assert!(matches!(action, AppAction::Quit));
+    }
 }
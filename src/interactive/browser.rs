@@ -1,15 +1,25 @@
-use super::discovery::{DiscoveryEvent, DocumentEntry, filter_documents};
+use super::discovery::{
+    ContentMatch, ContentSearch, DiscoveryEvent, DocumentEntry, filter_documents,
+};
+use super::watcher::DirectoryWatcher;
+use crate::reading::{ReadingRecord, ReadingStore};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 use std::time::Instant;
 
+mod contents;
 mod loading;
+mod ordering;
+
+pub use ordering::{BrowserSort, BrowserView};
+pub(crate) use ordering::{ListRow, file_name};
 
 const ITEM_HEIGHT: usize = 3;
 const TOP_PADDING: usize = 5;
 const BOTTOM_PADDING: usize = 3;
 const MINI_HELP_HEIGHT: usize = 2;
-const FULL_HELP_HEIGHT: usize = 5;
+const FULL_HELP_HEIGHT: usize = 6;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum FilterState {
@@ -18,6 +28,15 @@ pub(crate) enum FilterState {
     Applied,
 }
 
+/// What the filter query is matched against.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum FilterMode {
+    /// Fuzzy match of the relative path.
+    Name,
+    /// Lines of the documents that contain the query.
+    Content,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(super) enum BrowserSection {
     Documents,
@@ -27,11 +46,13 @@ pub(super) enum BrowserSection {
 pub(crate) struct BrowserState {
     root: PathBuf,
     documents: Vec<DocumentEntry>,
+    /// Indices of `documents`, or of `content_matches` in content mode.
     filtered: Vec<usize>,
     document_selection: usize,
     filter_selection: usize,
     height: u16,
     filter_state: FilterState,
+    filter_mode: FilterMode,
     section: BrowserSection,
     query: String,
     show_full_help: bool,
@@ -39,7 +60,23 @@ pub(crate) struct BrowserState {
     errors: Vec<String>,
     loaded: bool,
     receiver: Option<Receiver<DiscoveryEvent>>,
+    content_matches: Vec<ContentMatch>,
+    content_search: Option<ContentSearch>,
     loading_started: Instant,
+    reading: ReadingStore,
+    /// Documents marked with Space, opened together as pager tabs.
+    marked: HashSet<PathBuf>,
+    sort: BrowserSort,
+    view: BrowserView,
+    /// Loaded config file that keeps the sort and view chosen at runtime.
+    config_file: Option<PathBuf>,
+    /// Relative paths of the directories collapsed in the tree view.
+    collapsed: HashSet<String>,
+    /// Rows of the tree view; empty in the list view.
+    rows: Vec<ListRow>,
+    watcher: Option<DirectoryWatcher>,
+    /// Documents the watcher found after discovery, until they are opened or refreshed.
+    new_documents: HashSet<PathBuf>,
 }
 
 impl BrowserState {
@@ -54,6 +91,7 @@ impl BrowserState {
             filter_selection: 0,
             height,
             filter_state: FilterState::Unfiltered,
+            filter_mode: FilterMode::Name,
             section: BrowserSection::Documents,
             query: String::new(),
             show_full_help: false,
@@ -61,7 +99,18 @@ impl BrowserState {
             errors: Vec::new(),
             loaded: true,
             receiver: None,
+            content_matches: Vec::new(),
+            content_search: None,
             loading_started: Instant::now(),
+            reading: ReadingStore::default(),
+            marked: HashSet::new(),
+            sort: BrowserSort::Path,
+            view: BrowserView::List,
+            config_file: None,
+            collapsed: HashSet::new(),
+            rows: Vec::new(),
+            watcher: None,
+            new_documents: HashSet::new(),
         }
     }
 
@@ -74,10 +123,73 @@ impl BrowserState {
         &self.documents
     }
 
+    /// Reading state saved by the pager for `document`, once it has been read.
+    pub(super) fn reading_record(&self, document: &DocumentEntry) -> Option<&ReadingRecord> {
+        self.reading
+            .record(Some(&document.path))
+            .filter(|record| record.last_read().is_some())
+    }
+
+    pub(super) fn reload_reading(&mut self) {
+        self.reading = ReadingStore::load();
+        if self.sort == BrowserSort::Recent {
+            self.reorder();
+        }
+    }
+
+    pub(super) fn is_marked(&self, document: &DocumentEntry) -> bool {
+        self.marked.contains(&document.path)
+    }
+
+    /// Whether the document appeared while the browser was open.
+    pub(super) fn is_new(&self, document: &DocumentEntry) -> bool {
+        self.new_documents.contains(&document.path)
+    }
+
+    /// Marks or unmarks the selected document and moves to the next one.
+    pub(crate) fn toggle_mark(&mut self) {
+        let Some(path) = self
+            .selected_document()
+            .map(|document| document.path.clone())
+        else {
+            return;
+        };
+        if !self.marked.remove(&path) {
+            self.marked.insert(path);
+        }
+        self.move_down();
+    }
+
+    /// Marked documents plus the selected one, in list order, and the position of the
+    /// selected one among them. Marks are cleared.
+    pub(crate) fn take_documents_to_open(&mut self) -> Option<(Vec<PathBuf>, usize)> {
+        let selected = self.selected_document()?.path.clone();
+        let marked = std::mem::take(&mut self.marked);
+        let mut paths: Vec<PathBuf> = self
+            .documents
+            .iter()
+            .map(|document| document.path.clone())
+            .filter(|path| *path == selected || marked.contains(path))
+            .collect();
+        // A content match may come before discovery lists its document.
+        if !paths.contains(&selected) {
+            paths.push(selected.clone());
+        }
+        let active = paths.iter().position(|path| path == &selected)?;
+        for path in &paths {
+            self.new_documents.remove(path);
+        }
+        Some((paths, active))
+    }
+
     pub(crate) fn filter_state(&self) -> FilterState {
         self.filter_state
     }
 
+    pub(crate) fn filter_mode(&self) -> FilterMode {
+        self.filter_mode
+    }
+
     pub(super) fn section(&self) -> BrowserSection {
         self.section
     }
@@ -126,9 +238,19 @@ impl BrowserState {
     }
 
     pub(crate) fn begin_filter(&mut self) {
+        self.begin_filter_mode(FilterMode::Name);
+    }
+
+    fn begin_filter_mode(&mut self, mode: FilterMode) {
+        if mode != self.filter_mode {
+            self.filter_mode = mode;
+            self.filtered.clear();
+            self.content_matches.clear();
+            self.content_search = None;
+        }
         self.filter_state = FilterState::Editing;
-        self.filtered = filter_documents(&self.documents, &self.query);
         self.filter_selection = 0;
+        self.update_filter();
     }
 
     pub(super) fn push_filter_char(&mut self, character: char) {
@@ -153,12 +275,20 @@ impl BrowserState {
         self.section = BrowserSection::Documents;
         self.query.clear();
         self.filtered.clear();
+        self.content_matches.clear();
+        self.content_search = None;
         self.filter_selection = 0;
         self.clamp_selection();
     }
 
     pub(crate) fn confirm_filter(&mut self) {
         match self.filtered.as_slice() {
+            // Content matches may still be on their way.
+            [] if self.is_searching_contents() && !self.query.is_empty() => {
+                self.filter_state = FilterState::Applied;
+                self.section = BrowserSection::Filter;
+                self.filter_selection = 0;
+            }
             [] => {
                 self.cancel_filter();
             }
@@ -183,18 +313,32 @@ impl BrowserState {
         }
     }
 
-    pub(super) fn visible_indices(&self) -> Vec<usize> {
-        if self.filter_state == FilterState::Editing || self.section == BrowserSection::Filter {
-            self.filtered.clone()
+    /// Rows of the list: filter results are always flat, the documents may be a tree.
+    pub(super) fn visible_rows(&self) -> Vec<ListRow> {
+        if self.shows_filter() {
+            self.filtered
+                .iter()
+                .copied()
+                .map(ListRow::Document)
+                .collect()
+        } else if self.view == BrowserView::Tree {
+            self.rows.clone()
         } else {
-            (0..self.documents.len()).collect()
+            (0..self.documents.len()).map(ListRow::Document).collect()
         }
     }
 
+    /// Selected document; `None` on a directory row.
     pub(super) fn selected_document(&self) -> Option<&DocumentEntry> {
-        let visible = self.visible_indices();
-        let index = *visible.get(self.selection())?;
-        self.documents.get(index)
+        if self.shows_content_matches() {
+            return self
+                .selected_content_match()
+                .map(|content_match| &content_match.document);
+        }
+        match self.visible_rows().get(self.selection())? {
+            ListRow::Document(index) => self.documents.get(*index),
+            ListRow::Directory { .. } => None,
+        }
     }
 
     #[cfg(test)]
@@ -212,10 +356,7 @@ impl BrowserState {
     }
 
     pub(super) fn page_count(&self) -> usize {
-        self.visible_indices()
-            .len()
-            .max(1)
-            .div_ceil(self.per_page())
+        self.visible_rows().len().max(1).div_ceil(self.per_page())
     }
 
     pub(super) fn per_page(&self) -> usize {
@@ -235,7 +376,7 @@ impl BrowserState {
     }
 
     pub(crate) fn move_down(&mut self) {
-        let last = self.visible_indices().len().saturating_sub(1);
+        let last = self.visible_rows().len().saturating_sub(1);
         let selection = self.selection_mut();
         *selection = (*selection + 1).min(last);
     }
@@ -245,7 +386,7 @@ impl BrowserState {
     }
 
     pub(super) fn go_bottom(&mut self) {
-        *self.selection_mut() = self.visible_indices().len().saturating_sub(1);
+        *self.selection_mut() = self.visible_rows().len().saturating_sub(1);
     }
 
     pub(super) fn page_back(&mut self) {
@@ -256,13 +397,20 @@ impl BrowserState {
 
     pub(super) fn page_forward(&mut self) {
         let per_page = self.per_page();
-        let last = self.visible_indices().len().saturating_sub(1);
+        let last = self.visible_rows().len().saturating_sub(1);
         let selection = self.selection_mut();
         *selection = (*selection + per_page).min(last);
     }
 
     fn update_filter(&mut self) {
-        if self.filter_state != FilterState::Unfiltered {
+        match self.filter_mode {
+            FilterMode::Name => self.update_name_filter(),
+            FilterMode::Content => self.restart_content_search(),
+        }
+    }
+
+    fn update_name_filter(&mut self) {
+        if self.filter_state != FilterState::Unfiltered && self.filter_mode == FilterMode::Name {
             self.filtered = filter_documents(&self.documents, &self.query);
             self.filter_selection = self
                 .filter_selection
@@ -271,13 +419,18 @@ impl BrowserState {
     }
 
     fn clamp_selection(&mut self) {
-        let last = self.visible_indices().len().saturating_sub(1);
+        let last = self.visible_rows().len().saturating_sub(1);
         let selection = self.selection_mut();
         *selection = (*selection).min(last);
     }
 
+    /// Whether the list shows the filter results rather than every document.
+    fn shows_filter(&self) -> bool {
+        self.filter_state == FilterState::Editing || self.section == BrowserSection::Filter
+    }
+
     fn selection(&self) -> usize {
-        if self.filter_state == FilterState::Editing || self.section == BrowserSection::Filter {
+        if self.shows_filter() {
             self.filter_selection
         } else {
             self.document_selection
@@ -285,7 +438,7 @@ impl BrowserState {
     }
 
     fn selection_mut(&mut self) -> &mut usize {
-        if self.filter_state == FilterState::Editing || self.section == BrowserSection::Filter {
+        if self.shows_filter() {
             &mut self.filter_selection
         } else {
             &mut self.document_selection
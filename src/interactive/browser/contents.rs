@@ -0,0 +1,75 @@
+use super::*;
+use crate::interactive::discovery::ContentSearchEvent;
+use std::sync::mpsc::TryRecvError;
+
+const CONTENT_EVENTS_PER_TICK: usize = 128;
+
+impl BrowserState {
+    /// Starts editing a query that is searched for in the contents of every document.
+    pub(crate) fn begin_content_search(&mut self) {
+        self.begin_filter_mode(FilterMode::Content);
+    }
+
+    pub(in crate::interactive) fn content_matches(&self) -> &[ContentMatch] {
+        &self.content_matches
+    }
+
+    /// Whether the list shows content matches rather than documents.
+    pub(in crate::interactive) fn shows_content_matches(&self) -> bool {
+        self.filter_mode == FilterMode::Content && self.shows_filter()
+    }
+
+    pub(in crate::interactive) fn is_searching_contents(&self) -> bool {
+        self.content_search.is_some()
+    }
+
+    pub(super) fn selected_content_match(&self) -> Option<&ContentMatch> {
+        if !self.shows_content_matches() {
+            return None;
+        }
+        let index = *self.filtered.get(self.selection())?;
+        self.content_matches.get(index)
+    }
+
+    /// Source line of the selected content match, where the pager starts.
+    pub(crate) fn selected_line(&self) -> Option<usize> {
+        self.selected_content_match()
+            .map(|content_match| content_match.line)
+    }
+
+    /// Drops the matches of the previous query and searches for the current one.
+    pub(super) fn restart_content_search(&mut self) {
+        self.content_matches.clear();
+        self.filtered.clear();
+        self.filter_selection = 0;
+        self.content_search = (self.filter_state != FilterState::Unfiltered
+            && !self.query.trim().is_empty())
+        .then(|| ContentSearch::start(self.root.clone(), &self.query));
+    }
+
+    pub(in crate::interactive) fn poll_content_search(&mut self) {
+        let Some(search) = self.content_search.take() else {
+            return;
+        };
+        let mut finished = false;
+
+        for _ in 0..CONTENT_EVENTS_PER_TICK {
+            match search.try_recv() {
+                Ok(ContentSearchEvent::Match(content_match)) => {
+                    self.filtered.push(self.content_matches.len());
+                    self.content_matches.push(content_match);
+                }
+                Ok(ContentSearchEvent::Error(error)) => self.errors.push(error),
+                Ok(ContentSearchEvent::Finished) | Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+
+        if !finished {
+            self.content_search = Some(search);
+        }
+    }
+}
@@ -1,14 +1,24 @@
 use super::*;
 use crate::interactive::discovery::start_discovery;
+use crate::interactive::watcher::{DirectoryWatcher, WatchEvent};
 use std::path::Path;
 use std::sync::mpsc::TryRecvError;
 use std::time::Duration;
 
 const DISCOVERY_EVENTS_PER_TICK: usize = 128;
+const WATCH_EVENTS_PER_TICK: usize = 128;
 
 impl BrowserState {
-    pub(in crate::interactive) fn new(root: PathBuf, height: u16) -> Self {
+    pub(in crate::interactive) fn new(
+        root: PathBuf,
+        height: u16,
+        sort: BrowserSort,
+        view: BrowserView,
+        config_file: Option<PathBuf>,
+    ) -> Self {
         let receiver = Some(start_discovery(root.clone()));
+        // Without a working watcher the list still updates on refresh.
+        let watcher = Some(DirectoryWatcher::start(root.clone()));
         Self {
             root,
             documents: Vec::new(),
@@ -17,6 +27,7 @@ impl BrowserState {
             filter_selection: 0,
             height,
             filter_state: FilterState::Unfiltered,
+            filter_mode: FilterMode::Name,
             section: BrowserSection::Documents,
             query: String::new(),
             show_full_help: false,
@@ -24,7 +35,18 @@ impl BrowserState {
             errors: Vec::new(),
             loaded: false,
             receiver,
+            content_matches: Vec::new(),
+            content_search: None,
             loading_started: Instant::now(),
+            reading: ReadingStore::load(),
+            marked: HashSet::new(),
+            sort,
+            view,
+            config_file,
+            collapsed: HashSet::new(),
+            rows: Vec::new(),
+            watcher,
+            new_documents: HashSet::new(),
         }
     }
 
@@ -71,30 +93,94 @@ impl BrowserState {
             self.receiver = Some(receiver);
         }
         if documents_changed {
-            self.documents
-                .sort_by(|left, right| left.relative_path.cmp(&right.relative_path));
-            self.update_filter();
-            self.restore_selection(selected_path.as_deref());
+            self.resort(selected_path.as_deref());
         }
     }
 
+    /// Applies the documents that the watcher found created, modified, or removed once
+    /// discovery has finished. Returns whether the list changed.
+    pub(in crate::interactive) fn poll_watcher(&mut self) -> bool {
+        if !self.loaded {
+            return false;
+        }
+        let Some(watcher) = self.watcher.take() else {
+            return false;
+        };
+        let selected_path = self
+            .selected_document()
+            .map(|document| document.path.clone());
+        let mut documents_changed = false;
+        let mut disconnected = false;
+
+        for _ in 0..WATCH_EVENTS_PER_TICK {
+            match watcher.try_recv() {
+                Ok(WatchEvent::Document(document)) => {
+                    match self
+                        .documents
+                        .iter_mut()
+                        .find(|existing| existing.path == document.path)
+                    {
+                        Some(existing) => *existing = document,
+                        None => {
+                            self.new_documents.insert(document.path.clone());
+                            self.documents.push(document);
+                        }
+                    }
+                    documents_changed = true;
+                }
+                Ok(WatchEvent::Removed(path)) => {
+                    let count = self.documents.len();
+                    self.documents
+                        .retain(|document| !document.path.starts_with(&path));
+                    self.new_documents
+                        .retain(|document| !document.starts_with(&path));
+                    documents_changed |= self.documents.len() != count;
+                }
+                Ok(WatchEvent::Error(error)) => self.errors.push(error),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        if !disconnected {
+            self.watcher = Some(watcher);
+        }
+        if documents_changed {
+            self.resort(selected_path.as_deref());
+        }
+        documents_changed
+    }
+
+    pub(in crate::interactive) fn is_watching(&self) -> bool {
+        self.watcher.is_some()
+    }
+
     pub(in crate::interactive) fn refresh(&mut self) {
         self.documents.clear();
+        self.rows.clear();
         self.filtered.clear();
         self.document_selection = 0;
         self.filter_selection = 0;
         self.errors.clear();
+        self.new_documents.clear();
         self.show_error = false;
         self.loaded = false;
         self.loading_started = Instant::now();
         self.receiver = Some(start_discovery(self.root.clone()));
+        if self.filter_mode == FilterMode::Content {
+            self.restart_content_search();
+        }
+        self.reload_reading();
     }
 
     pub(in crate::interactive) fn loading_elapsed(&self) -> Option<Duration> {
         (!self.loaded).then(|| self.loading_started.elapsed())
     }
 
-    fn restore_selection(&mut self, selected_path: Option<&Path>) {
+    pub(super) fn restore_selection(&mut self, selected_path: Option<&Path>) {
         let selected = selected_path
             .and_then(|path| {
                 self.documents
@@ -102,9 +188,9 @@ impl BrowserState {
                     .position(|document| document.path == path)
             })
             .and_then(|document_index| {
-                self.visible_indices()
+                self.visible_rows()
                     .iter()
-                    .position(|index| *index == document_index)
+                    .position(|row| *row == ListRow::Document(document_index))
             });
 
         if let Some(selected) = selected {
@@ -0,0 +1,233 @@
+use super::*;
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Order of the documents in the browser list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrowserSort {
+    /// Relative path, alphabetically.
+    #[default]
+    Path,
+    /// Modification time, newest first.
+    Modified,
+    /// File size, largest first.
+    Size,
+    /// Title, or the path of untitled documents, alphabetically.
+    Title,
+    /// Last time the pager left the document, most recent first; unread ones last.
+    Recent,
+}
+
+impl BrowserSort {
+    const ALL: [Self; 5] = [
+        Self::Path,
+        Self::Modified,
+        Self::Size,
+        Self::Title,
+        Self::Recent,
+    ];
+
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Modified => "modified",
+            Self::Size => "size",
+            Self::Title => "title",
+            Self::Recent => "recently read",
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|sort| *sort == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Layout of the unfiltered document list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BrowserView {
+    /// One card per document.
+    #[default]
+    List,
+    /// Documents grouped under collapsible directory rows.
+    Tree,
+}
+
+/// Entry of the browser list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ListRow {
+    /// Index of a document, or of a content match in content mode.
+    Document(usize),
+    /// Directory of the tree view with the number of documents below it.
+    Directory {
+        path: String,
+        depth: usize,
+        documents: usize,
+        collapsed: bool,
+    },
+}
+
+impl BrowserState {
+    pub(crate) fn sort(&self) -> BrowserSort {
+        self.sort
+    }
+
+    pub(crate) fn view(&self) -> BrowserView {
+        self.view
+    }
+
+    /// Whether the list shows the directory tree rather than flat cards.
+    pub(crate) fn shows_tree(&self) -> bool {
+        self.view == BrowserView::Tree && !self.shows_filter()
+    }
+
+    /// Switches to the next sort mode, keeping the selected document, and writes it to
+    /// the config file.
+    pub(crate) fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.reorder();
+        self.save_setting("browser_sort", self.sort);
+    }
+
+    /// Switches between the flat list and the directory tree, and writes the view to the
+    /// config file.
+    pub(crate) fn toggle_view(&mut self) {
+        let selected_path = self.selected_path_buf();
+        self.view = match self.view {
+            BrowserView::List => BrowserView::Tree,
+            BrowserView::Tree => BrowserView::List,
+        };
+        self.rebuild_rows();
+        self.restore_selection(selected_path.as_deref());
+        self.save_setting("browser_view", self.view);
+    }
+
+    /// Without a loaded config file the choice lasts for the session.
+    fn save_setting(&mut self, key: &str, value: impl Serialize) {
+        let Some(path) = &self.config_file else {
+            return;
+        };
+        if let Err(error) = Config::save_value(path, key, value) {
+            self.add_error(format!("{error:#}"));
+        }
+    }
+
+    /// Collapses or expands the selected directory row. Returns whether one was selected.
+    pub(crate) fn toggle_selected_directory(&mut self) -> bool {
+        let Some(ListRow::Directory { path, .. }) =
+            self.visible_rows().get(self.selection()).cloned()
+        else {
+            return false;
+        };
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+        // Rows above the directory do not change, so the selection stays on it.
+        self.rebuild_rows();
+        true
+    }
+
+    /// Sorts the documents again after the sort mode or the reading state changed.
+    pub(super) fn reorder(&mut self) {
+        let selected_path = self.selected_path_buf();
+        self.resort(selected_path.as_deref());
+    }
+
+    /// Sorts the changed documents and refilters them, keeping `selected_path` selected.
+    /// When it is gone, the selection stays at the same position.
+    pub(super) fn resort(&mut self, selected_path: Option<&Path>) {
+        self.sort_documents();
+        self.update_name_filter();
+        if !self.shows_content_matches() {
+            self.restore_selection(selected_path);
+        }
+    }
+
+    /// Orders the documents by the sort mode, with the path breaking ties.
+    pub(super) fn sort_documents(&mut self) {
+        let reading = &self.reading;
+        let documents = &mut self.documents;
+        // The sorts below are stable, so equal keys stay in path order.
+        documents.sort_by(|left, right| left.relative_path.cmp(&right.relative_path));
+        match self.sort {
+            BrowserSort::Path => {}
+            BrowserSort::Modified => documents.sort_by_key(|document| Reverse(document.modified)),
+            BrowserSort::Size => documents.sort_by_key(|document| Reverse(document.size)),
+            BrowserSort::Title => {
+                documents.sort_by_cached_key(|document| document.display_name().to_lowercase());
+            }
+            BrowserSort::Recent => documents.sort_by_cached_key(|document| {
+                Reverse(
+                    reading
+                        .record(Some(&document.path))
+                        .and_then(ReadingRecord::last_read),
+                )
+            }),
+        }
+        self.rebuild_rows();
+    }
+
+    pub(super) fn rebuild_rows(&mut self) {
+        self.rows = match self.view {
+            BrowserView::List => Vec::new(),
+            BrowserView::Tree => tree_rows(&self.documents, &self.collapsed),
+        };
+    }
+
+    fn selected_path_buf(&self) -> Option<PathBuf> {
+        self.selected_document()
+            .map(|document| document.path.clone())
+    }
+}
+
+/// Directory rows, each followed by its documents in list order, with the directories
+/// below it after them. Documents at the root come first.
+fn tree_rows(documents: &[DocumentEntry], collapsed: &HashSet<String>) -> Vec<ListRow> {
+    let mut directories: BTreeMap<Vec<&str>, Vec<usize>> = BTreeMap::new();
+    let mut counts: HashMap<Vec<&str>, usize> = HashMap::new();
+    for (index, document) in documents.iter().enumerate() {
+        let components: Vec<&str> = document.relative_path.split('/').collect();
+        let parent = &components[..components.len() - 1];
+        for depth in 1..=parent.len() {
+            directories.entry(parent[..depth].to_vec()).or_default();
+            *counts.entry(parent[..depth].to_vec()).or_default() += 1;
+        }
+        directories.entry(parent.to_vec()).or_default().push(index);
+    }
+
+    let mut rows = Vec::new();
+    for (components, indices) in directories {
+        let hidden =
+            (1..components.len()).any(|depth| collapsed.contains(&components[..depth].join("/")));
+        if hidden {
+            continue;
+        }
+        if !components.is_empty() {
+            let path = components.join("/");
+            let is_collapsed = collapsed.contains(&path);
+            rows.push(ListRow::Directory {
+                documents: counts.get(&components).copied().unwrap_or_default(),
+                depth: components.len() - 1,
+                collapsed: is_collapsed,
+                path,
+            });
+            if is_collapsed {
+                continue;
+            }
+        }
+        rows.extend(indices.into_iter().map(ListRow::Document));
+    }
+    rows
+}
+
+/// Last component of a relative path.
+pub(crate) fn file_name(relative_path: &str) -> &str {
+    relative_path
+        .rsplit_once('/')
+        .map_or(relative_path, |(_, name)| name)
+}
@@ -1,4 +1,6 @@
+use super::ordering::BrowserSort;
 use super::*;
+use crate::interactive::watcher::DirectoryWatcher;
 use std::sync::mpsc;
 
 #[test]
@@ -34,3 +36,260 @@ fn discovery_updates_the_browser_before_the_scan_finishes() {
 
     assert!(browser.is_loaded());
 }
+
+#[test]
+fn content_search_lists_matching_lines_and_opens_at_the_selected_one() {
+    let directory = tempfile::tempdir().unwrap();
+    std::fs::write(
+        directory.path().join("intro.md"),
+        "# Intro\n\nNothing here.\n",
+    )
+    .unwrap();
+    std::fs::write(
+        directory.path().join("retries.md"),
+        "# Retries\n\nEach client keeps a retry budget.\n\nThe Retry Budget refills.\n",
+    )
+    .unwrap();
+    let mut browser = BrowserState::for_test(
+        vec![
+            DocumentEntry::for_test("intro.md"),
+            DocumentEntry::for_test("retries.md"),
+        ],
+        24,
+    );
+    browser.root = directory.path().to_path_buf();
+
+    browser.begin_content_search();
+    assert!(!browser.is_searching_contents());
+    browser.set_filter("retry budget");
+    for _ in 0..500 {
+        browser.poll_content_search();
+        if !browser.is_searching_contents() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let lines: Vec<_> = browser
+        .content_matches()
+        .iter()
+        .map(|content_match| {
+            (
+                content_match.document.relative_path.as_str(),
+                content_match.line,
+            )
+        })
+        .collect();
+    assert_eq!(lines, [("retries.md", 3), ("retries.md", 5)]);
+
+    browser.confirm_filter();
+    browser.move_down();
+    assert_eq!(browser.selected_line(), Some(5));
+    let (paths, active) = browser.take_documents_to_open().unwrap();
+    assert_eq!(paths[active], directory.path().join("retries.md"));
+
+    browser.next_section();
+    assert_eq!(browser.selected_line(), None);
+    browser.cancel_filter();
+    assert!(browser.content_matches().is_empty());
+}
+
+fn document(relative_path: &str, modified: u64, size: u64) -> DocumentEntry {
+    let mut document = DocumentEntry::for_test(relative_path);
+    document.modified =
+        std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(modified);
+    document.size = size;
+    document
+}
+
+fn listed_paths(browser: &BrowserState) -> Vec<&str> {
+    browser
+        .documents()
+        .iter()
+        .map(|document| document.relative_path.as_str())
+        .collect()
+}
+
+#[test]
+fn sort_modes_reorder_the_list_and_keep_the_selected_document() {
+    let mut browser = BrowserState::for_test(
+        vec![
+            document("alpha.md", 10, 300),
+            document("beta.md", 30, 100),
+            document("gamma.md", 20, 200),
+        ],
+        24,
+    );
+    browser.reading = serde_json::from_str(r#"{"files": {"gamma.md": {"last_read": 5}}}"#).unwrap();
+    browser.move_down();
+
+    browser.cycle_sort();
+    assert_eq!(browser.sort(), BrowserSort::Modified);
+    assert_eq!(listed_paths(&browser), ["beta.md", "gamma.md", "alpha.md"]);
+    assert_eq!(browser.selected_path(), Some("beta.md"));
+
+    browser.cycle_sort();
+    assert_eq!(listed_paths(&browser), ["alpha.md", "gamma.md", "beta.md"]);
+    browser.cycle_sort();
+    assert_eq!(browser.sort(), BrowserSort::Title);
+    assert_eq!(listed_paths(&browser), ["alpha.md", "beta.md", "gamma.md"]);
+    browser.cycle_sort();
+    assert_eq!(listed_paths(&browser), ["gamma.md", "alpha.md", "beta.md"]);
+    browser.cycle_sort();
+    assert_eq!(browser.sort(), BrowserSort::Path);
+    assert_eq!(browser.selected_path(), Some("beta.md"));
+}
+
+#[test]
+fn chosen_sort_and_view_are_written_to_the_config_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("config.yaml");
+    std::fs::write(
+        &path,
+        "theme: nord\nbrowser_sort: path         # o cycles it\n",
+    )
+    .unwrap();
+    let mut browser = BrowserState::for_test(vec![DocumentEntry::for_test("alpha.md")], 24);
+    browser.config_file = Some(path.clone());
+
+    browser.cycle_sort();
+    browser.toggle_view();
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "theme: nord\nbrowser_sort: modified     # o cycles it\nbrowser_view: tree\n"
+    );
+    let config: crate::config::Config =
+        serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(config.browser_sort, BrowserSort::Modified);
+    assert_eq!(config.browser_view, BrowserView::Tree);
+    assert!(browser.errors().is_empty());
+}
+
+#[test]
+fn tree_view_groups_documents_under_collapsible_directories() {
+    let mut browser = BrowserState::for_test(
+        vec![
+            DocumentEntry::for_test("README.md"),
+            DocumentEntry::for_test("docs/guide.md"),
+            DocumentEntry::for_test("docs/api/auth.md"),
+            DocumentEntry::for_test("docs-old/notes.md"),
+        ],
+        40,
+    );
+    browser.sort_documents();
+    browser.toggle_view();
+    assert_eq!(
+        listed_paths(&browser),
+        [
+            "README.md",
+            "docs-old/notes.md",
+            "docs/api/auth.md",
+            "docs/guide.md"
+        ]
+    );
+
+    let directory = |path: &str, depth, documents, collapsed| ListRow::Directory {
+        path: path.to_string(),
+        depth,
+        documents,
+        collapsed,
+    };
+    let expanded = vec![
+        ListRow::Document(0),
+        directory("docs", 0, 2, false),
+        ListRow::Document(3),
+        directory("docs/api", 1, 1, false),
+        ListRow::Document(2),
+        directory("docs-old", 0, 1, false),
+        ListRow::Document(1),
+    ];
+    assert_eq!(browser.visible_rows(), expanded);
+    assert_eq!(browser.selected_path(), Some("README.md"));
+
+    browser.move_down();
+    assert_eq!(browser.selected_document().map(|_| ()), None);
+    assert!(browser.toggle_selected_directory());
+    assert_eq!(
+        browser.visible_rows(),
+        [
+            ListRow::Document(0),
+            directory("docs", 0, 2, true),
+            directory("docs-old", 0, 1, false),
+            ListRow::Document(1),
+        ]
+    );
+
+    browser.toggle_selected_directory();
+    assert_eq!(browser.visible_rows(), expanded);
+    browser.go_top();
+    assert!(!browser.toggle_selected_directory());
+    browser.toggle_view();
+    assert_eq!(browser.visible_rows().len(), 4);
+}
+
+#[test]
+fn watcher_updates_the_list_in_place_and_marks_created_documents() {
+    let directory = tempfile::tempdir().unwrap();
+    let root = directory.path().canonicalize().unwrap();
+    std::fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+    std::fs::write(root.join("beta.md"), "# Beta\n").unwrap();
+    let mut browser = BrowserState::for_test(Vec::new(), 24);
+    browser.root = root.clone();
+    browser.loaded = false;
+    browser.receiver = Some(crate::interactive::discovery::start_discovery(root.clone()));
+    browser.watcher = Some(DirectoryWatcher::start(root.clone()));
+    let wait_for = |browser: &mut BrowserState, done: &dyn Fn(&BrowserState) -> bool| {
+        for _ in 0..500 {
+            browser.poll_discovery();
+            browser.poll_watcher();
+            if done(browser) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("the browser did not update: {:?}", listed_paths(browser));
+    };
+    wait_for(&mut browser, &|browser| browser.is_loaded());
+    assert_eq!(listed_paths(&browser), ["beta.md"]);
+    // The watches are set up in the background, so repeat a change until it shows up,
+    // leaving the debounce time between the writes.
+    let polls = std::cell::Cell::new(0);
+    wait_for(&mut browser, &|browser| {
+        if polls.replace(polls.get() + 1) % 20 == 0 {
+            std::fs::write(root.join("probe.md"), "# Probe\n").unwrap();
+        }
+        browser.documents().len() == 2
+    });
+    std::fs::remove_file(root.join("probe.md")).unwrap();
+    wait_for(&mut browser, &|browser| browser.documents().len() == 1);
+    browser.new_documents.clear();
+
+    std::fs::create_dir(root.join("generated")).unwrap();
+    std::fs::write(root.join("generated/out.md"), "# Out\n").unwrap();
+    std::fs::write(root.join("alpha.md"), "# Alpha\n").unwrap();
+    wait_for(&mut browser, &|browser| browser.documents().len() == 2);
+    assert_eq!(listed_paths(&browser), ["alpha.md", "beta.md"]);
+    assert_eq!(browser.selected_path(), Some("beta.md"));
+    assert!(browser.is_new(&browser.documents()[0]));
+    assert!(!browser.is_new(&browser.documents()[1]));
+
+    std::fs::remove_file(root.join("beta.md")).unwrap();
+    wait_for(&mut browser, &|browser| browser.documents().len() == 1);
+    assert_eq!(browser.selected_path(), Some("alpha.md"));
+    assert_eq!(
+        browser.documents()[0].summary.title.as_deref(),
+        Some("Alpha")
+    );
+
+    // A created directory is watched once it is found.
+    std::fs::create_dir(root.join("notes")).unwrap();
+    std::fs::write(root.join("notes/first.md"), "# First\n").unwrap();
+    wait_for(&mut browser, &|browser| browser.documents().len() == 2);
+    std::fs::write(root.join("notes/second.md"), "# Second\n").unwrap();
+    wait_for(&mut browser, &|browser| browser.documents().len() == 3);
+    assert_eq!(
+        listed_paths(&browser),
+        ["alpha.md", "notes/first.md", "notes/second.md"]
+    );
+}
@@ -1,51 +1,141 @@
+use crate::markdown::{DocumentSummary, summarize_file};
+use crate::utils::is_markdown_path;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::SystemTime;
 use unicode_normalization::UnicodeNormalization;
 use unicode_normalization::char::is_combining_mark;
 
-const MARKDOWN_EXTENSIONS: &[&str] = &["md", "mdown", "mkdn", "mkd", "markdown"];
 const DISCOVERY_CHANNEL_CAPACITY: usize = 256;
+/// Matches after which a content search stops.
+const CONTENT_MATCH_LIMIT: usize = 1000;
+/// Characters kept in a snippet before the first match of its line.
+const SNIPPET_CONTEXT: usize = 24;
+/// Front matter keys that a filter term also finds under the other spelling.
+const PROPERTY_ALIASES: [(&str, &str); 1] = [("tag", "tags")];
 
 #[derive(Debug, Clone)]
 pub(crate) struct DocumentEntry {
     pub(crate) path: PathBuf,
     pub(crate) relative_path: String,
     pub(crate) modified: SystemTime,
+    /// File length in bytes.
+    pub(crate) size: u64,
+    /// Title and front matter; empty until discovery reads the file.
+    pub(crate) summary: DocumentSummary,
     filter_value: String,
+    title_filter_value: Option<String>,
 }
 
 impl DocumentEntry {
-    fn new(path: PathBuf, root: &Path, modified: SystemTime) -> Self {
+    fn new(
+        path: PathBuf,
+        root: &Path,
+        modified: SystemTime,
+        size: u64,
+        summary: DocumentSummary,
+    ) -> Self {
         let relative_path = path
             .strip_prefix(root)
             .unwrap_or(&path)
             .to_string_lossy()
             .replace('\\', "/");
-        let filter_value = normalize(&relative_path);
         Self {
+            filter_value: normalize(&relative_path),
+            title_filter_value: summary.title.as_deref().map(normalize),
             path,
             relative_path,
             modified,
-            filter_value,
+            size,
+            summary,
         }
     }
 
     #[cfg(test)]
     pub(crate) fn for_test(relative_path: &str) -> Self {
-        Self {
-            path: PathBuf::from(relative_path),
-            relative_path: relative_path.to_string(),
-            modified: SystemTime::UNIX_EPOCH,
-            filter_value: normalize(relative_path),
-        }
+        Self::new(
+            PathBuf::from(relative_path),
+            Path::new(""),
+            SystemTime::UNIX_EPOCH,
+            0,
+            DocumentSummary::default(),
+        )
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_summary(mut self, summary: DocumentSummary) -> Self {
+        self.title_filter_value = summary.title.as_deref().map(normalize);
+        self.summary = summary;
+        self
+    }
+
+    /// Front matter title or first heading, falling back to the relative path.
+    pub(crate) fn display_name(&self) -> &str {
+        self.summary.title.as_deref().unwrap_or(&self.relative_path)
     }
 
+    /// Characters of `display_name` matched by the text part of a filter query.
     pub(crate) fn match_indices(&self, query: &str) -> Vec<usize> {
-        fuzzy_match_indices(&self.relative_path, query)
+        fuzzy_match_indices(self.display_name(), &FilterQuery::parse(query).text)
+    }
+
+    /// Values of the front matter property `key`; `tag` and `tags` find each other.
+    pub(crate) fn property(&self, key: &str) -> Option<&[String]> {
+        let alias = PROPERTY_ALIASES.iter().find_map(|&(one, other)| {
+            if key.eq_ignore_ascii_case(one) {
+                Some(other)
+            } else if key.eq_ignore_ascii_case(other) {
+                Some(one)
+            } else {
+                None
+            }
+        });
+        self.summary
+            .properties
+            .iter()
+            .find(|(name, _)| {
+                name.eq_ignore_ascii_case(key)
+                    || alias.is_some_and(|alias| name.eq_ignore_ascii_case(alias))
+            })
+            .map(|(_, values)| values.as_slice())
+    }
+
+    fn matches_properties(&self, properties: &[(String, String)]) -> bool {
+        properties.iter().all(|(key, value)| {
+            self.property(key)
+                .is_some_and(|values| values.iter().any(|text| normalize(text).contains(value)))
+        })
+    }
+}
+
+/// Filter query split into `key:value` terms for front matter and fuzzy text for the
+/// title and path.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct FilterQuery {
+    pub(crate) text: String,
+    /// Normalized keys and values.
+    properties: Vec<(String, String)>,
+}
+
+impl FilterQuery {
+    pub(crate) fn parse(query: &str) -> Self {
+        let mut filter = Self::default();
+        let mut text = Vec::new();
+        for term in query.split_whitespace() {
+            match term.split_once(':') {
+                Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+                    filter.properties.push((normalize(key), normalize(value)));
+                }
+                _ => text.push(term),
+            }
+        }
+        filter.text = text.join(" ");
+        filter
     }
 }
 
@@ -65,7 +155,7 @@ pub(crate) enum DiscoveryEvent {
 pub(crate) fn start_discovery(root: PathBuf) -> Receiver<DiscoveryEvent> {
     let (sender, receiver) = mpsc::sync_channel(DISCOVERY_CHANNEL_CAPACITY);
     std::thread::spawn(move || {
-        let completed = walk_paths(&root, |event| sender.send(event).is_ok());
+        let completed = walk_paths(&root, true, |event| sender.send(event).is_ok());
         if completed {
             let _ = sender.send(DiscoveryEvent::Finished);
         }
@@ -73,10 +163,124 @@ pub(crate) fn start_discovery(root: PathBuf) -> Receiver<DiscoveryEvent> {
     receiver
 }
 
+/// Line of a document that contains the query of a content search.
+#[derive(Debug, Clone)]
+pub(crate) struct ContentMatch {
+    pub(crate) document: DocumentEntry,
+    /// Source line, counted from 1.
+    pub(crate) line: usize,
+    /// The trimmed line, shortened before its first match.
+    pub(crate) snippet: String,
+    /// Character indices of the matched text in `snippet`.
+    pub(crate) highlights: Vec<usize>,
+}
+
+pub(crate) enum ContentSearchEvent {
+    Match(ContentMatch),
+    Error(String),
+    Finished,
+}
+
+/// Content search running in the background; dropping it stops the walk.
+pub(crate) struct ContentSearch {
+    receiver: Receiver<ContentSearchEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ContentSearch {
+    /// Reads every Markdown file under `root` for lines that contain `query`, ignoring
+    /// case and accents.
+    pub(crate) fn start(root: PathBuf, query: &str) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(DISCOVERY_CHANNEL_CAPACITY);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let query = normalize(query);
+        let stop = cancelled.clone();
+        std::thread::spawn(move || {
+            let mut found = 0;
+            walk_paths(&root, false, |event| {
+                let DiscoveryEvent::Document(document) = event else {
+                    // Discovery already reports the errors of the walk.
+                    return true;
+                };
+                if stop.load(Ordering::Relaxed) {
+                    return false;
+                }
+                let text = match std::fs::read_to_string(&document.path) {
+                    Ok(text) => text,
+                    Err(error) => {
+                        let error = format!("{}: {error}", document.relative_path);
+                        return sender.send(ContentSearchEvent::Error(error)).is_ok();
+                    }
+                };
+                for content_match in find_content_matches(&document, &text, &query) {
+                    if found == CONTENT_MATCH_LIMIT
+                        || sender
+                            .send(ContentSearchEvent::Match(content_match))
+                            .is_err()
+                    {
+                        return false;
+                    }
+                    found += 1;
+                }
+                true
+            });
+            let _ = sender.send(ContentSearchEvent::Finished);
+        });
+        Self {
+            receiver,
+            cancelled,
+        }
+    }
+
+    pub(crate) fn try_recv(&self) -> Result<ContentSearchEvent, TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
+impl Drop for ContentSearch {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Lines of `text` that contain the normalized `query`.
+fn find_content_matches(document: &DocumentEntry, text: &str, query: &str) -> Vec<ContentMatch> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    text.lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let trimmed = line.trim();
+            let (normalized, original_indices) = normalize_with_indices(trimmed);
+            let query_length = query.chars().count();
+            let mut highlights: Vec<usize> = Vec::new();
+            for (start, _) in normalized.match_indices(query) {
+                let first = normalized[..start].chars().count();
+                highlights.extend(&original_indices[first..first + query_length]);
+            }
+            highlights.dedup();
+            let skipped = highlights.first()?.saturating_sub(SNIPPET_CONTEXT);
+            let ellipsis = usize::from(skipped > 0);
+            let mut snippet = "…".repeat(ellipsis);
+            snippet.extend(trimmed.chars().skip(skipped));
+            Some(ContentMatch {
+                document: document.clone(),
+                line: index + 1,
+                snippet,
+                highlights: highlights
+                    .iter()
+                    .map(|index| index - skipped + ellipsis)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 pub(crate) fn discover_paths(root: &Path) -> DiscoveryResult {
     let mut result = DiscoveryResult::default();
-    walk_paths(root, |event| {
+    walk_paths(root, true, |event| {
         match event {
             DiscoveryEvent::Document(document) => result.documents.push(document),
             DiscoveryEvent::Error(error) => result.errors.push(error),
@@ -90,7 +294,81 @@ pub(crate) fn discover_paths(root: &Path) -> DiscoveryResult {
     result
 }
 
-fn walk_paths(root: &Path, mut emit: impl FnMut(DiscoveryEvent) -> bool) -> bool {
+fn walk_paths(
+    root: &Path,
+    summarize_documents: bool,
+    mut emit: impl FnMut(DiscoveryEvent) -> bool,
+) -> bool {
+    for entry in walk_builder(root).build() {
+        let event = match entry {
+            Ok(entry) => match document_event(entry, root, summarize_documents) {
+                Some(event) => event,
+                None => continue,
+            },
+            Err(error) => DiscoveryEvent::Error(error.to_string()),
+        };
+        if !emit(event) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Documents that discovery of `root` would list at `path` after it changed: the file
+/// itself, or every document below a directory. Nothing is emitted for a path that is
+/// gone, hidden, ignored, or not Markdown.
+pub(crate) fn discover_path(
+    root: &Path,
+    path: &Path,
+    mut emit: impl FnMut(DiscoveryEvent) -> bool,
+) -> bool {
+    if !path.starts_with(root) {
+        return true;
+    }
+    walk_toward(root, path)
+        .build()
+        .filter_map(Result::ok)
+        .filter_map(|entry| document_event(entry, root, true))
+        .all(&mut emit)
+}
+
+/// Directories that discovery of `root` walks at or below `path`: the ones a watcher
+/// has to follow, without hidden, ignored, or `node_modules` trees. The walk is lazy, so
+/// a caller can stop it between directories.
+pub(crate) fn discovered_directories(
+    root: &Path,
+    path: &Path,
+) -> impl Iterator<Item = PathBuf> + use<> {
+    let target = path.to_path_buf();
+    let walk = path
+        .starts_with(root)
+        .then(|| walk_toward(root, path).build());
+    walk.into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(move |entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+                && entry.path().starts_with(&target)
+        })
+        .map(ignore::DirEntry::into_path)
+}
+
+/// Walk of `root` limited to the directories on the way to `path` and everything below
+/// it. Walking from the root applies the ignore rules of every directory above the path.
+fn walk_toward(root: &Path, path: &Path) -> WalkBuilder {
+    let target = path.to_path_buf();
+    let mut builder = walk_builder(root);
+    // The filter replaces the one of `walk_builder`, so it skips `node_modules` again.
+    builder.filter_entry(move |entry| {
+        entry.file_name() != "node_modules"
+            && (target.starts_with(entry.path()) || entry.path().starts_with(&target))
+    });
+    builder
+}
+
+fn walk_builder(root: &Path) -> WalkBuilder {
     let mut builder = WalkBuilder::new(root);
     builder
         .hidden(true)
@@ -100,58 +378,62 @@ fn walk_paths(root: &Path, mut emit: impl FnMut(DiscoveryEvent) -> bool) -> bool
         .require_git(false)
         .follow_links(false)
         .filter_entry(|entry| entry.file_name() != "node_modules");
+    builder
+}
 
-    for entry in builder.build() {
-        let entry = match entry {
-            Ok(entry) => entry,
-            Err(error) => {
-                if !emit(DiscoveryEvent::Error(error.to_string())) {
-                    return false;
-                }
-                continue;
-            }
-        };
-        let Some(file_type) = entry.file_type() else {
-            continue;
-        };
-        if !file_type.is_file() || !is_markdown_path(entry.path()) {
-            continue;
-        }
-
-        match entry.metadata() {
-            Ok(metadata) => {
-                let document = DocumentEntry::new(
-                    entry.into_path(),
-                    root,
-                    metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
-                );
-                if !emit(DiscoveryEvent::Document(document)) {
-                    return false;
-                }
-            }
-            Err(error) => {
-                if !emit(DiscoveryEvent::Error(error.to_string())) {
-                    return false;
-                }
-            }
-        }
+/// Document event for a Markdown file of the walk, relative to `root`.
+fn document_event(
+    entry: ignore::DirEntry,
+    root: &Path,
+    summarize_documents: bool,
+) -> Option<DiscoveryEvent> {
+    if !entry.file_type()?.is_file() || !is_markdown_path(entry.path()) {
+        return None;
     }
-    true
+    let metadata = match entry.metadata() {
+        Ok(metadata) => metadata,
+        Err(error) => return Some(DiscoveryEvent::Error(error.to_string())),
+    };
+    // Content search reads the file itself and needs no summary.
+    let summary = if summarize_documents {
+        summarize_file(entry.path()).unwrap_or_default()
+    } else {
+        DocumentSummary::default()
+    };
+    Some(DiscoveryEvent::Document(DocumentEntry::new(
+        entry.into_path(),
+        root,
+        metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        metadata.len(),
+        summary,
+    )))
 }
 
+/// Indices of the documents that have every `key:value` property of `query` and match
+/// its remaining text by path or title, best matches first.
 pub(crate) fn filter_documents(documents: &[DocumentEntry], query: &str) -> Vec<usize> {
     if query.is_empty() {
         return (0..documents.len()).collect();
     }
 
-    let query = normalize(query);
+    let query = FilterQuery::parse(query);
+    let text = normalize(&query.text);
     let matcher = SkimMatcherV2::default();
     let mut matches: Vec<_> = documents
         .iter()
         .enumerate()
+        .filter(|(_, document)| document.matches_properties(&query.properties))
         .filter_map(|(index, document)| {
+            if text.is_empty() {
+                return Some((index, 0));
+            }
+            let title_score = document
+                .title_filter_value
+                .as_deref()
+                .and_then(|title| matcher.fuzzy_match(title, &text));
             matcher
-                .fuzzy_match(&document.filter_value, &query)
+                .fuzzy_match(&document.filter_value, &text)
+                .max(title_score)
                 .map(|score| (index, score))
         })
         .collect();
@@ -183,16 +465,6 @@ fn fuzzy_match_indices(text: &str, query: &str) -> Vec<usize> {
     matches
 }
 
-fn is_markdown_path(path: &Path) -> bool {
-    path.extension()
-        .and_then(|extension| extension.to_str())
-        .is_some_and(|extension| {
-            MARKDOWN_EXTENSIONS
-                .iter()
-                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
-        })
-}
-
 fn normalize(text: &str) -> String {
     text.nfd()
         .filter(|character| !is_combining_mark(*character))
@@ -220,6 +492,70 @@ fn normalize_with_indices(text: &str) -> (String, Vec<usize>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::markdown::summarize;
+
+    #[test]
+    fn content_matches_highlight_the_query_in_a_shortened_snippet() {
+        let document = DocumentEntry::for_test("docs/retries.md");
+        let text = "# Retries\n\n  Each client keeps a Retry Budget.\nNothing here.\n";
+        let matches = find_content_matches(&document, text, &normalize("retry budget"));
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 3);
+        assert_eq!(matches[0].snippet, "Each client keeps a Retry Budget.");
+        assert_eq!(matches[0].highlights, (20..32).collect::<Vec<_>>());
+
+        let long = format!("{} résumé", "x".repeat(40));
+        let matches = find_content_matches(&document, &long, &normalize("RESUME"));
+        let snippet: Vec<char> = matches[0].snippet.chars().collect();
+        assert_eq!(snippet[0], '…');
+        let highlighted: String = matches[0]
+            .highlights
+            .iter()
+            .map(|index| snippet[*index])
+            .collect();
+        assert_eq!(highlighted, "résumé");
+        assert!(find_content_matches(&document, text, " ").is_empty());
+    }
+
+    #[test]
+    fn property_terms_filter_by_front_matter_and_text_matches_titles() {
+        let documents = [
+            DocumentEntry::for_test("ops/retries.md").with_summary(summarize(
+                "---\ntitle: Retry budgets\ntags: [runbook, ops]\nstatus: draft\n---\n",
+            )),
+            DocumentEntry::for_test("ops/deploys.md").with_summary(summarize(
+                "---\ntags: runbook\nstatus: published\n---\n# Deploy checklist\n",
+            )),
+            DocumentEntry::for_test("notes.md")
+                .with_summary(summarize("---\ntag: draft\nstatuses: [draft]\n---\n")),
+        ];
+
+        assert_eq!(
+            filter_documents(&documents, "tag:runbook status:draft"),
+            [0]
+        );
+        assert_eq!(filter_documents(&documents, "tag:RUNBOOK"), [0, 1]);
+        assert_eq!(filter_documents(&documents, "tag:runbook checklist"), [1]);
+        assert_eq!(filter_documents(&documents, "tags:draft"), [2]);
+        assert_eq!(filter_documents(&documents, "status:draft"), [0]);
+        assert_eq!(
+            filter_documents(&documents, "statu:draft"),
+            [] as [usize; 0]
+        );
+        assert_eq!(filter_documents(&documents, "owner:ann"), [] as [usize; 0]);
+        assert_eq!(
+            documents[0].match_indices("status:draft budg"),
+            [6, 7, 8, 9]
+        );
+        assert_eq!(
+            FilterQuery::parse("a:b text: :c"),
+            FilterQuery {
+                text: "text: :c".to_string(),
+                properties: vec![("a".to_string(), "b".to_string())],
+            }
+        );
+    }
 
     #[test]
     fn fuzzy_indices_point_to_original_characters_after_normalization() {
@@ -1,22 +1,28 @@
 mod app;
 pub(crate) mod browser;
 pub(crate) mod discovery;
+mod preview;
 pub(crate) mod screen;
+mod watcher;
 
+use crate::clipboard::Clipboard;
 use crate::config::Config;
 use crate::editor::EditorCommand;
-use crate::pager::{self, PagerDocument, PagerScreen, RefreshCallback};
+use crate::keybindings::KeyScope;
+use crate::pager::{self, DocumentLoader, PagerDocument, PagerScreen};
 use anyhow::{Result, anyhow, ensure};
 use app::{App, AppAction};
 use crossterm::event::{self, Event};
 use screen::TerminalSession;
 use std::io::{IsTerminal, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 const MAX_FRAMES_PER_SECOND: u64 = 120;
 const FRAME_INTERVAL: Duration = Duration::from_nanos(1_000_000_000 / MAX_FRAMES_PER_SECOND);
+/// How often an idle browser checks for changes reported by the file watcher.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum InteractiveTarget {
@@ -65,7 +71,7 @@ pub(crate) fn run(target: InteractiveTarget, config: Config) -> Result<()> {
     let root = match target {
         InteractiveTarget::Directory(root) => root,
         InteractiveTarget::File(path) => {
-            return open_file_in_pager(path, &config, PagerScreen::Alternate);
+            return open_files_in_pager(vec![path], 0, None, &config, PagerScreen::Alternate);
         }
         InteractiveTarget::Stdin => {
             let mut source = String::new();
@@ -82,8 +88,8 @@ pub(crate) fn run(target: InteractiveTarget, config: Config) -> Result<()> {
 
     loop {
         let was_loading = app.is_loading();
-        app.tick();
-        redraw_pending |= was_loading;
+        let watched = app.tick();
+        redraw_pending |= was_loading || watched;
 
         let now = Instant::now();
         if redraw_pending && now >= next_frame {
@@ -92,14 +98,20 @@ pub(crate) fn run(target: InteractiveTarget, config: Config) -> Result<()> {
             next_frame = Instant::now() + FRAME_INTERVAL;
         }
 
-        let terminal_event = if app.is_loading() || redraw_pending {
-            let timeout = next_frame.saturating_duration_since(Instant::now());
-            if !event::poll(timeout)? {
-                continue;
-            }
-            event::read()?
+        // An idle browser still wakes up to apply file system changes.
+        let timeout = if app.is_loading() || redraw_pending {
+            Some(next_frame.saturating_duration_since(Instant::now()))
         } else {
-            event::read()?
+            app.is_watching().then_some(WATCH_POLL_INTERVAL)
+        };
+        let terminal_event = match timeout {
+            Some(timeout) => {
+                if !event::poll(timeout)? {
+                    continue;
+                }
+                event::read()?
+            }
+            None => event::read()?,
         };
         redraw_pending = true;
         let action = match terminal_event {
@@ -122,9 +134,10 @@ pub(crate) fn run(target: InteractiveTarget, config: Config) -> Result<()> {
         match action {
             AppAction::None => {}
             AppAction::Quit => return Ok(()),
-            AppAction::OpenPager(path) => {
+            AppAction::OpenPager(paths, active, line) => {
                 terminal.pause_for_pager()?;
-                let result = open_file_in_pager(path, &config, PagerScreen::InPlace);
+                let result =
+                    open_files_in_pager(paths, active, line, &config, PagerScreen::InPlace);
                 terminal.resume_after_pager()?;
                 app.after_pager(result);
             }
@@ -143,24 +156,49 @@ pub(crate) fn run(target: InteractiveTarget, config: Config) -> Result<()> {
     }
 }
 
-fn open_file_in_pager(path: PathBuf, config: &Config, screen: PagerScreen) -> Result<()> {
-    let document = crate::render_document_file(&path, config, false, false, None)?;
-    let refresh_path = path.clone();
-    let refresh_config = config.clone();
-    let refresh = Arc::new(move || {
-        crate::render_document_file(&refresh_path, &refresh_config, false, false, None)
-    }) as RefreshCallback;
-    pager::page(document, Some(path), Some(refresh), screen)
+/// Opens `paths` as pager tabs, showing the one at `active` first, at source `line` when
+/// given.
+fn open_files_in_pager(
+    paths: Vec<PathBuf>,
+    active: usize,
+    line: Option<usize>,
+    config: &Config,
+    screen: PagerScreen,
+) -> Result<()> {
+    let loader_config = config.clone();
+    let loader = Arc::new(move |path: &Path| {
+        crate::render_document_file(path, &loader_config, None, false, None)
+    }) as DocumentLoader;
+    let documents = paths
+        .into_iter()
+        .map(|path| Ok((loader(&path)?, Some(path))))
+        .collect::<Result<Vec<_>>>()?;
+    pager::page(
+        documents,
+        active,
+        line,
+        Some(loader),
+        screen,
+        config.keybindings.resolve(KeyScope::Pager),
+        Clipboard::from_config(config),
+    )
 }
 
 fn open_source_in_pager(source: String, config: &Config) -> Result<()> {
-    let rendered = crate::render_document(&source, config, false, false, None, true)?;
+    let rendered = crate::render_document(&source, config, None, None, false, None, true)?;
     pager::page(
-        PagerDocument::new(rendered.output, source)
-            .with_status_bar_transparent(rendered.pager_status_bar_transparent),
+        vec![(
+            PagerDocument::new(rendered.output, source)
+                .with_colors(rendered.pager_colors)
+                .with_map(rendered.map),
+            None,
+        )],
+        0,
         None,
         None,
         PagerScreen::Alternate,
+        config.keybindings.resolve(KeyScope::Pager),
+        Clipboard::from_config(config),
     )
 }
 
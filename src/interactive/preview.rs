@@ -0,0 +1,266 @@
+use super::discovery::DocumentEntry;
+use crate::config::Config;
+use crate::utils::strip_ansi;
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant, SystemTime};
+use unicode_width::UnicodeWidthChar;
+
+/// Narrowest terminal that shows the preview pane.
+const PREVIEW_MIN_WIDTH: u16 = 100;
+/// Share of the terminal width, in percent, taken by the document list.
+const LIST_WIDTH_PERCENT: u16 = 45;
+/// Columns between the list and the pane: a space, the separator, and a space.
+pub(super) const PREVIEW_GAP: u16 = 3;
+/// Time the selection has to stay on a document before it is rendered.
+const PREVIEW_DELAY: Duration = Duration::from_millis(120);
+const PREVIEW_CACHE_CAPACITY: usize = 32;
+/// Rendered lines kept per document; the pane never shows more than a screen.
+const PREVIEW_MAX_LINES: usize = 256;
+
+/// Widths of the document list and the preview pane, when the terminal is wide enough.
+pub(super) fn preview_layout(width: u16) -> Option<(u16, u16)> {
+    if width < PREVIEW_MIN_WIDTH {
+        return None;
+    }
+    let list = width * LIST_WIDTH_PERCENT / 100;
+    Some((list, width - list - PREVIEW_GAP))
+}
+
+/// Version of a document at a pane width; a modified file gets a new key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PreviewKey {
+    path: PathBuf,
+    modified: SystemTime,
+    width: u16,
+}
+
+/// Rendered lines of a document, or why it could not be rendered.
+pub(super) type Preview = Result<Vec<String>, String>;
+
+/// Renders the selected document on a worker thread once the selection settles, and
+/// keeps the latest previews.
+pub(super) struct PreviewPane {
+    requests: Sender<PreviewKey>,
+    results: Receiver<(PreviewKey, Preview)>,
+    cache: HashMap<PreviewKey, Preview>,
+    /// Cached keys from the oldest.
+    order: VecDeque<PreviewKey>,
+    /// Document the pane should show and when it was selected.
+    wanted: Option<(PreviewKey, Instant)>,
+    in_flight: Option<PreviewKey>,
+}
+
+impl PreviewPane {
+    pub(super) fn new(config: &Config) -> Self {
+        let (requests, worker_requests) = mpsc::channel::<PreviewKey>();
+        let (worker_results, results) = mpsc::channel();
+        let config = config.clone();
+        std::thread::spawn(move || {
+            while let Ok(mut key) = worker_requests.recv() {
+                // Only the latest selection matters.
+                while let Ok(newer) = worker_requests.try_recv() {
+                    key = newer;
+                }
+                let preview = render_preview(&key.path, &config, key.width)
+                    .map_err(|error| format!("{error:#}"));
+                if worker_results.send((key, preview)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            requests,
+            results,
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            wanted: None,
+            in_flight: None,
+        }
+    }
+
+    /// Sets the document to show and the pane width, or clears the pane.
+    pub(super) fn select(&mut self, selection: Option<(&DocumentEntry, u16)>) {
+        let key = selection.map(|(document, width)| PreviewKey {
+            path: document.path.clone(),
+            modified: document.modified,
+            width,
+        });
+        if self.wanted.as_ref().map(|(wanted, _)| wanted) != key.as_ref() {
+            self.wanted = key.map(|key| (key, Instant::now()));
+        }
+    }
+
+    /// Takes finished previews and requests the wanted one once the selection settles.
+    /// Returns whether a preview arrived.
+    pub(super) fn poll(&mut self) -> bool {
+        let mut received = false;
+        while let Ok((key, preview)) = self.results.try_recv() {
+            if self.in_flight.as_ref() == Some(&key) {
+                self.in_flight = None;
+            }
+            self.insert(key, preview);
+            received = true;
+        }
+        if let Some((key, selected)) = &self.wanted
+            && !self.cache.contains_key(key)
+            && self.in_flight.as_ref() != Some(key)
+            && selected.elapsed() >= PREVIEW_DELAY
+            && self.requests.send(key.clone()).is_ok()
+        {
+            self.in_flight = Some(key.clone());
+        }
+        received
+    }
+
+    /// Whether the wanted preview is still waiting or rendering.
+    pub(super) fn is_pending(&self) -> bool {
+        self.wanted
+            .as_ref()
+            .is_some_and(|(key, _)| !self.cache.contains_key(key))
+    }
+
+    pub(super) fn current(&self) -> Option<&Preview> {
+        self.wanted
+            .as_ref()
+            .and_then(|(key, _)| self.cache.get(key))
+    }
+
+    fn insert(&mut self, key: PreviewKey, preview: Preview) {
+        if self.cache.insert(key.clone(), preview).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > PREVIEW_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.cache.remove(&oldest);
+            }
+        }
+    }
+}
+
+fn render_preview(path: &Path, config: &Config, width: u16) -> Result<Vec<String>> {
+    let mut content = std::fs::read_to_string(path)?;
+    crate::strip_leading_bom(&mut content);
+    let mut config = config.clone();
+    config.cols = Some(usize::from(width));
+    config.cols_from_cli = true;
+    config.source_map = false;
+    config.document_dir = path.parent().map(Path::to_path_buf);
+    let rendered = crate::render_document(&content, &config, None, None, false, None, false)?;
+    Ok(rendered
+        .output
+        .lines()
+        .take(PREVIEW_MAX_LINES)
+        .map(|line| fit_line(line, usize::from(width)))
+        .collect())
+}
+
+/// Cuts a rendered line to `width` columns, keeping its color sequences, and resets the
+/// style after it.
+pub(super) fn fit_line(line: &str, width: usize) -> String {
+    if crate::utils::display_width(&strip_ansi(line)) <= width {
+        return format!("{line}\x1b[0m");
+    }
+    let mut output = String::with_capacity(line.len());
+    let mut used = 0;
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character == '\x1b' {
+            output.push(character);
+            match characters.next() {
+                Some('[') => {
+                    output.push('[');
+                    for next in characters.by_ref() {
+                        output.push(next);
+                        if ('@'..='~').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+                Some(']') => {
+                    output.push(']');
+                    while let Some(next) = characters.next() {
+                        output.push(next);
+                        if next == '\x07' {
+                            break;
+                        }
+                        if next == '\x1b' && characters.peek() == Some(&'\\') {
+                            output.push('\\');
+                            characters.next();
+                            break;
+                        }
+                    }
+                }
+                Some(next) => output.push(next),
+                None => {}
+            }
+            continue;
+        }
+        let character_width = character.width().unwrap_or(0);
+        if used + character_width > width {
+            break;
+        }
+        used += character_width;
+        output.push(character);
+    }
+    output.push_str("\x1b[0m");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn narrow_terminals_have_no_preview_pane() {
+        assert_eq!(preview_layout(99), None);
+        assert_eq!(preview_layout(120), Some((54, 63)));
+    }
+
+    #[test]
+    fn long_lines_are_cut_without_dropping_color_sequences() {
+        let line = "\x1b[31mred\x1b[0m \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+        let fitted = fit_line(line, 6);
+
+        assert_eq!(strip_ansi(&fitted), "red li");
+        assert!(fitted.starts_with("\x1b[31mred\x1b[0m \x1b]8;;https://example.com\x1b\\"));
+        assert_eq!(fit_line("short", 10), "short\x1b[0m");
+    }
+
+    #[test]
+    fn previews_render_off_the_ui_thread_once_the_selection_settles() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("guide.md");
+        std::fs::write(&path, "# Guide\n\nPreview text.\n").unwrap();
+        let mut document = DocumentEntry::for_test("guide.md");
+        document.path = path;
+        let mut pane = PreviewPane::new(&Config::default());
+
+        pane.select(Some((&document, 40)));
+        pane.poll();
+        assert!(pane.is_pending());
+        assert!(pane.current().is_none());
+
+        std::thread::sleep(PREVIEW_DELAY);
+        for _ in 0..500 {
+            pane.poll();
+            if !pane.is_pending() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let lines = pane.current().unwrap().as_ref().unwrap();
+        assert!(
+            lines
+                .iter()
+                .any(|line| strip_ansi(line).contains("Preview text."))
+        );
+
+        pane.select(None);
+        assert!(!pane.is_pending());
+        pane.select(Some((&document, 40)));
+        assert!(!pane.is_pending(), "the cached preview is reused");
+    }
+}
@@ -1,9 +1,15 @@
 use super::app::App;
-use super::browser::{BrowserSection, BrowserState, FilterState};
+use super::browser::{
+    BrowserSection, BrowserSort, BrowserState, BrowserView, FilterMode, FilterState, ListRow,
+    file_name,
+};
+use super::discovery::{ContentMatch, DocumentEntry};
+use crate::keybindings::{KeyAction, KeyBindings};
+use crate::reading::ReadingRecord;
 use crate::terminal::AnsiStyle;
 use crate::utils::display_width;
 use anyhow::{Context, Result};
-use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::cursor::{Hide, MoveTo, MoveToColumn, Show};
 use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
 use crossterm::style::{Color, ResetColor};
 use crossterm::terminal::{
@@ -51,50 +57,6 @@ const BROWSER_HELP_SEPARATOR: Color = Color::Rgb {
     g: 60,
     b: 60,
 };
-const BROWSER_MINI_HELP: &[(&str, &str)] = &[
-    ("h/l ←/→", "page"),
-    ("/", "find"),
-    ("r", "refresh"),
-    ("e", "edit"),
-    ("q", "quit"),
-    ("?", "more"),
-];
-const BROWSER_FILTERED_MINI_HELP: &[(&str, &str)] = &[
-    ("tab", "section"),
-    ("/", "edit search"),
-    ("esc", "clear filter"),
-    ("r", "refresh"),
-    ("e", "edit"),
-    ("q", "quit"),
-    ("?", "more"),
-];
-const BROWSER_FULL_HELP_ROWS: [[Option<(&str, &str)>; 4]; 4] = [
-    [
-        Some(("enter", "open")),
-        Some(("/", "find")),
-        Some(("e", "edit")),
-        Some(("r", "refresh")),
-    ],
-    [
-        Some(("j/k ↑/↓", "choose")),
-        Some(("esc", "clear")),
-        Some(("!", "errors")),
-        Some(("q", "quit")),
-    ],
-    [
-        Some(("h/l ←/→", "page")),
-        Some(("tab", "section")),
-        Some(("?", "close help")),
-        None,
-    ],
-    [
-        Some(("g/home", "first")),
-        Some(("G/end", "last")),
-        None,
-        None,
-    ],
-];
-
 mod draw;
 mod frame;
 mod header;
@@ -7,12 +7,11 @@ pub(super) fn draw_browser(frame: &mut ScreenFrame, app: &App) -> Result<()> {
         draw_browser_error(frame, browser, app.width, app.height, no_colors);
         return Ok(());
     }
+    let layout = app.preview_layout();
+    let width = layout.map_or(app.width, |(list_width, _)| list_width);
 
     if browser.filter_state() == FilterState::Editing {
-        let filter_text = truncate_plain(
-            &format!("Find: {}", browser.query()),
-            app.width.saturating_sub(3) as usize,
-        );
+        let filter_text = browser_filter_text(browser, width);
         let filter = browser_filter_prompt_text(&filter_text, no_colors);
         frame.write_line(1, &filter);
     } else {
@@ -22,12 +21,18 @@ pub(super) fn draw_browser(frame: &mut ScreenFrame, app: &App) -> Result<()> {
     let header = browser_header(browser, no_colors);
     frame.write_line(3, &format!("   {header}"));
 
-    let visible = browser.visible_indices();
+    let visible = browser.visible_rows();
     let page_start = browser.page() * browser.per_page();
     let page_end = (page_start + browser.per_page()).min(visible.len());
-    let available_width = app.width.saturating_sub(8) as usize;
+    let available_width = width.saturating_sub(8) as usize;
     if page_start == page_end {
-        let message = if browser.is_loaded() {
+        let message = if browser.shows_content_matches() {
+            if browser.is_searching_contents() {
+                "Searching file contents..."
+            } else {
+                "No matches found."
+            }
+        } else if browser.is_loaded() {
             "No files found."
         } else {
             "Looking for local files..."
@@ -40,8 +45,7 @@ pub(super) fn draw_browser(frame: &mut ScreenFrame, app: &App) -> Result<()> {
             ),
         );
     } else {
-        for (row, document_index) in visible[page_start..page_end].iter().enumerate() {
-            let document = &browser.documents()[*document_index];
+        for (row, list_row) in visible[page_start..page_end].iter().enumerate() {
             let y = 5 + (row * 3) as u16;
             let selected = browser_item_selected(browser, row);
             let title_color = if selected {
@@ -54,19 +58,103 @@ pub(super) fn draw_browser(frame: &mut ScreenFrame, app: &App) -> Result<()> {
             } else {
                 rgb(98, 98, 98)
             };
-            let title = truncate_plain(&sanitize_display(&document.relative_path), available_width);
-            let title = if browser.filter_state() == FilterState::Editing
-                || browser.section() == BrowserSection::Filter
-            {
-                filtered_title(document, &title, browser.query(), title_color, no_colors)
+            let prefix = item_prefix(selected, no_colors);
+            let index = match list_row {
+                ListRow::Document(index) => *index,
+                ListRow::Directory {
+                    path,
+                    depth,
+                    documents,
+                    collapsed,
+                } => {
+                    let indent = "  ".repeat(*depth);
+                    let marker = if *collapsed { "▸" } else { "▾" };
+                    let name = truncate_plain(
+                        &sanitize_display(&format!("{marker} {}/", file_name(path))),
+                        available_width.saturating_sub(indent.len()),
+                    );
+                    let count = if *documents == 1 {
+                        "1 document".to_string()
+                    } else {
+                        format!("{documents} documents")
+                    };
+                    let name = styled(&name, Some(title_color), None, false, no_colors);
+                    let count = styled(&count, Some(date_color), None, false, no_colors);
+                    frame.write_line(y, &format!("{prefix}{indent}{name}"));
+                    frame.write_line(y + 1, &format!("{prefix}{indent}  {count}"));
+                    continue;
+                }
+            };
+            let content_match = browser
+                .shows_content_matches()
+                .then(|| &browser.content_matches()[index]);
+            let document = content_match.map_or(&browser.documents()[index], |content_match| {
+                &content_match.document
+            });
+            // Tree rows sit under their directory and need only the file name.
+            let (indent, name) = if browser.shows_tree() {
+                let depth = document.relative_path.matches('/').count();
+                let name = document
+                    .summary
+                    .title
+                    .as_deref()
+                    .unwrap_or_else(|| file_name(&document.relative_path));
+                ("  ".repeat(depth), name)
             } else {
-                styled(&title, Some(title_color), None, false, no_colors)
+                (String::new(), document.display_name())
             };
-            let date_text = relative_time(document.modified)?;
-            let date = styled(&date_text, Some(date_color), None, false, no_colors);
-            let prefix = item_prefix(selected, no_colors);
-            frame.write_line(y, &format!("{prefix}{title}"));
-            frame.write_line(y + 1, &format!("{prefix}{date}"));
+            let available_width = available_width.saturating_sub(indent.len());
+            let marked = browser.is_marked(document);
+            let title_width = available_width.saturating_sub(if marked { 2 } else { 0 });
+            let title = match content_match {
+                Some(content_match) => styled(
+                    &truncate_plain(
+                        &sanitize_display(&format!(
+                            "{}:{}",
+                            document.relative_path, content_match.line
+                        )),
+                        title_width,
+                    ),
+                    Some(title_color),
+                    None,
+                    false,
+                    no_colors,
+                ),
+                None => {
+                    let title = truncate_plain(&sanitize_display(name), title_width);
+                    if browser.filter_state() == FilterState::Editing
+                        || browser.section() == BrowserSection::Filter
+                    {
+                        filtered_title(document, &title, browser.query(), title_color, no_colors)
+                    } else {
+                        styled(&title, Some(title_color), None, false, no_colors)
+                    }
+                }
+            };
+            let date = match content_match {
+                Some(content_match) => {
+                    content_snippet(content_match, available_width, date_color, no_colors)
+                }
+                None => {
+                    let date_text = truncate_plain(
+                        &sanitize_display(&card_details(
+                            document,
+                            browser.is_new(document),
+                            browser.reading_record(document),
+                            &app.config.browser_metadata,
+                        )?),
+                        available_width,
+                    );
+                    styled(&date_text, Some(date_color), None, false, no_colors)
+                }
+            };
+            let mark = if marked {
+                styled("+ ", Some(BROWSER_ACCENT), None, true, no_colors)
+            } else {
+                String::new()
+            };
+            frame.write_line(y, &format!("{prefix}{indent}{mark}{title}"));
+            frame.write_line(y + 1, &format!("{prefix}{indent}{date}"));
         }
     }
 
@@ -74,20 +162,57 @@ pub(super) fn draw_browser(frame: &mut ScreenFrame, app: &App) -> Result<()> {
     if browser.page_count() > 1 {
         frame.write_line(
             pagination_y,
-            &format!("   {}", pagination(browser, app.width as usize, no_colors)),
+            &format!("   {}", pagination(browser, width as usize, no_colors)),
         );
     }
-    draw_browser_help(frame, browser, help_y, app.width as usize, no_colors);
+    draw_browser_help(frame, browser, &app.keys, help_y, width as usize, no_colors);
     if browser.filter_state() == FilterState::Editing {
-        let filter_text = truncate_plain(
-            &format!("Find: {}", browser.query()),
-            app.width.saturating_sub(3) as usize,
-        );
-        frame.show_cursor_at(browser_filter_cursor_x(&filter_text, app.width), 1);
+        let filter_text = browser_filter_text(browser, width);
+        frame.show_cursor_at(browser_filter_cursor_x(&filter_text, width), 1);
+    }
+    if let Some((list_width, pane_width)) = layout {
+        draw_preview(frame, app, list_width, pane_width, no_colors);
     }
     Ok(())
 }
 
+/// Rendered selected document to the right of the list, from the logo row down.
+fn draw_preview(
+    frame: &mut ScreenFrame,
+    app: &App,
+    list_width: u16,
+    pane_width: u16,
+    no_colors: bool,
+) {
+    let separator = styled("│", Some(BROWSER_HELP_SEPARATOR), None, false, no_colors);
+    let status = |message: &str, color| {
+        styled(
+            &truncate_plain(&sanitize_display(message), usize::from(pane_width)),
+            Some(color),
+            None,
+            false,
+            no_colors,
+        )
+    };
+    let message;
+    let lines: &[String] = match app.preview.current() {
+        Some(Ok(lines)) => lines,
+        Some(Err(error)) => {
+            message = [status(error, rgb(237, 86, 122))];
+            &message
+        }
+        None if app.preview.is_pending() => {
+            message = [status("Rendering preview...", rgb(98, 98, 98))];
+            &message
+        }
+        None => &[],
+    };
+    for y in 1..app.height.saturating_sub(1) {
+        let line = lines.get(usize::from(y - 1)).map_or("", String::as_str);
+        frame.write_pane(list_width + 1, y, &format!("{separator} {line}"));
+    }
+}
+
 pub(super) fn draw_browser_error(
     frame: &mut ScreenFrame,
     browser: &BrowserState,
@@ -121,3 +246,42 @@ pub(super) fn draw_browser_error(
     );
     frame.write_line(height.saturating_sub(2), &format!("   {prompt}"));
 }
+
+/// Second row of a document card: whether the document is new, the path when the title
+/// took the first row, the document details, and the configured front matter properties.
+pub(super) fn card_details(
+    document: &DocumentEntry,
+    is_new: bool,
+    reading: Option<&ReadingRecord>,
+    metadata: &[String],
+) -> Result<String> {
+    let mut details = Vec::new();
+    if is_new {
+        details.push("new".to_string());
+    }
+    if document.summary.title.is_some() {
+        details.push(document.relative_path.clone());
+    }
+    details.push(document_details(document.modified, reading)?);
+    for key in metadata {
+        if let Some(values) = document.property(key) {
+            details.push(format!("{key}: {}", values.join(", ")));
+        }
+    }
+    Ok(details.join(" · "))
+}
+
+/// Modification time, followed by when the document was last read and how far.
+pub(super) fn document_details(
+    modified: SystemTime,
+    reading: Option<&ReadingRecord>,
+) -> Result<String> {
+    let modified = relative_time(modified)?;
+    match reading.and_then(|record| Some((record.last_read()?, record.progress))) {
+        Some((last_read, progress)) => Ok(format!(
+            "{modified} · read {}, {progress}%",
+            relative_time(last_read)?
+        )),
+        None => Ok(modified),
+    }
+}
@@ -23,6 +23,15 @@ impl ScreenFrame {
         }
     }
 
+    /// Draws `text` from column `x` of row `y`, after clearing the rest of what is there.
+    pub(super) fn write_pane(&mut self, x: u16, y: u16, text: &str) {
+        if let Some(row) = self.rows.get_mut(usize::from(y)) {
+            let _ = Clear(ClearType::UntilNewLine).write_ansi(row);
+            let _ = MoveToColumn(x).write_ansi(row);
+            row.push_str(text);
+        }
+    }
+
     pub(super) fn show_cursor_at(&mut self, x: u16, y: u16) {
         if x < self.width && usize::from(y) < self.rows.len() {
             self.cursor = Some((x, y));
@@ -6,16 +6,24 @@ const BROWSER_SPINNER_INTERVAL: Duration = Duration::from_millis(100);
 
 pub(super) fn browser_header(browser: &BrowserState, no_colors: bool) -> String {
     if browser.filter_state() == FilterState::Editing {
-        return styled(
-            &format!("{} local", browser.documents().len()),
-            Some(rgb(98, 98, 98)),
-            None,
-            false,
-            no_colors,
-        );
+        let count = if browser.filter_mode() == FilterMode::Content {
+            let searching = if browser.is_searching_contents() {
+                " · searching"
+            } else {
+                ""
+            };
+            format!("{} matches{searching}", browser.filtered_count())
+        } else {
+            format!("{} local", browser.documents().len())
+        };
+        return styled(&count, Some(rgb(98, 98, 98)), None, false, no_colors);
     }
 
-    let documents = format!("{} documents", browser.documents().len());
+    let documents = format!(
+        "{} documents{}",
+        browser.documents().len(),
+        browser_order_text(browser)
+    );
     if browser.filter_state() != FilterState::Applied {
         return styled(&documents, Some(rgb(98, 98, 98)), None, false, no_colors);
     }
@@ -38,6 +46,19 @@ pub(super) fn browser_header(browser: &BrowserState, no_colors: bool) -> String
     )
 }
 
+/// Sort mode and view of the list when they differ from the defaults.
+fn browser_order_text(browser: &BrowserState) -> String {
+    let mut text = String::new();
+    if browser.sort() != BrowserSort::Path {
+        text.push_str(" · by ");
+        text.push_str(browser.sort().label());
+    }
+    if browser.view() == BrowserView::Tree {
+        text.push_str(" · tree");
+    }
+    text
+}
+
 pub(super) fn pagination(browser: &BrowserState, width: usize, no_colors: bool) -> String {
     let pages = browser.page_count();
     let dots_width = pages;
@@ -63,6 +84,18 @@ pub(super) fn pagination(browser: &BrowserState, width: usize, no_colors: bool)
         .join("")
 }
 
+/// Query prompt of the filter mode, shortened to the screen.
+pub(super) fn browser_filter_text(browser: &BrowserState, width: u16) -> String {
+    let label = match browser.filter_mode() {
+        FilterMode::Name => "Find:",
+        FilterMode::Content => "Search:",
+    };
+    truncate_plain(
+        &format!("{label} {}", browser.query()),
+        width.saturating_sub(3) as usize,
+    )
+}
+
 pub(super) fn browser_filter_cursor_x(filter_text: &str, width: u16) -> u16 {
     display_width(filter_text)
         .saturating_add(3)
@@ -95,7 +128,7 @@ pub(super) fn browser_filter_prompt_text(text: &str, no_colors: bool) -> String
 }
 
 pub(super) fn filtered_title(
-    document: &crate::interactive::discovery::DocumentEntry,
+    document: &DocumentEntry,
     title: &str,
     query: &str,
     color: Color,
@@ -117,6 +150,30 @@ pub(super) fn filtered_title(
     output
 }
 
+/// Line of a content match with the matched text underlined in the accent color.
+pub(super) fn content_snippet(
+    content_match: &ContentMatch,
+    width: usize,
+    color: Color,
+    no_colors: bool,
+) -> String {
+    let snippet = truncate_plain(&sanitize_display(&content_match.snippet), width);
+    if no_colors {
+        return snippet;
+    }
+
+    let mut output = String::new();
+    for (index, character) in snippet.chars().enumerate() {
+        let style = if content_match.highlights.binary_search(&index).is_ok() {
+            AnsiStyle::new().fg(BROWSER_ACCENT).underline()
+        } else {
+            AnsiStyle::new().fg(color)
+        };
+        output.push_str(&style.apply(&character.to_string(), false));
+    }
+    output
+}
+
 pub(super) fn browser_logo_line(loading_elapsed: Option<Duration>, no_colors: bool) -> String {
     let logo = styled(
         " MDV ",
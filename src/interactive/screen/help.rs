@@ -1,11 +1,91 @@
 use super::*;
 
-pub(super) fn browser_mini_help(browser: &BrowserState, width: usize, no_colors: bool) -> String {
-    let entries = if browser.filter_state() == FilterState::Applied {
-        BROWSER_FILTERED_MINI_HELP
+type HelpEntry = (String, &'static str);
+
+fn mini_help_entries(browser: &BrowserState, keys: &KeyBindings) -> Vec<HelpEntry> {
+    let mut entries = if browser.filter_state() == FilterState::Applied {
+        vec![
+            ("tab".to_string(), "section"),
+            (keys.primary_label(filter_action(browser)), "edit search"),
+            ("esc".to_string(), "clear filter"),
+        ]
     } else {
-        BROWSER_MINI_HELP
+        vec![
+            (
+                keys.pair_label(KeyAction::PageUp, KeyAction::PageDown),
+                "page",
+            ),
+            (keys.primary_label(KeyAction::Filter), "find"),
+        ]
     };
+    entries.extend([
+        (keys.primary_label(KeyAction::Reload), "refresh"),
+        (keys.primary_label(KeyAction::Edit), "edit"),
+        (keys.primary_label(KeyAction::Quit), "quit"),
+        (keys.primary_label(KeyAction::Help), "more"),
+    ]);
+    entries.retain(|(key, _)| !key.is_empty());
+    entries
+}
+
+/// Action that edits the query of the current filter mode.
+fn filter_action(browser: &BrowserState) -> KeyAction {
+    match browser.filter_mode() {
+        FilterMode::Name => KeyAction::Filter,
+        FilterMode::Content => KeyAction::SearchContents,
+    }
+}
+
+fn full_help_rows(keys: &KeyBindings) -> [[Option<HelpEntry>; 4]; 5] {
+    let bound = |key: String, label| (!key.is_empty()).then_some((key, label));
+    let fixed = |key: &str, label| Some((key.to_string(), label));
+    [
+        [
+            fixed("enter", "open"),
+            bound(keys.primary_label(KeyAction::Filter), "find"),
+            bound(keys.primary_label(KeyAction::Edit), "edit"),
+            bound(keys.primary_label(KeyAction::Reload), "refresh"),
+        ],
+        [
+            bound(
+                keys.pair_label(KeyAction::ScrollUp, KeyAction::ScrollDown),
+                "choose",
+            ),
+            fixed("esc", "clear"),
+            fixed("!", "errors"),
+            bound(keys.primary_label(KeyAction::Quit), "quit"),
+        ],
+        [
+            bound(
+                keys.pair_label(KeyAction::PageUp, KeyAction::PageDown),
+                "page",
+            ),
+            fixed("tab", "section"),
+            bound(keys.primary_label(KeyAction::Help), "close help"),
+            bound(keys.primary_label(KeyAction::SearchContents), "contents"),
+        ],
+        [
+            bound(keys.label(KeyAction::Top), "first"),
+            bound(keys.label(KeyAction::Bottom), "last"),
+            fixed("space", "mark"),
+            bound(keys.primary_label(KeyAction::Preview), "preview"),
+        ],
+        [
+            bound(keys.primary_label(KeyAction::Sort), "sort"),
+            bound(keys.primary_label(KeyAction::Tree), "tree"),
+            fixed("enter", "fold dir"),
+            None,
+        ],
+    ]
+}
+
+pub(super) fn browser_mini_help(
+    browser: &BrowserState,
+    keys: &KeyBindings,
+    width: usize,
+    no_colors: bool,
+) -> String {
+    let entries = mini_help_entries(browser, keys);
     let max_width = width.saturating_sub(1);
     if max_width == 0 {
         return String::new();
@@ -13,7 +93,7 @@ pub(super) fn browser_mini_help(browser: &BrowserState, width: usize, no_colors:
 
     let mut help = String::from("   ");
     let mut help_width = display_width(&help);
-    for (index, &(key, label)) in entries.iter().enumerate() {
+    for (index, (key, label)) in entries.iter().enumerate() {
         let has_next = index + 1 < entries.len();
         let entry_width =
             display_width(key) + 1 + display_width(label) + if has_next { 3 } else { 0 };
@@ -101,11 +181,11 @@ pub(super) fn browser_filter_full_help(no_colors: bool) -> Vec<String> {
         .collect()
 }
 
-pub(super) fn browser_full_help(no_colors: bool) -> Vec<String> {
+pub(super) fn browser_full_help(keys: &KeyBindings, no_colors: bool) -> Vec<String> {
+    let rows = full_help_rows(keys);
     let column_widths: [(usize, usize); 4] = std::array::from_fn(|column| {
-        BROWSER_FULL_HELP_ROWS
-            .iter()
-            .fold((0, 0), |(key_width, label_width), row| match row[column] {
+        rows.iter()
+            .fold((0, 0), |(key_width, label_width), row| match &row[column] {
                 Some((key, label)) => (
                     key_width.max(display_width(key)),
                     label_width.max(display_width(label)),
@@ -114,8 +194,7 @@ pub(super) fn browser_full_help(no_colors: bool) -> Vec<String> {
             })
     });
 
-    BROWSER_FULL_HELP_ROWS
-        .iter()
+    rows.iter()
         .map(|row| {
             let last_column = row.iter().rposition(Option::is_some).unwrap_or(0);
             let mut line = String::from("   ");
@@ -124,7 +203,7 @@ pub(super) fn browser_full_help(no_colors: bool) -> Vec<String> {
                     line.push_str("    ");
                 }
                 let (key_width, label_width) = column_widths[column];
-                match row[column] {
+                match &row[column] {
                     Some((key, label)) => {
                         line.push_str(&styled(key, Some(BROWSER_HELP_KEY), None, false, no_colors));
                         line.push_str(&" ".repeat(key_width - display_width(key) + 2));
@@ -160,7 +239,7 @@ pub(super) fn browser_help_rows(browser: &BrowserState) -> u16 {
     if browser.filter_state() == FilterState::Editing && browser.show_full_help() {
         3
     } else if browser.show_full_help() {
-        4
+        5
     } else {
         1
     }
@@ -175,12 +254,13 @@ pub(super) fn browser_footer_rows(height: u16, browser: &BrowserState) -> (u16,
 pub(super) fn draw_browser_help(
     frame: &mut ScreenFrame,
     browser: &BrowserState,
+    keys: &KeyBindings,
     start_y: u16,
     width: usize,
     no_colors: bool,
 ) {
     if browser.filter_state() != FilterState::Editing && !browser.show_full_help() {
-        frame.write_line(start_y, &browser_mini_help(browser, width, no_colors));
+        frame.write_line(start_y, &browser_mini_help(browser, keys, width, no_colors));
         return;
     }
 
@@ -196,7 +276,7 @@ pub(super) fn draw_browser_help(
         return;
     }
 
-    for (index, row) in browser_full_help(no_colors).iter().enumerate() {
+    for (index, row) in browser_full_help(keys, no_colors).iter().enumerate() {
         frame.write_line(start_y + index as u16, row);
     }
 }
@@ -1,5 +1,10 @@
 use super::super::discovery::DocumentEntry;
 use super::*;
+use crate::keybindings::{KeyBindingOverrides, KeyScope};
+
+fn browser_keys() -> KeyBindings {
+    KeyBindingOverrides::default().resolve(KeyScope::Browser)
+}
 
 #[test]
 fn pagination_dots_are_adjacent() {
@@ -19,6 +24,24 @@ fn recent_documents_keep_relative_time() {
     assert_eq!(document_time(modified, now).unwrap(), "6 days ago");
 }
 
+#[test]
+fn read_documents_show_when_they_were_read_and_how_far() {
+    let now = SystemTime::now();
+    let mut record = crate::reading::ReadingRecord::default();
+
+    assert_eq!(document_details(now, Some(&record)).unwrap(), "just now");
+    record.mark_read(
+        crate::reading::ReadingPosition::capture(&crate::renderer::DocumentMap::default(), 0),
+        40,
+        now,
+    );
+    assert_eq!(
+        document_details(now, Some(&record)).unwrap(),
+        "just now · read just now, 40%"
+    );
+    assert_eq!(document_details(now, None).unwrap(), "just now");
+}
+
 #[test]
 fn week_old_documents_use_an_absolute_local_timestamp() {
     let now = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000_000);
@@ -97,7 +120,12 @@ fn first_frame_is_a_full_synchronized_redraw() {
 #[test]
 fn mini_help_matches_the_navigation_status() {
     assert_eq!(
-        browser_mini_help(&BrowserState::for_test(Vec::new(), 24), 120, true),
+        browser_mini_help(
+            &BrowserState::for_test(Vec::new(), 24),
+            &browser_keys(),
+            120,
+            true
+        ),
         "   h/l ←/→ page • / find • r refresh • e edit • q quit • ? more"
     );
 }
@@ -108,13 +136,13 @@ fn collapsed_help_draws_only_the_mini_help() {
     let mut frame = ScreenFrame::new(120, 24);
     let (_, help_y) = browser_footer_rows(24, &browser);
 
-    draw_browser_help(&mut frame, &browser, help_y, 120, true);
+    draw_browser_help(&mut frame, &browser, &browser_keys(), help_y, 120, true);
 
     let mut output = String::new();
     encode_synchronized_frame(&mut output, None, &frame).unwrap();
     assert!(output.contains("h/l ←/→ page • / find"));
     assert!(!output.contains("enter  open"));
-    assert!(!output.contains("j/k ↑/↓  choose"));
+    assert!(!output.contains("k/j ↑/↓  choose"));
 }
 
 #[test]
@@ -182,7 +210,7 @@ fn applied_filter_omits_page_navigation_from_mini_help() {
 
     assert!(browser.page_count() > 1);
     assert_eq!(
-        browser_mini_help(&browser, 120, true),
+        browser_mini_help(&browser, &browser_keys(), 120, true),
         "   tab section • / edit search • esc clear filter • r refresh • e edit • q quit • ? more"
     );
 }
@@ -199,7 +227,7 @@ fn mini_help_truncates_at_a_segment_boundary() {
     browser.confirm_filter();
 
     assert_eq!(
-        browser_mini_help(&browser, 64, true),
+        browser_mini_help(&browser, &browser_keys(), 64, true),
         "   tab section • / edit search • esc clear filter • …"
     );
 }
@@ -219,6 +247,36 @@ fn filtering_underlines_only_the_fuzzy_match_characters() {
     assert_eq!(crate::utils::strip_ansi(&title), "test_codeblock_indent.md");
 }
 
+#[test]
+fn titled_cards_show_the_path_and_configured_properties_below_the_title() {
+    let document =
+        DocumentEntry::for_test("ops/retries.md").with_summary(crate::markdown::summarize(
+            "---\ntitle: Retry budgets\ntags: [runbook, ops]\nowner: ann\n---\n",
+        ));
+    let metadata = [
+        "tags".to_string(),
+        "status".to_string(),
+        "owner".to_string(),
+    ];
+
+    assert_eq!(document.display_name(), "Retry budgets");
+    assert!(
+        card_details(&document, false, None, &metadata)
+            .unwrap()
+            .starts_with("ops/retries.md · ")
+    );
+    assert!(
+        card_details(&document, false, None, &metadata)
+            .unwrap()
+            .ends_with(" · tags: runbook, ops · owner: ann")
+    );
+    assert!(
+        !card_details(&DocumentEntry::for_test("notes.md"), false, None, &metadata)
+            .unwrap()
+            .contains('·')
+    );
+}
+
 #[test]
 fn filtering_without_colors_keeps_the_title_plain() {
     assert_eq!(
@@ -252,17 +310,17 @@ fn filter_editing_never_marks_a_result_as_selected() {
 
 #[test]
 fn full_help_uses_aligned_columns_from_browser_column_three() {
-    let rows = browser_full_help(true);
+    let rows = browser_full_help(&browser_keys(), true);
     let visual_column = |row: &str, text: &str| {
         let byte_index = row.find(text).unwrap();
         display_width(&row[..byte_index])
     };
 
-    assert_eq!(rows.len(), 4);
+    assert_eq!(rows.len(), 5);
     assert_eq!(
         [
             visual_column(&rows[0], "enter"),
-            visual_column(&rows[1], "j/k"),
+            visual_column(&rows[1], "k/j"),
             visual_column(&rows[2], "h/l"),
             visual_column(&rows[3], "g/home"),
         ],
@@ -279,18 +337,20 @@ fn full_help_uses_aligned_columns_from_browser_column_three() {
     );
     assert_eq!(
         [
-            visual_column(&rows[0], "e  edit"),
-            visual_column(&rows[1], "!  errors"),
-            visual_column(&rows[2], "?  close help"),
+            visual_column(&rows[0], "e      edit"),
+            visual_column(&rows[1], "!      errors"),
+            visual_column(&rows[2], "?      close help"),
+            visual_column(&rows[3], "space  mark"),
+            visual_column(&rows[4], "enter  fold dir"),
         ],
-        [40; 3]
+        [40; 5]
     );
     assert_eq!(
         [
             visual_column(&rows[0], "r  refresh"),
             visual_column(&rows[1], "q  quit"),
         ],
-        [57; 2]
+        [61; 2]
     );
 }
 
@@ -299,7 +359,7 @@ fn mini_help_reserves_a_bottom_row() {
     let mut browser = BrowserState::for_test(vec![DocumentEntry::for_test("README.md")], 24);
     assert_eq!(browser_footer_rows(24, &browser), (20, 22));
     browser.toggle_help();
-    assert_eq!(browser_footer_rows(24, &browser), (17, 19));
+    assert_eq!(browser_footer_rows(24, &browser), (16, 18));
 }
 
 #[test]
@@ -313,3 +373,16 @@ fn pager_handoff_keeps_the_current_screen_buffer() {
     assert!(!output.contains("\x1b[?1049h"));
     assert!(!output.contains("\x1b[?1049l"));
 }
+
+#[test]
+fn preview_pane_clears_the_rest_of_the_list_row_before_its_column() {
+    let mut frame = ScreenFrame::new(120, 3);
+    frame.write_line(1, "   README.md");
+    frame.write_pane(55, 1, "│ # Title");
+    frame.write_pane(55, 2, "│ text");
+
+    let mut output = String::new();
+    encode_synchronized_frame(&mut output, None, &frame).unwrap();
+    assert!(output.contains("   README.md\x1b[K\x1b[56G│ # Title"));
+    assert!(output.contains("\x1b[K\x1b[56G│ text"));
+}
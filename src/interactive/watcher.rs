@@ -0,0 +1,175 @@
+use super::discovery::{DiscoveryEvent, DocumentEntry, discover_path, discovered_directories};
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Time without new file system events before the changed paths are discovered again.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+/// How long the watcher thread waits for an event before it checks the debounce deadline
+/// and whether it should stop.
+const WATCH_EVENT_TIMEOUT: Duration = Duration::from_millis(25);
+
+#[derive(Debug)]
+pub(crate) enum WatchEvent {
+    /// Document created, modified, or moved into the tree.
+    Document(DocumentEntry),
+    /// File or directory that no longer exists; no document is left below it.
+    Removed(PathBuf),
+    Error(String),
+}
+
+/// Watches every directory that discovery walks below the browser root and reports the
+/// documents that changed. Hidden, ignored, and `node_modules` trees are not watched.
+pub(crate) struct DirectoryWatcher {
+    receiver: Receiver<WatchEvent>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DirectoryWatcher {
+    /// Starts watching on a background thread; setting up the watches walks the tree,
+    /// so a failure arrives as a `WatchEvent::Error` and ends the watcher.
+    pub(crate) fn start(root: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            if let Err(error) = watch(&root, &sender, &thread_stop) {
+                let _ = sender.send(WatchEvent::Error(format!("{error:#}")));
+            }
+        });
+
+        Self {
+            receiver,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    pub(crate) fn try_recv(&self) -> Result<WatchEvent, TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
+impl Drop for DirectoryWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Watches the directories below `root` and sends the changes until `stop` is set or the
+/// browser is gone.
+fn watch(root: &Path, sender: &Sender<WatchEvent>, stop: &AtomicBool) -> Result<()> {
+    let (event_tx, event_rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(event_tx)
+        .context("Failed to initialize browser file watcher")?;
+    for directory in discovered_directories(root, root) {
+        if stop.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        watch_directory(&mut watcher, &directory)?;
+    }
+
+    // Some backends report canonical paths for a root given as a relative one.
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let mut changed = BTreeSet::new();
+    let mut deadline = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        match event_rx.recv_timeout(WATCH_EVENT_TIMEOUT) {
+            // Reading a file for its summary must not count as a change.
+            Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => {
+                changed.extend(
+                    event
+                        .paths
+                        .iter()
+                        .filter_map(|path| within_root(path, root, &canonical_root))
+                        .filter(|path| !is_skipped(root, path)),
+                );
+                deadline = Some(Instant::now() + WATCH_DEBOUNCE);
+            }
+            Ok(Ok(_)) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Ok(Err(error)) => {
+                if sender
+                    .send(WatchEvent::Error(format!("File watcher error: {error}")))
+                    .is_err()
+                {
+                    return Ok(());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            deadline = None;
+            for path in std::mem::take(&mut changed) {
+                // A created or moved-in directory needs watches of its own.
+                if path.is_dir()
+                    && let Err(error) = discovered_directories(root, &path)
+                        .take_while(|_| !stop.load(Ordering::SeqCst))
+                        .try_for_each(|directory| watch_directory(&mut watcher, &directory))
+                    && sender
+                        .send(WatchEvent::Error(format!("{error:#}")))
+                        .is_err()
+                {
+                    return Ok(());
+                }
+                if !send_changes(root, &path, sender) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn watch_directory(watcher: &mut RecommendedWatcher, directory: &Path) -> Result<()> {
+    watcher
+        .watch(directory, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", directory.display()))
+}
+
+/// Sends the documents found at a changed path, or its removal when it is gone.
+fn send_changes(root: &Path, path: &Path, sender: &Sender<WatchEvent>) -> bool {
+    if !path.exists() {
+        return sender.send(WatchEvent::Removed(path.to_path_buf())).is_ok();
+    }
+    discover_path(root, path, |event| {
+        let event = match event {
+            DiscoveryEvent::Document(document) => WatchEvent::Document(document),
+            DiscoveryEvent::Error(error) => WatchEvent::Error(error),
+            DiscoveryEvent::Finished => return true,
+        };
+        sender.send(event).is_ok()
+    })
+}
+
+/// Whether `path` is hidden or lies in a hidden or `node_modules` tree below `root`. The
+/// contents of ignored trees report nothing, as their directories are not watched.
+fn is_skipped(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root).is_ok_and(|relative| {
+        relative.components().any(|component| {
+            let name = component.as_os_str();
+            name == "node_modules" || name.to_string_lossy().starts_with('.')
+        })
+    })
+}
+
+/// Event path spelled below `root`, the way discovery lists it.
+fn within_root(path: &Path, root: &Path, canonical_root: &Path) -> Option<PathBuf> {
+    if path.starts_with(root) {
+        return Some(path.to_path_buf());
+    }
+    path.strip_prefix(canonical_root)
+        .ok()
+        .map(|relative| root.join(relative))
+}
@@ -0,0 +1,697 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Part of the interface that a set of key bindings drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyScope {
+    Pager,
+    Browser,
+}
+
+impl KeyScope {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Pager => "pager",
+            Self::Browser => "browser",
+        }
+    }
+
+    /// Keys with fixed meanings that actions cannot be bound to.
+    const fn reserved_chords(self) -> &'static [&'static str] {
+        match self {
+            Self::Pager => &[
+                "o",
+                "O",
+                "x",
+                "y",
+                "t",
+                "s",
+                "m",
+                "'",
+                "[",
+                "]",
+                "n",
+                "p",
+                "tab",
+                "shift+tab",
+                "esc",
+                "ctrl+c",
+            ],
+            Self::Browser => &[
+                "H",
+                "L",
+                "!",
+                "space",
+                "enter",
+                "tab",
+                "shift+tab",
+                "esc",
+                "ctrl+c",
+                "ctrl+z",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum KeyAction {
+    ScrollUp,
+    ScrollDown,
+    HalfPageUp,
+    HalfPageDown,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    Filter,
+    SearchContents,
+    Copy,
+    Reload,
+    Edit,
+    Preview,
+    Sort,
+    Tree,
+    Help,
+    Quit,
+}
+
+impl KeyAction {
+    const ALL: [Self; 19] = [
+        Self::ScrollUp,
+        Self::ScrollDown,
+        Self::HalfPageUp,
+        Self::HalfPageDown,
+        Self::PageUp,
+        Self::PageDown,
+        Self::Top,
+        Self::Bottom,
+        Self::Search,
+        Self::Filter,
+        Self::SearchContents,
+        Self::Copy,
+        Self::Reload,
+        Self::Edit,
+        Self::Preview,
+        Self::Sort,
+        Self::Tree,
+        Self::Help,
+        Self::Quit,
+    ];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::ScrollUp => "scroll_up",
+            Self::ScrollDown => "scroll_down",
+            Self::HalfPageUp => "half_page_up",
+            Self::HalfPageDown => "half_page_down",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Search => "search",
+            Self::Filter => "filter",
+            Self::SearchContents => "search_contents",
+            Self::Copy => "copy",
+            Self::Reload => "reload",
+            Self::Edit => "edit",
+            Self::Preview => "preview",
+            Self::Sort => "sort",
+            Self::Tree => "tree",
+            Self::Help => "help",
+            Self::Quit => "quit",
+        }
+    }
+
+    /// Chords bound to the action when the configuration does not list it. Actions
+    /// without defaults in `scope` do not exist there.
+    const fn default_chords(self, scope: KeyScope) -> &'static [&'static str] {
+        match (scope, self) {
+            (_, Self::ScrollUp) => &["k", "up"],
+            (_, Self::ScrollDown) => &["j", "down"],
+            (KeyScope::Pager, Self::HalfPageUp) => &["u", "ctrl+u"],
+            (KeyScope::Pager, Self::HalfPageDown) => &["d", "ctrl+d"],
+            (KeyScope::Pager, Self::PageUp) => &["b", "pgup"],
+            (KeyScope::Pager, Self::PageDown) => &["f", "pgdn"],
+            (KeyScope::Browser, Self::PageUp) => &["h", "left", "b", "u", "pgup"],
+            (KeyScope::Browser, Self::PageDown) => &["l", "right", "f", "d", "pgdn"],
+            (_, Self::Top) => &["g", "home"],
+            (_, Self::Bottom) => &["G", "end"],
+            (KeyScope::Pager, Self::Search) | (KeyScope::Browser, Self::Filter) => &["/", "ctrl+f"],
+            (KeyScope::Browser, Self::SearchContents) => &["s"],
+            (KeyScope::Pager, Self::Copy) => &["c"],
+            (_, Self::Reload) => &["r"],
+            (_, Self::Edit) => &["e", "E"],
+            (KeyScope::Browser, Self::Preview) => &["p"],
+            (KeyScope::Browser, Self::Sort) => &["o"],
+            (KeyScope::Browser, Self::Tree) => &["t"],
+            (_, Self::Help) => &["?"],
+            (_, Self::Quit) => &["q"],
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KeyAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+            .ok_or_else(|| format!("unknown key binding action '{value}'"))
+    }
+}
+
+/// One key with its modifiers. Shift is folded into the character it types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers.intersection(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(character) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(single_uppercase(character))
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Whether `key` presses this chord. A letter typed in the Russian layout counts as
+    /// the Latin letter on the same key.
+    pub(crate) fn matches(self, key: &KeyEvent) -> bool {
+        let pressed = Self::new(key.code, key.modifiers);
+        if pressed.modifiers != self.modifiers {
+            return false;
+        }
+        pressed.code == self.code
+            || matches!(
+                (pressed.code, self.code),
+                (KeyCode::Char(typed), KeyCode::Char(bound))
+                    if latin_layout_letter(typed) == Some(bound)
+            )
+    }
+
+    const fn has_control_or_alt(self) -> bool {
+        self.modifiers
+            .intersects(KeyModifiers::CONTROL.union(KeyModifiers::ALT))
+    }
+
+    /// Short form shown in help panels.
+    fn help_label(self) -> String {
+        let key = match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::BackTab => "⇧tab".to_string(),
+            _ => return self.to_string(),
+        };
+        let mut label = self.modifier_prefix();
+        label.push_str(&key);
+        label
+    }
+
+    fn modifier_prefix(self) -> String {
+        [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| self.modifiers.contains(*modifier))
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (modifier_names, key) = match value.rsplit_once('+') {
+            Some((modifiers, key)) if !key.is_empty() => (Some(modifiers), key),
+            _ => (None, value),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names
+            .into_iter()
+            .flat_map(|names| names.split('+'))
+        {
+            modifiers |= match name.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{name}' in key '{value}'")),
+            };
+        }
+
+        let mut characters = key.chars();
+        let code = match (characters.next(), characters.next()) {
+            (None, _) => return Err("key binding is empty".to_string()),
+            (Some(character), None) => KeyCode::Char(character),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "del" | "delete" => KeyCode::Delete,
+                "ins" | "insert" => KeyCode::Insert,
+                name => match name
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse().ok())
+                {
+                    Some(number @ 1..=12) => KeyCode::F(number),
+                    _ => return Err(format!("unknown key '{key}' in '{value}'")),
+                },
+            },
+        };
+        if let KeyCode::Char(character) = code
+            && modifiers.contains(KeyModifiers::SHIFT)
+            && !character.is_alphabetic()
+        {
+            return Err(format!(
+                "'{value}' combines shift with a symbol; write the shifted symbol instead"
+            ));
+        }
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.modifier_prefix())?;
+        match self.code {
+            KeyCode::Char(' ') => formatter.write_str("space"),
+            KeyCode::Char(character) => write!(formatter, "{character}"),
+            KeyCode::Up => formatter.write_str("up"),
+            KeyCode::Down => formatter.write_str("down"),
+            KeyCode::Left => formatter.write_str("left"),
+            KeyCode::Right => formatter.write_str("right"),
+            KeyCode::Home => formatter.write_str("home"),
+            KeyCode::End => formatter.write_str("end"),
+            KeyCode::PageUp => formatter.write_str("pgup"),
+            KeyCode::PageDown => formatter.write_str("pgdn"),
+            KeyCode::Enter => formatter.write_str("enter"),
+            KeyCode::Esc => formatter.write_str("esc"),
+            KeyCode::Tab => formatter.write_str("tab"),
+            KeyCode::BackTab => formatter.write_str("shift+tab"),
+            KeyCode::Backspace => formatter.write_str("backspace"),
+            KeyCode::Delete => formatter.write_str("del"),
+            KeyCode::Insert => formatter.write_str("ins"),
+            KeyCode::F(number) => write!(formatter, "f{number}"),
+            code => write!(formatter, "{code:?}"),
+        }
+    }
+}
+
+fn single_uppercase(character: char) -> char {
+    let mut uppercase = character.to_uppercase();
+    match (uppercase.next(), uppercase.next()) {
+        (Some(upper), None) => upper,
+        _ => character,
+    }
+}
+
+/// Latin letter on the key that types the Russian `character`.
+fn latin_layout_letter(character: char) -> Option<char> {
+    const RUSSIAN: &str = "йцукенгшщзфывапролдячсмить";
+    const LATIN: &str = "qwertyuiopasdfghjklzxcvbnm";
+
+    let lowercase = character.to_lowercase().next()?;
+    let index = RUSSIAN.chars().position(|russian| russian == lowercase)?;
+    let latin = LATIN.chars().nth(index)?;
+    Some(if character.is_uppercase() {
+        latin.to_ascii_uppercase()
+    } else {
+        latin
+    })
+}
+
+/// Chords per action from the `keybindings` configuration section. A listed action
+/// replaces all of its default chords; an empty list leaves it unbound.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyBindingOverrides {
+    pager: BTreeMap<KeyAction, Vec<KeyChord>>,
+    browser: BTreeMap<KeyAction, Vec<KeyChord>>,
+}
+
+impl KeyBindingOverrides {
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.pager.extend(
+            other
+                .pager
+                .iter()
+                .map(|(action, chords)| (*action, chords.clone())),
+        );
+        self.browser.extend(
+            other
+                .browser
+                .iter()
+                .map(|(action, chords)| (*action, chords.clone())),
+        );
+    }
+
+    /// Effective bindings of `scope`, with defaults for the actions left out.
+    pub(crate) fn resolve(&self, scope: KeyScope) -> KeyBindings {
+        let overrides = self.scope(scope);
+        let actions = KeyAction::ALL
+            .into_iter()
+            .filter(|action| !action.default_chords(scope).is_empty())
+            .map(|action| {
+                let chords = overrides.get(&action).cloned().unwrap_or_else(|| {
+                    action
+                        .default_chords(scope)
+                        .iter()
+                        .filter_map(|chord| chord.parse().ok())
+                        .collect()
+                });
+                (action, chords)
+            })
+            .collect();
+        KeyBindings { scope, actions }
+    }
+
+    fn scope(&self, scope: KeyScope) -> &BTreeMap<KeyAction, Vec<KeyChord>> {
+        match scope {
+            KeyScope::Pager => &self.pager,
+            KeyScope::Browser => &self.browser,
+        }
+    }
+
+    fn parse_scope(
+        scope: KeyScope,
+        raw: BTreeMap<String, RawChords>,
+    ) -> Result<BTreeMap<KeyAction, Vec<KeyChord>>, String> {
+        raw.into_iter()
+            .map(|(name, chords)| {
+                let action = name.parse::<KeyAction>()?;
+                if action.default_chords(scope).is_empty() {
+                    return Err(format!(
+                        "key binding action '{action}' is not available in the {}",
+                        scope.name()
+                    ));
+                }
+                let chords = chords
+                    .into_vec()
+                    .iter()
+                    .map(|chord| chord.parse())
+                    .collect::<Result<_, _>>()?;
+                Ok((action, chords))
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl RawChords {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(chord) => vec![chord],
+            Self::Many(chords) => chords,
+        }
+    }
+}
+
+impl Serialize for KeyBindingOverrides {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let scope = |overrides: &BTreeMap<KeyAction, Vec<KeyChord>>| {
+            overrides
+                .iter()
+                .map(|(action, chords)| {
+                    (
+                        action.as_str(),
+                        chords.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let mut scopes = BTreeMap::new();
+        if !self.pager.is_empty() {
+            scopes.insert(KeyScope::Pager.name(), scope(&self.pager));
+        }
+        if !self.browser.is_empty() {
+            scopes.insert(KeyScope::Browser.name(), scope(&self.browser));
+        }
+        scopes.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBindingOverrides {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Default, Deserialize)]
+        #[serde(default, deny_unknown_fields)]
+        struct Input {
+            pager: BTreeMap<String, RawChords>,
+            browser: BTreeMap<String, RawChords>,
+        }
+
+        let input = Option::<Input>::deserialize(deserializer)?.unwrap_or_default();
+        let overrides = Self {
+            pager: Self::parse_scope(KeyScope::Pager, input.pager).map_err(de::Error::custom)?,
+            browser: Self::parse_scope(KeyScope::Browser, input.browser)
+                .map_err(de::Error::custom)?,
+        };
+        for scope in [KeyScope::Pager, KeyScope::Browser] {
+            overrides
+                .resolve(scope)
+                .check_conflicts()
+                .map_err(de::Error::custom)?;
+        }
+        Ok(overrides)
+    }
+}
+
+/// Effective key bindings of one scope.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyBindings {
+    scope: KeyScope,
+    actions: Vec<(KeyAction, Vec<KeyChord>)>,
+}
+
+impl KeyBindings {
+    /// Action bound to `key`, if any.
+    pub(crate) fn action(&self, key: &KeyEvent) -> Option<KeyAction> {
+        self.actions
+            .iter()
+            .find(|(_, chords)| chords.iter().any(|chord| chord.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Chords of `action` joined with `/` for help panels. Chords with Ctrl or Alt are
+    /// left out when the action also has a plain key. Empty when the action is unbound.
+    pub(crate) fn label(&self, action: KeyAction) -> String {
+        self.help_labels(action).join("/")
+    }
+
+    /// First chord of [`Self::label`].
+    pub(crate) fn primary_label(&self, action: KeyAction) -> String {
+        self.help_labels(action)
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// The first two chords of two opposite actions side by side, like `h/l ←/→`.
+    pub(crate) fn pair_label(&self, first: KeyAction, second: KeyAction) -> String {
+        self.help_labels(first)
+            .into_iter()
+            .zip(self.help_labels(second))
+            .take(2)
+            .map(|(first, second)| format!("{first}/{second}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn help_labels(&self, action: KeyAction) -> Vec<String> {
+        let chords = self.chords(action);
+        let plain = chords.iter().any(|chord| !chord.has_control_or_alt());
+        chords
+            .iter()
+            .filter(|chord| !plain || !chord.has_control_or_alt())
+            .map(|chord| chord.help_label())
+            .collect()
+    }
+
+    fn chords(&self, action: KeyAction) -> &[KeyChord] {
+        self.actions
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, chords)| chords.as_slice())
+    }
+
+    fn check_conflicts(&self) -> Result<(), String> {
+        let scope = self.scope.name();
+        let reserved: Vec<KeyChord> = self
+            .scope
+            .reserved_chords()
+            .iter()
+            .filter_map(|chord| chord.parse().ok())
+            .collect();
+        for (index, (action, chords)) in self.actions.iter().enumerate() {
+            for chord in chords {
+                if reserved.contains(chord) {
+                    return Err(format!(
+                        "key '{chord}' of '{action}' is reserved in the {scope}"
+                    ));
+                }
+                if let Some((other, _)) = self.actions[index + 1..]
+                    .iter()
+                    .find(|(_, other_chords)| other_chords.contains(chord))
+                {
+                    return Err(format!(
+                        "key '{chord}' is bound to both '{action}' and '{other}' in the {scope}"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn chords_parse_modifiers_and_named_keys() {
+        let chord: KeyChord = "ctrl+f".parse().unwrap();
+        assert!(chord.matches(&key(KeyCode::Char('f'), KeyModifiers::CONTROL)));
+        assert!(!chord.matches(&key(KeyCode::Char('f'), KeyModifiers::NONE)));
+
+        let chord: KeyChord = "shift+g".parse().unwrap();
+        assert_eq!(chord, "G".parse().unwrap());
+        assert!(chord.matches(&key(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+
+        let chord: KeyChord = "shift+tab".parse().unwrap();
+        assert!(chord.matches(&key(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert_eq!(chord.help_label(), "⇧tab");
+        assert_eq!("pagedown".parse::<KeyChord>().unwrap().to_string(), "pgdn");
+        assert_eq!("space".parse::<KeyChord>().unwrap().to_string(), "space");
+
+        assert!("hyper+x".parse::<KeyChord>().is_err());
+        assert!("pgdown".parse::<KeyChord>().is_err());
+        assert!("shift+/".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn letters_match_in_the_russian_layout() {
+        let bindings = KeyBindingOverrides::default().resolve(KeyScope::Pager);
+
+        for character in ['e', 'E', 'у', 'У'] {
+            assert_eq!(
+                bindings.action(&key(KeyCode::Char(character), KeyModifiers::NONE)),
+                Some(KeyAction::Edit),
+                "character: {character}"
+            );
+        }
+        assert_eq!(
+            bindings.action(&key(KeyCode::Char('e'), KeyModifiers::CONTROL)),
+            None
+        );
+    }
+
+    #[test]
+    fn configured_actions_replace_their_defaults() {
+        let overrides: KeyBindingOverrides = serde_yaml::from_str(
+            "pager:\n  copy: [Y, ctrl+y]\n  quit: []\nbrowser:\n  filter: f\n  page_down: [l, right]\n",
+        )
+        .unwrap();
+        let pager = overrides.resolve(KeyScope::Pager);
+        let browser = overrides.resolve(KeyScope::Browser);
+
+        assert_eq!(
+            pager.action(&key(KeyCode::Char('y'), KeyModifiers::CONTROL)),
+            Some(KeyAction::Copy)
+        );
+        assert_eq!(
+            pager.action(&key(KeyCode::Char('c'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            pager.action(&key(KeyCode::Char('q'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(pager.label(KeyAction::Copy), "Y");
+        assert_eq!(pager.label(KeyAction::Quit), "");
+        assert_eq!(
+            browser.action(&key(KeyCode::Char('f'), KeyModifiers::NONE)),
+            Some(KeyAction::Filter)
+        );
+        assert_eq!(
+            browser.pair_label(KeyAction::PageUp, KeyAction::PageDown),
+            "h/l ←/→"
+        );
+    }
+
+    #[test]
+    fn invalid_bindings_are_rejected_when_the_config_loads() {
+        for (yaml, message) in [
+            ("pager:\n  jump: j\n", "unknown key binding action 'jump'"),
+            ("pager:\n  filter: f\n", "not available in the pager"),
+            ("browser:\n  copy: c\n", "not available in the browser"),
+            ("pager:\n  copy: ctrl+\n", "unknown key"),
+            ("pager:\n  copy: r\n", "bound to both 'copy' and 'reload'"),
+            ("pager:\n  copy: o\n", "reserved in the pager"),
+            ("tabs:\n  quit: q\n", "unknown field"),
+        ] {
+            let error = serde_yaml::from_str::<KeyBindingOverrides>(yaml)
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains(message), "{yaml}: {error}");
+        }
+    }
+}
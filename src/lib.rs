@@ -3,34 +3,46 @@ mod callout;
 mod checkbox;
 mod checkbox_override;
 pub mod cli;
+pub mod clipboard;
 pub mod config;
 mod custom_code_block;
+mod diff;
 mod editor;
 pub mod error;
 pub mod inline_style;
 mod interactive;
+pub mod keybindings;
 mod list_marker;
 pub mod markdown;
 pub mod math;
+mod mermaid;
 pub mod monitor;
 mod pager;
 mod preset;
+mod reading;
 pub mod renderer;
 pub mod table;
 pub mod terminal;
 pub mod theme;
+mod toc;
 mod user_themes;
 pub mod utils;
 
 #[cfg(test)]
 mod interactive_tests;
 
+pub use interactive::browser::{BrowserSort, BrowserView};
 pub use list_marker::{PrettyListStyle, UniformListMarker};
 
 use anyhow::Result;
 use clap::{ArgMatches, CommandFactory};
-use cli::{Cli, CliCommand};
+use cli::{
+    Cli, CliCommand, ExportFormat, HtmlMode, ImageMode, LineNumberOptions, LineNumberTarget,
+    LinkStyle, OutputFormat,
+};
+use clipboard::Clipboard;
 use config::Config;
+use keybindings::KeyScope;
 use markdown::MarkdownProcessor;
 use renderer::TerminalRenderer;
 use std::io::IsTerminal;
@@ -40,8 +52,15 @@ use std::sync::Arc;
 
 /// Main entry point for the mdv application
 pub fn run(mut cli: Cli, matches: &ArgMatches) -> Result<()> {
-    if matches!(cli.command, Some(CliCommand::Help)) {
-        return show_help(&cli, matches);
+    match &cli.command {
+        Some(CliCommand::Help) => return show_help(&cli, matches),
+        Some(CliCommand::Toc(args)) => {
+            let config = Config::from_cli(&cli, matches)?;
+            let content = read_input(args.filename.as_deref())?;
+            print!("{}", toc::format_outline(&content, &config, args.format)?);
+            return Ok(());
+        }
+        None => {}
     }
 
     if cli.init_config.is_some() {
@@ -50,7 +69,7 @@ pub fn run(mut cli: Cli, matches: &ArgMatches) -> Result<()> {
         return Ok(());
     }
 
-    let config = Config::from_cli(&cli, matches)?;
+    let mut config = Config::from_cli(&cli, matches)?;
     if let Some(Some(path)) = &cli.theme_info
         && cli.filename.is_none()
     {
@@ -73,6 +92,12 @@ pub fn run(mut cli: Cli, matches: &ArgMatches) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(format) = cli.format {
+        let content = get_input_content(&cli)?;
+        print!("{}", format_document(&content, &config, format)?);
+        return Ok(());
+    }
+
     let show_current_theme = config.theme_info || cli.theme_info.is_some();
     let current_preset = cli.preset.as_deref().filter(|_| cli.preset_info);
 
@@ -80,51 +105,87 @@ pub fn run(mut cli: Cli, matches: &ArgMatches) -> Result<()> {
     if let Some(target) = interactive::select_interactive_target(
         cli.filename.as_deref(),
         cli.interactive,
-        cli.pager,
+        cli.pager || cli.split,
         stdin_is_terminal,
     )? {
+        config.images = config.images.for_output(true, true);
+        config.source_map = true;
         return interactive::run(target, config);
     }
 
     let content = get_input_content(&cli)?;
     let stdout_is_terminal = std::io::stdout().is_terminal();
+    let pager_active = (cli.pager || cli.split) && stdout_is_terminal && cli.export.is_none();
+    // Exported screens are drawn from text, so images there become half-blocks too.
+    config.images = config
+        .images
+        .for_output(pager_active || cli.export.is_some(), stdout_is_terminal);
+    config.diff_base = match (&cli.diff, &cli.diff_git, &cli.filename) {
+        (Some(old), _, _) => Some(read_input(Some(old))?),
+        (None, Some(revision), Some(filename)) => {
+            Some(diff::read_git_revision(revision, filename)?)
+        }
+        _ => None,
+    };
+    config.source_map = pager_active || config.diff_base.is_some();
+    config.split_view = pager_active && cli.split;
+    config.document_dir = cli
+        .filename
+        .as_deref()
+        .filter(|filename| *filename != "-")
+        .and_then(|filename| Path::new(filename).parent())
+        .map(Path::to_path_buf);
     let rendered = render_document(
         &content,
         &config,
-        cli.do_html,
+        cli.html,
+        cli.export,
         show_current_theme,
         current_preset,
         stdout_is_terminal,
     )?;
 
-    let pager_active = cli.pager && stdout_is_terminal;
     if pager_active {
         let pager_file = cli
             .filename
             .as_deref()
             .filter(|filename| *filename != "-")
             .map(PathBuf::from);
-        let refresh = pager_file.as_ref().map(|path| {
-            let path = path.clone();
+        let loader = pager_file.as_ref().map(|file| {
             let config = config.clone();
-            let do_html = cli.do_html;
+            // Only the compared file is shown as a diff; linked documents render plainly.
+            let mut other_config = config.clone();
+            other_config.diff_base = None;
+            let diffed_file = config
+                .diff_base
+                .as_ref()
+                .and_then(|_| file.canonicalize().ok());
+            let html = cli.html;
             let current_preset = current_preset.map(str::to_owned);
-            Arc::new(move || {
+            Arc::new(move |path: &Path| {
+                let is_diffed = diffed_file.is_some() && path.canonicalize().ok() == diffed_file;
                 render_document_file(
-                    &path,
-                    &config,
-                    do_html,
+                    path,
+                    if is_diffed { &config } else { &other_config },
+                    html,
                     show_current_theme,
                     current_preset.as_deref(),
                 )
-            }) as pager::RefreshCallback
+            }) as pager::DocumentLoader
         });
         pager::page(
-            pager::PagerDocument::new(rendered.output, content)
-                .with_status_bar_transparent(rendered.pager_status_bar_transparent),
-            pager_file,
-            refresh,
+            vec![(
+                pager::PagerDocument::new(rendered.output, content)
+                    .with_colors(rendered.pager_colors)
+                    .with_map(rendered.map),
+                pager_file,
+            )],
+            0,
+            None,
+            loader,
             pager::PagerScreen::Alternate,
+            config.keybindings.resolve(KeyScope::Pager),
+            Clipboard::from_config(&config),
         )?;
     } else {
         print!("{}", rendered.output);
@@ -151,11 +212,15 @@ fn show_help(cli: &Cli, matches: &ArgMatches) -> Result<()> {
     }
     let help = command.render_long_help().to_string();
     if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        let config = Config::from_cli(cli, matches)?;
         pager::page(
-            build_help_document(cli, matches, help)?,
+            vec![(build_help_document(&config, help)?, None)],
+            0,
             None,
             None,
             pager::PagerScreen::Alternate,
+            config.keybindings.resolve(KeyScope::Pager),
+            Clipboard::from_config(&config),
         )
     } else {
         print!("{help}");
@@ -163,40 +228,50 @@ fn show_help(cli: &Cli, matches: &ArgMatches) -> Result<()> {
     }
 }
 
-fn build_help_document(
-    cli: &Cli,
-    matches: &ArgMatches,
-    help: String,
-) -> Result<pager::PagerDocument> {
-    let config = Config::from_cli(cli, matches)?;
-    let status_bar_transparent = renderer::terminal::pager_status_bar_transparent(&config)?;
+fn build_help_document(config: &Config, help: String) -> Result<pager::PagerDocument> {
+    let colors = pager::PagerColors::from_theme(&renderer::terminal::resolved_theme(config)?);
     Ok(pager::PagerDocument::new(help.clone(), help)
         .with_title("Help")
-        .with_status_bar_transparent(status_bar_transparent))
+        .with_colors(colors))
 }
 
 struct RenderedOutput {
     output: String,
-    pager_status_bar_transparent: bool,
+    map: renderer::DocumentMap,
+    pager_colors: pager::PagerColors,
 }
 
 fn render_document(
     content: &str,
     config: &Config,
-    do_html: bool,
+    html: Option<HtmlMode>,
+    export: Option<ExportFormat>,
     show_current_theme: bool,
     current_preset: Option<&str>,
     add_leading_blank: bool,
 ) -> Result<RenderedOutput> {
     let processor = MarkdownProcessor::new(config);
-    let document = processor.parse_document(content)?;
+    let diff = config
+        .diff_base
+        .as_deref()
+        .map(|base| diff::DocumentDiff::new(&processor, base, content));
+    let document =
+        processor.parse_document(diff.as_ref().map_or(content, |diff| diff.source.as_str()))?;
     let renderer = TerminalRenderer::new(config)?;
-    let pager_status_bar_transparent = renderer.pager_status_bar_transparent();
+    let mut pager_colors = pager::PagerColors::from_theme(renderer.theme());
+    if config.no_colors {
+        pager_colors = pager_colors.with_plain_change_marker();
+    }
 
-    if do_html {
+    if let Some(mode) = html {
+        let output = match mode {
+            HtmlMode::Fragment => renderer.to_html_document(document)?,
+            HtmlMode::Standalone => renderer.to_standalone_html(document)?,
+        };
         return Ok(RenderedOutput {
-            output: renderer.to_html_document(document)?,
-            pager_status_bar_transparent,
+            output,
+            map: renderer::DocumentMap::default(),
+            pager_colors,
         });
     }
 
@@ -213,32 +288,72 @@ fn render_document(
     if add_leading_blank {
         output.push('\n');
     }
-    output.push_str(&renderer.render_document(document)?);
+    let (rendered, mut map) = if let Some(diff) = &diff {
+        renderer.render_diff(document, diff)?
+    } else if config.split_view {
+        renderer.render_split_view(document, content)?
+    } else {
+        renderer.render_document_map(document)?
+    };
+    map.offset_lines(output.matches('\n').count());
+    output.push_str(&rendered);
+    if let Some(format) = export {
+        output = renderer.export_screen(&output, format);
+    }
     Ok(RenderedOutput {
         output,
-        pager_status_bar_transparent,
+        map,
+        pager_colors,
     })
 }
 
+/// Serializes the preprocessed document for `--format`.
+fn format_document(content: &str, config: &Config, format: OutputFormat) -> Result<String> {
+    let mut config = config.clone();
+    match format {
+        OutputFormat::Json => {
+            config.line_numbers = Some(LineNumberOptions {
+                target: LineNumberTarget::Source,
+                separator: false,
+            });
+        }
+        OutputFormat::Text => {
+            config.no_colors = true;
+            config.link_style = LinkStyle::EndTable;
+            config.images = ImageMode::Off;
+        }
+    }
+    let document = MarkdownProcessor::new(&config).parse_document(content)?;
+    let renderer = TerminalRenderer::new(&config)?;
+    match format {
+        OutputFormat::Json => renderer.to_json(document),
+        OutputFormat::Text => renderer.to_plain_text(document),
+    }
+}
+
 fn render_document_file(
     path: &Path,
     config: &Config,
-    do_html: bool,
+    html: Option<HtmlMode>,
     show_current_theme: bool,
     current_preset: Option<&str>,
 ) -> Result<pager::PagerDocument> {
     let mut content = std::fs::read_to_string(path)?;
     strip_leading_bom(&mut content);
+    let mut config = config.clone();
+    config.document_dir = path.parent().map(Path::to_path_buf);
     let rendered = render_document(
         &content,
-        config,
-        do_html,
+        &config,
+        html,
+        None,
         show_current_theme,
         current_preset,
         true,
     )?;
     Ok(pager::PagerDocument::new(rendered.output, content)
-        .with_status_bar_transparent(rendered.pager_status_bar_transparent))
+        .with_map(rendered.map)
+        .with_colors(rendered.pager_colors))
 }
 
 fn format_current_themes(config: &Config) -> String {
@@ -253,8 +368,12 @@ fn format_current_themes(config: &Config) -> String {
 }
 
 fn get_input_content(cli: &Cli) -> Result<String> {
-    let mut content = match &cli.filename {
-        Some(filename) if filename == "-" => {
+    read_input(cli.filename.as_deref())
+}
+
+fn read_input(filename: Option<&str>) -> Result<String> {
+    let mut content = match filename {
+        Some("-") => {
             let mut content = String::new();
             io::stdin().read_to_string(&mut content)?;
             content
@@ -315,8 +434,10 @@ mod tests {
         ]);
         let cli = Cli::from_arg_matches(&matches).unwrap();
 
-        let document = build_help_document(&cli, &matches, "help".to_string()).unwrap();
+        let config = Config::from_cli(&cli, &matches).unwrap();
+
+        let document = build_help_document(&config, "help".to_string()).unwrap();
 
-        assert!(document.status_bar_transparent());
+        assert!(document.colors().transparent);
     }
 }
@@ -5,10 +5,12 @@ use serde_yaml::Mapping;
 use std::mem;
 use std::ops::Range;
 
+mod outline;
 mod raw_html;
 mod source_lines;
 
 pub(crate) const BLANK_LINE_MARKER: &str = "MDV_BLANK_LINE_MARKER";
+pub(crate) use outline::{Heading, HeadingSlugs, heading_text, headings};
 pub(crate) use source_lines::{Marker as SourceLineMarker, from_event as source_line_from_event};
 
 /// Markdown processor that parses markdown and prepares it for rendering
@@ -29,15 +31,22 @@ pub(crate) struct FrontMatter {
 
 mod admonitions;
 mod blockquotes;
+mod blocks;
 mod conversion;
 mod detection;
 mod events;
 mod fences;
 mod parsing;
+mod sections;
 mod structure;
+mod summary;
 mod task_lists;
 
+pub(crate) use blocks::SourceBlock;
 pub use detection::{detect_source_code, extract_code_language};
+#[cfg(test)]
+pub(crate) use summary::summarize;
+pub(crate) use summary::{DocumentSummary, summarize_file};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,102 @@
+use super::parsing::split_front_matter;
+use super::*;
+
+/// Lines of one Markdown block, from where it starts up to the next block, with the
+/// parsed events that start inside them.
+#[derive(Debug)]
+pub(crate) struct SourceBlock {
+    pub(crate) lines: Range<usize>,
+    pub(crate) events: Vec<Event<'static>>,
+    /// Paragraph or HTML block that a directly following line of text would continue.
+    pub(crate) open_ended: bool,
+}
+
+impl PartialEq for SourceBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.events == other.events
+    }
+}
+
+impl MarkdownProcessor {
+    /// Splits `markdown` into blocks at paragraphs, headings, list items, table rows,
+    /// code blocks, HTML blocks, rules, and footnote definitions. Front matter forms one
+    /// block, and blank lines belong to the block above them.
+    pub(crate) fn source_blocks(&self, markdown: &str) -> Vec<SourceBlock> {
+        let (body, body_start) = match split_front_matter(markdown) {
+            Ok(document) if document.front_matter.is_some() => {
+                (document.body, document.body_start_line - 1)
+            }
+            _ => (markdown, 0),
+        };
+        let line_starts = source_lines::starts(body);
+        let events: Vec<(Event<'static>, usize)> = Parser::new_ext(body, self.options)
+            .into_offset_iter()
+            .map(|(event, range)| {
+                let line = source_lines::index_for_offset(&line_starts, range.start);
+                (event.into_static(), body_start + line)
+            })
+            .collect();
+
+        let mut starts: Vec<(usize, bool)> = Vec::new();
+        if body_start > 0 {
+            starts.push((0, false));
+        }
+        for (event, line) in &events {
+            let open_ended = match event {
+                Event::Start(Tag::Paragraph | Tag::HtmlBlock) => true,
+                Event::Start(
+                    Tag::Heading { .. }
+                    | Tag::CodeBlock(_)
+                    | Tag::Item
+                    | Tag::TableHead
+                    | Tag::TableRow
+                    | Tag::FootnoteDefinition(_)
+                    | Tag::DefinitionListTitle
+                    | Tag::DefinitionListDefinition,
+                )
+                | Event::Rule => false,
+                _ => continue,
+            };
+            match starts.last_mut() {
+                Some((start, _)) if *start >= *line => {}
+                _ => starts.push((*line, open_ended)),
+            }
+        }
+
+        let line_count = markdown.lines().count();
+        let mut blocks: Vec<SourceBlock> = starts
+            .iter()
+            .enumerate()
+            .map(|(index, &(start, open_ended))| SourceBlock {
+                lines: start..starts.get(index + 1).map_or(line_count, |(next, _)| *next),
+                events: Vec::new(),
+                open_ended,
+            })
+            .collect();
+        if let Some(first) = blocks.first_mut() {
+            first.lines.start = 0;
+            if body_start > 0 {
+                first
+                    .events
+                    .push(Event::Text(markdown_lines(markdown, 0..body_start).into()));
+            }
+        }
+        for (event, line) in events {
+            let index = blocks
+                .partition_point(|block| block.lines.start <= line)
+                .saturating_sub(1);
+            if let Some(block) = blocks.get_mut(index) {
+                block.events.push(event);
+            }
+        }
+        blocks
+    }
+}
+
+fn markdown_lines(markdown: &str, lines: Range<usize>) -> String {
+    markdown
+        .split_inclusive('\n')
+        .skip(lines.start)
+        .take(lines.len())
+        .collect()
+}
@@ -0,0 +1,139 @@
+use super::source_lines::{self, Marker};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::collections::HashMap;
+
+/// Heading of a parsed document with its anchor slug.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Heading {
+    pub(crate) level: u8,
+    pub(crate) text: String,
+    pub(crate) slug: String,
+    /// Source line of the heading, when the events carry source-line markers.
+    pub(crate) line: Option<usize>,
+}
+
+/// Lists non-empty headings in document order.
+pub(crate) fn headings(events: &[Event<'_>]) -> Vec<Heading> {
+    let mut slugs = HeadingSlugs::default();
+    events
+        .iter()
+        .enumerate()
+        .filter_map(|(index, event)| {
+            let Event::Start(Tag::Heading { level, id, .. }) = event else {
+                return None;
+            };
+            let body = &events[index + 1..];
+            let text = heading_text(body);
+            if text.is_empty() {
+                return None;
+            }
+            let line = body
+                .iter()
+                .take_while(|event| !matches!(event, Event::End(TagEnd::Heading(_))))
+                .find_map(|event| match source_lines::from_event(event) {
+                    Some(Marker::Content(line)) => Some(line),
+                    _ => None,
+                });
+            Some(Heading {
+                level: *level as u8,
+                slug: slugs.next(&text, id.as_deref()),
+                text,
+                line,
+            })
+        })
+        .collect()
+}
+
+/// Assigns unique slugs in document order, numbering repeated headings like GitHub.
+#[derive(Default)]
+pub(crate) struct HeadingSlugs {
+    seen: HashMap<String, usize>,
+}
+
+impl HeadingSlugs {
+    pub(crate) fn next(&mut self, text: &str, id: Option<&str>) -> String {
+        let base = id.map_or_else(|| heading_slug(text), str::to_owned);
+        let mut slug = base.clone();
+        while self.seen.contains_key(&slug) {
+            let count = self.seen.entry(base.clone()).or_insert(0);
+            *count += 1;
+            slug = format!("{base}-{count}");
+        }
+        self.seen.insert(slug.clone(), 0);
+        slug
+    }
+}
+
+pub(crate) fn heading_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| {
+            if ch.is_alphanumeric() || ch == '-' || ch == '_' {
+                Some(ch)
+            } else if ch.is_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Collects the visible text of a heading whose start tag precedes `events`.
+pub(crate) fn heading_text(events: &[Event<'_>]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::End(TagEnd::Heading(_)) => break,
+            Event::Text(fragment) | Event::Code(fragment) => text.push_str(fragment),
+            Event::InlineMath(math) => text.push_str(math),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{Options, Parser};
+
+    fn parse(markdown: &str) -> Vec<Event<'_>> {
+        Parser::new_ext(markdown, Options::ENABLE_HEADING_ATTRIBUTES).collect()
+    }
+
+    #[test]
+    fn slugs_follow_github_rules() {
+        assert_eq!(heading_slug("Install on Linux"), "install-on-linux");
+        assert_eq!(heading_slug("What's new in v2.0?"), "whats-new-in-v20");
+        assert_eq!(
+            heading_slug("snake_case & kebab-case"),
+            "snake_case--kebab-case"
+        );
+        assert_eq!(heading_slug("Установка"), "установка");
+    }
+
+    #[test]
+    fn repeated_headings_receive_numbered_slugs() {
+        let mut slugs = HeadingSlugs::default();
+        let assigned = ["Setup", "Setup", "Setup-1", "Setup"].map(|text| slugs.next(text, None));
+
+        assert_eq!(assigned, ["setup", "setup-1", "setup-1-1", "setup-2"]);
+    }
+
+    #[test]
+    fn heading_text_includes_inline_code_and_explicit_ids() {
+        let events = parse("## Run `mdv` *now* {#run}\n");
+        let Event::Start(Tag::Heading { id, .. }) = &events[0] else {
+            panic!("expected a heading");
+        };
+
+        assert_eq!(heading_text(&events[1..]), "Run mdv now");
+        assert_eq!(
+            HeadingSlugs::default().next("Run mdv now", id.as_deref()),
+            "run"
+        );
+    }
+}
@@ -66,6 +66,22 @@ impl MarkdownProcessor {
                 .collect::<Vec<_>>()
         });
 
+        if let Some(section) = &self.config.section {
+            let lines: Vec<&str> = processed.lines().collect();
+            let selected = self.section_lines(&processed, section)?;
+            processed = selected
+                .iter()
+                .map(|line| line.map_or("", |line| lines[line]))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if let Some(source_lines) = source_lines.as_mut() {
+                *source_lines = selected
+                    .iter()
+                    .map(|line| line.and_then(|line| source_lines[line]))
+                    .collect();
+            }
+        }
+
         if let Some(from_text) = &self.config.from_text {
             let lines: Vec<&str> = processed.lines().collect();
             let range = Self::filter_line_range(&lines, from_text);
@@ -125,13 +141,13 @@ impl MarkdownProcessor {
     }
 }
 
-struct SplitDocument<'a> {
-    body: &'a str,
-    body_start_line: usize,
-    front_matter: Option<FrontMatter>,
+pub(super) struct SplitDocument<'a> {
+    pub(super) body: &'a str,
+    pub(super) body_start_line: usize,
+    pub(super) front_matter: Option<FrontMatter>,
 }
 
-fn split_front_matter(source: &str) -> Result<SplitDocument<'_>> {
+pub(super) fn split_front_matter(source: &str) -> Result<SplitDocument<'_>> {
     let mut lines = source.split_inclusive('\n');
     let Some(opening) = lines.next() else {
         return Ok(without_front_matter(source));
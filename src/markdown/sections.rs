@@ -0,0 +1,151 @@
+use super::*;
+use crate::error::MdvError;
+use pulldown_cmark::{HeadingLevel, LinkType};
+
+struct SourceHeading {
+    level: HeadingLevel,
+    text: String,
+    slug: String,
+    path: Vec<String>,
+    start: usize,
+}
+
+impl MarkdownProcessor {
+    /// Returns the lines of the section selected by `query`, followed by the footnote and
+    /// link reference definitions it uses from elsewhere in the document.
+    ///
+    /// `None` entries are blank separator lines that do not come from the source.
+    pub(super) fn section_lines(&self, content: &str, query: &str) -> Result<Vec<Option<usize>>> {
+        let mut parser = Parser::new_ext(content, self.options).into_offset_iter();
+        let (events, ranges): (Vec<Event>, Vec<Range<usize>>) = parser.by_ref().unzip();
+        let headings = source_headings(&events, &ranges);
+        let Some(selected) = find_section(&headings, query) else {
+            return Err(
+                MdvError::MarkdownError(format!("section not found: {}", query.trim())).into(),
+            );
+        };
+        let heading = &headings[selected];
+        let end = headings[selected + 1..]
+            .iter()
+            .find(|next| next.level <= heading.level)
+            .map_or(content.len(), |next| next.start);
+        let section = heading.start..end;
+
+        let line_starts = source_lines::starts(content);
+        let line_of = |offset: usize| source_lines::index_for_offset(&line_starts, offset);
+        let lines_of = |range: &Range<usize>| {
+            line_of(range.start)..line_of(range.end.saturating_sub(1).max(range.start)) + 1
+        };
+
+        let mut definitions: Vec<Range<usize>> = Vec::new();
+        for (event, range) in events.iter().zip(&ranges) {
+            if !section.contains(&range.start) {
+                continue;
+            }
+            let definition = match event {
+                Event::FootnoteReference(label) => {
+                    events
+                        .iter()
+                        .zip(&ranges)
+                        .find_map(|(event, range)| match event {
+                            Event::Start(Tag::FootnoteDefinition(definition))
+                                if definition == label =>
+                            {
+                                Some(range.clone())
+                            }
+                            _ => None,
+                        })
+                }
+                Event::Start(
+                    Tag::Link { link_type, id, .. } | Tag::Image { link_type, id, .. },
+                ) if matches!(
+                    link_type,
+                    LinkType::Reference | LinkType::Collapsed | LinkType::Shortcut
+                ) =>
+                {
+                    parser
+                        .reference_definitions()
+                        .get(id)
+                        .map(|definition| definition.span.clone())
+                }
+                _ => None,
+            };
+            if let Some(definition) = definition
+                && !section.contains(&definition.start)
+            {
+                definitions.push(lines_of(&definition));
+            }
+        }
+        definitions.sort_by_key(|lines| lines.start);
+        definitions.dedup();
+
+        let mut lines: Vec<Option<usize>> = lines_of(&section).map(Some).collect();
+        for definition in definitions {
+            lines.push(None);
+            lines.extend(definition.map(Some));
+        }
+        Ok(lines)
+    }
+}
+
+fn source_headings(events: &[Event<'_>], ranges: &[Range<usize>]) -> Vec<SourceHeading> {
+    let mut slugs = HeadingSlugs::default();
+    let mut ancestors: Vec<(HeadingLevel, String)> = Vec::new();
+    let mut headings = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        let Event::Start(Tag::Heading { level, id, .. }) = event else {
+            continue;
+        };
+        let text = heading_text(&events[index + 1..]);
+        while ancestors.last().is_some_and(|(parent, _)| parent >= level) {
+            ancestors.pop();
+        }
+        ancestors.push((*level, text.clone()));
+        headings.push(SourceHeading {
+            level: *level,
+            slug: slugs.next(&text, id.as_deref()),
+            path: ancestors.iter().map(|(_, text)| text.clone()).collect(),
+            text,
+            start: ranges[index].start,
+        });
+    }
+    headings
+}
+
+/// Matches a `Parent > Child` heading path, or a single anchor slug.
+///
+/// Path components are compared case-insensitively; the last one must name the heading
+/// itself and the others must name its ancestors in order, skipping levels as needed.
+fn find_section(headings: &[SourceHeading], query: &str) -> Option<usize> {
+    let components: Vec<String> = query
+        .split('>')
+        .map(|component| component.trim().to_lowercase())
+        .collect();
+    if components.iter().any(String::is_empty) {
+        return None;
+    }
+
+    headings
+        .iter()
+        .position(|heading| {
+            let Some((target, parents)) = components.split_last() else {
+                return false;
+            };
+            let Some((_, ancestors)) = heading.path.split_last() else {
+                return false;
+            };
+            let mut ancestors = ancestors.iter();
+            heading.text.to_lowercase() == *target
+                && parents.iter().all(|parent| {
+                    ancestors
+                        .by_ref()
+                        .any(|ancestor| ancestor.to_lowercase() == *parent)
+                })
+        })
+        .or_else(|| {
+            let slug = query.trim().trim_start_matches('#');
+            (components.len() == 1)
+                .then(|| headings.iter().position(|heading| heading.slug == slug))
+                .flatten()
+        })
+}
@@ -0,0 +1,222 @@
+use super::outline::heading_text;
+use super::parsing::split_front_matter;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde_yaml::Value;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Title and front matter properties of a document, read without rendering it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DocumentSummary {
+    /// Front matter `title`, or the text of the first level-one heading.
+    pub(crate) title: Option<String>,
+    /// Front matter keys with their scalar values; a list gives one value per item.
+    pub(crate) properties: Vec<(String, Vec<String>)>,
+}
+
+pub(crate) fn summarize(source: &str) -> DocumentSummary {
+    // Invalid front matter is rendered as text, so only the headings are left to read.
+    let (body, front_matter) = match split_front_matter(source) {
+        Ok(document) => (document.body, document.front_matter),
+        Err(_) => (source, None),
+    };
+    let properties: Vec<(String, Vec<String>)> = front_matter
+        .iter()
+        .flat_map(|front_matter| &front_matter.properties)
+        .filter_map(|(key, value)| {
+            let values = match value {
+                Value::Sequence(items) => items.iter().filter_map(scalar_text).collect(),
+                value => vec![scalar_text(value)?],
+            };
+            Some((scalar_text(key)?, values))
+        })
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+    let title = properties
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("title"))
+        .map(|(_, values)| values.join(" "))
+        .or_else(|| first_title_heading(body));
+    DocumentSummary { title, properties }
+}
+
+/// Summary of the file at `path`, read only up to the front matter `title` or the
+/// first level-one heading so that discovery does not load whole documents.
+pub(crate) fn summarize_file(path: &Path) -> io::Result<DocumentSummary> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut source = String::new();
+    let mut scanner = TitleScanner::default();
+    loop {
+        let start = source.len();
+        if reader.read_line(&mut source)? == 0 {
+            return Ok(summarize(&source));
+        }
+        if scanner.may_end_title(&source[start..]) {
+            let summary = summarize(&source);
+            if summary.title.is_some() {
+                return Ok(summary);
+            }
+        }
+    }
+}
+
+/// Line by line guess of where a title may be complete: the end of the front matter,
+/// or an ATX or setext level-one heading outside fenced code.
+#[derive(Default)]
+struct TitleScanner {
+    lines: usize,
+    in_front_matter: bool,
+    /// Marker character and length of the open code fence.
+    fence: Option<(char, usize)>,
+}
+
+impl TitleScanner {
+    fn may_end_title(&mut self, line: &str) -> bool {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let first = self.lines == 0;
+        self.lines += 1;
+        if first && line == "---" {
+            self.in_front_matter = true;
+            return false;
+        }
+        if self.in_front_matter {
+            self.in_front_matter = line != "---";
+            return !self.in_front_matter;
+        }
+
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if indent > 3 {
+            return false;
+        }
+        let text = &line[indent..];
+        let marker = text
+            .chars()
+            .next()
+            .filter(|marker| matches!(marker, '`' | '~'));
+        let run = marker.map_or(0, |marker| {
+            text.len() - text.trim_start_matches(marker).len()
+        });
+        match (self.fence, marker) {
+            (Some((open, length)), Some(marker))
+                if marker == open && run >= length && text[run..].trim().is_empty() =>
+            {
+                self.fence = None;
+                false
+            }
+            (Some(_), _) => false,
+            (None, Some(marker)) if run >= 3 => {
+                self.fence = Some((marker, run));
+                false
+            }
+            (None, _) => {
+                let atx = text
+                    .strip_prefix('#')
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']));
+                let setext = text.starts_with('=') && text.trim_end().trim_matches('=').is_empty();
+                atx || setext
+            }
+        }
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn first_title_heading(body: &str) -> Option<String> {
+    let mut heading = None;
+    for event in Parser::new_ext(body, Options::ENABLE_HEADING_ATTRIBUTES) {
+        match event {
+            Event::Start(Tag::Heading {
+                level: HeadingLevel::H1,
+                ..
+            }) => heading = Some(Vec::new()),
+            Event::End(TagEnd::Heading(HeadingLevel::H1)) => {
+                let text = heading_text(&heading.take().unwrap_or_default());
+                if !text.is_empty() {
+                    return Some(text);
+                }
+            }
+            event => {
+                if let Some(events) = &mut heading {
+                    events.push(event);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_matter_title_wins_over_the_first_heading() {
+        let summary = summarize(
+            "---\ntitle: Retry budgets\ntags: [runbook, ops]\nstatus: draft\nreviewers:\n  lead: ann\n---\n\n# Heading\n",
+        );
+
+        assert_eq!(summary.title.as_deref(), Some("Retry budgets"));
+        assert_eq!(
+            summary.properties,
+            [
+                ("title".to_string(), vec!["Retry budgets".to_string()]),
+                (
+                    "tags".to_string(),
+                    vec!["runbook".to_string(), "ops".to_string()]
+                ),
+                ("status".to_string(), vec!["draft".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn title_falls_back_to_the_first_level_one_heading() {
+        let summary = summarize("Intro\n\n## Setup\n\n# The `mdv` *guide*\n\n# Later\n");
+
+        assert_eq!(summary.title.as_deref(), Some("The mdv guide"));
+        assert!(summary.properties.is_empty());
+        assert_eq!(
+            summarize("---\n: [\n---\n# Broken\n").title.as_deref(),
+            Some("Broken")
+        );
+        assert_eq!(summarize("No headings.\n").title, None);
+    }
+
+    #[test]
+    fn file_summary_stops_reading_after_the_title() {
+        let temp_dir = tempfile::TempDir::new().expect("create temp dir");
+        let path = temp_dir.path().join("guide.md");
+        // The invalid UTF-8 after the title fails the read if it is reached.
+        let write = |source: &str| {
+            std::fs::write(&path, [source.as_bytes(), b"\xff\n"].concat()).expect("write");
+        };
+
+        write("---\ntitle: Guide\ntags: [ops]\n---\n");
+        let summary = summarize_file(&path).expect("summary");
+        assert_eq!(summary.title.as_deref(), Some("Guide"));
+        assert_eq!(summary.properties.len(), 2);
+
+        write("---\ntags: ops\n---\n```sh\n# comment\n```\nIntro\n\n# Setup\n");
+        let summary = summarize_file(&path).expect("summary");
+        assert_eq!(summary.title.as_deref(), Some("Setup"));
+        assert_eq!(summary.properties.len(), 1);
+
+        write("Setup guide\n===\n");
+        assert_eq!(
+            summarize_file(&path).expect("summary").title.as_deref(),
+            Some("Setup guide")
+        );
+
+        write("## Setup\n");
+        assert!(summarize_file(&path).is_err());
+    }
+}
@@ -40,6 +40,37 @@ fn test_filter_from_text() {
     assert_eq!(result, "Target Line\nLine 3");
 }
 
+#[test]
+fn section_selection_ends_at_the_next_heading_of_the_same_level() {
+    let processor = MarkdownProcessor::new(&Config::default());
+    let content = "# Guide\n## Install\n### Linux\nsteps\n## Install\nagain\n# Other";
+
+    assert_eq!(
+        processor.section_lines(content, "install").unwrap(),
+        [Some(1), Some(2), Some(3)]
+    );
+    assert_eq!(
+        processor.section_lines(content, "Guide > Linux").unwrap(),
+        [Some(2), Some(3)]
+    );
+    assert_eq!(
+        processor.section_lines(content, "#install-1").unwrap(),
+        [Some(4), Some(5)]
+    );
+    assert!(processor.section_lines(content, "Other > Linux").is_err());
+}
+
+#[test]
+fn section_selection_appends_referenced_definitions() {
+    let processor = MarkdownProcessor::new(&Config::default());
+    let content = "# One\nSee [docs][ref].[^note]\n# Two\nUnused.[^other]\n\n[^note]: Note.\n[^other]: Other.\n\n[ref]: https://example.com\n";
+
+    assert_eq!(
+        processor.section_lines(content, "One").unwrap(),
+        [Some(0), Some(1), None, Some(5), None, Some(8)]
+    );
+}
+
 #[test]
 fn test_extract_code_language() {
     let fenced = CodeBlockKind::Fenced("rust".into());
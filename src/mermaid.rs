@@ -0,0 +1,72 @@
+use crate::utils::display_width;
+use regex::regex;
+
+mod canvas;
+mod flowchart;
+mod sequence;
+
+/// Draws a Mermaid flowchart or sequence diagram with box-drawing characters.
+///
+/// Returns `None` for other diagram types, for syntax outside the supported subset, and
+/// when the drawing would be wider than `max_width`, so callers can show the source.
+pub(crate) fn render_mermaid(source: &str, max_width: usize) -> Option<String> {
+    let lines = diagram_lines(source);
+    let (header, body) = lines.split_first()?;
+    let keyword = header.split([' ', '\t', ';']).next().unwrap_or_default();
+
+    let rendered = match keyword {
+        "graph" | "flowchart" => flowchart::render(header, body)?,
+        "sequenceDiagram" => sequence::render(header, body)?,
+        _ => return None,
+    };
+    let width = rendered.lines().map(display_width).max().unwrap_or(0);
+    (width <= max_width).then_some(rendered)
+}
+
+pub(crate) fn is_mermaid_language_hint(language_hint: &str) -> bool {
+    language_hint
+        .split([' ', '\t', ',', ';', '|'])
+        .find(|token| !token.is_empty())
+        .is_some_and(|token| token.eq_ignore_ascii_case("mermaid"))
+}
+
+/// Returns the trimmed, non-empty lines of a diagram without front matter,
+/// `%%` comments, and `%%{init}%%` directives.
+fn diagram_lines(source: &str) -> Vec<&str> {
+    let mut lines = source.lines().map(str::trim).peekable();
+    if lines.peek() == Some(&"---") {
+        lines.next();
+        lines.by_ref().find(|line| *line == "---");
+    }
+    lines
+        .filter(|line| !line.is_empty() && !line.starts_with("%%"))
+        .collect()
+}
+
+/// Splits a label on `<br>` tags after removing quotes and Markdown-string backticks.
+fn label_lines(label: &str) -> Vec<String> {
+    let label = label.trim();
+    let label = label
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(label);
+    let label = label
+        .strip_prefix('`')
+        .and_then(|rest| rest.strip_suffix('`'))
+        .unwrap_or(label);
+    regex!(r"(?i)<br\s*/?>")
+        .split(label)
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+fn lines_width(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests;
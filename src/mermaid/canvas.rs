@@ -0,0 +1,208 @@
+use unicode_width::UnicodeWidthChar;
+
+pub(super) const UP: u8 = 1;
+pub(super) const DOWN: u8 = 2;
+pub(super) const LEFT: u8 = 4;
+pub(super) const RIGHT: u8 = 8;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum Stroke {
+    #[default]
+    Light,
+    Dashed,
+    Heavy,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum Glyph {
+    #[default]
+    Lines,
+    Char(char),
+    /// Right half of a double-width character.
+    Continuation,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Cell {
+    lines: u8,
+    stroke: Stroke,
+    glyph: Glyph,
+}
+
+/// Character grid where line segments merge into box-drawing junctions.
+///
+/// Lines record the directions they leave each cell in, so a vertical line meeting a
+/// horizontal one becomes `┬`, `├`, `┼` and so on. Text and explicit glyphs win over lines.
+pub(super) struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Canvas {
+    pub(super) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height],
+        }
+    }
+
+    fn cell_mut(&mut self, row: usize, column: usize) -> Option<&mut Cell> {
+        (row < self.height && column < self.width)
+            .then(|| &mut self.cells[row * self.width + column])
+    }
+
+    fn connect(&mut self, row: usize, column: usize, directions: u8, stroke: Stroke) {
+        if let Some(cell) = self.cell_mut(row, column) {
+            if cell.lines != 0 && cell.stroke != stroke {
+                cell.stroke = Stroke::Light;
+            } else {
+                cell.stroke = stroke;
+            }
+            cell.lines |= directions;
+        }
+    }
+
+    /// Draws a vertical line between two rows, both inclusive.
+    pub(super) fn vertical(&mut self, column: usize, from: usize, to: usize, stroke: Stroke) {
+        let (top, bottom) = (from.min(to), from.max(to));
+        for row in top..bottom {
+            self.connect(row, column, DOWN, stroke);
+            self.connect(row + 1, column, UP, stroke);
+        }
+    }
+
+    /// Draws a horizontal line between two columns, both inclusive.
+    pub(super) fn horizontal(&mut self, row: usize, from: usize, to: usize, stroke: Stroke) {
+        let (left, right) = (from.min(to), from.max(to));
+        for column in left..right {
+            self.connect(row, column, RIGHT, stroke);
+            self.connect(row, column + 1, LEFT, stroke);
+        }
+    }
+
+    pub(super) fn put(&mut self, row: usize, column: usize, glyph: char) {
+        if let Some(cell) = self.cell_mut(row, column) {
+            cell.glyph = Glyph::Char(glyph);
+        }
+    }
+
+    /// Writes text from `column`, returning the column after its last character.
+    pub(super) fn text(&mut self, row: usize, column: usize, text: &str) -> usize {
+        let mut column = column;
+        for ch in text.chars() {
+            let width = ch.width().unwrap_or(0);
+            if width == 0 {
+                continue;
+            }
+            self.put(row, column, ch);
+            if width == 2
+                && let Some(cell) = self.cell_mut(row, column + 1)
+            {
+                cell.glyph = Glyph::Continuation;
+            }
+            column += width;
+        }
+        column
+    }
+
+    /// Returns whether the cells in `columns` are empty, apart from the one at `except`.
+    ///
+    /// Cells past the right edge count as empty.
+    pub(super) fn is_blank(
+        &self,
+        row: usize,
+        columns: std::ops::Range<usize>,
+        except: Option<usize>,
+    ) -> bool {
+        row < self.height
+            && columns
+                .filter(|column| Some(*column) != except && *column < self.width)
+                .all(|column| {
+                    let cell = self.cells[row * self.width + column];
+                    cell.lines == 0 && matches!(cell.glyph, Glyph::Lines)
+                })
+    }
+
+    /// Draws a rectangle whose edges join lines that touch them.
+    pub(super) fn frame(
+        &mut self,
+        top: usize,
+        left: usize,
+        height: usize,
+        width: usize,
+        corners: [char; 4],
+    ) {
+        let (bottom, right) = (top + height - 1, left + width - 1);
+        self.horizontal(top, left, right, Stroke::Light);
+        self.horizontal(bottom, left, right, Stroke::Light);
+        self.vertical(left, top, bottom, Stroke::Light);
+        self.vertical(right, top, bottom, Stroke::Light);
+        self.put(top, left, corners[0]);
+        self.put(top, right, corners[1]);
+        self.put(bottom, left, corners[2]);
+        self.put(bottom, right, corners[3]);
+    }
+
+    /// Paints an opaque box over whatever is below it, such as lifelines under a note.
+    pub(super) fn opaque_box(&mut self, top: usize, left: usize, height: usize, width: usize) {
+        let (bottom, right) = (top + height - 1, left + width - 1);
+        for row in top..=bottom {
+            for column in left..=right {
+                let glyph = match (row, column) {
+                    (row, column) if row == top && column == left => '┌',
+                    (row, column) if row == top && column == right => '┐',
+                    (row, column) if row == bottom && column == left => '└',
+                    (row, column) if row == bottom && column == right => '┘',
+                    (row, _) if row == top || row == bottom => '─',
+                    (_, column) if column == left || column == right => '│',
+                    _ => ' ',
+                };
+                self.put(row, column, glyph);
+            }
+        }
+    }
+
+    pub(super) fn render(&self) -> String {
+        let mut output = String::new();
+        for row in self.cells.chunks(self.width.max(1)).take(self.height) {
+            let mut line = String::new();
+            for cell in row {
+                match cell.glyph {
+                    Glyph::Char(ch) => line.push(ch),
+                    Glyph::Continuation => {}
+                    Glyph::Lines => line.push(line_glyph(cell.lines, cell.stroke)),
+                }
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn line_glyph(lines: u8, stroke: Stroke) -> char {
+    let vertical = lines & (LEFT | RIGHT) == 0;
+    let horizontal = lines & (UP | DOWN) == 0;
+    match (lines, stroke) {
+        (0, _) => ' ',
+        (_, Stroke::Dashed) if vertical => '╎',
+        (_, Stroke::Dashed) if horizontal => '╌',
+        (_, Stroke::Heavy) if vertical => '┃',
+        (_, Stroke::Heavy) if horizontal => '━',
+        _ if vertical => '│',
+        _ if horizontal => '─',
+        _ => match lines {
+            l if l == DOWN | RIGHT => '┌',
+            l if l == DOWN | LEFT => '┐',
+            l if l == UP | RIGHT => '└',
+            l if l == UP | LEFT => '┘',
+            l if l == UP | DOWN | RIGHT => '├',
+            l if l == UP | DOWN | LEFT => '┤',
+            l if l == DOWN | LEFT | RIGHT => '┬',
+            l if l == UP | LEFT | RIGHT => '┴',
+            _ => '┼',
+        },
+    }
+}
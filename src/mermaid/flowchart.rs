@@ -0,0 +1,65 @@
+use super::canvas::Stroke;
+
+mod layout;
+mod parser;
+
+/// Direction the flowchart ranks advance in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Down,
+    Up,
+    Right,
+    Left,
+}
+
+impl Direction {
+    fn is_vertical(self) -> bool {
+        matches!(self, Self::Down | Self::Up)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Square,
+    Rounded,
+    Decision,
+}
+
+impl Shape {
+    /// Top-left, top-right, bottom-left, and bottom-right corners.
+    fn corners(self) -> [char; 4] {
+        match self {
+            Self::Square => ['┌', '┐', '└', '┘'],
+            Self::Rounded => ['╭', '╮', '╰', '╯'],
+            Self::Decision => ['╱', '╲', '╲', '╱'],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    lines: Vec<String>,
+    shape: Shape,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    stroke: Stroke,
+    head_from: bool,
+    head_to: bool,
+}
+
+#[derive(Debug)]
+struct Flowchart {
+    direction: Direction,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+pub(super) fn render(header: &str, body: &[&str]) -> Option<String> {
+    let chart = parser::parse(header, body)?;
+    layout::draw(&chart)
+}
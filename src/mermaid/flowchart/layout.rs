@@ -0,0 +1,853 @@
+//! Layered layout: ranks along the flow, barycenter ordering within ranks, and
+//! orthogonal edges routed on tracks in the gaps between ranks.
+
+use super::{Edge, Flowchart, Node};
+use crate::mermaid::canvas::{Canvas, Stroke};
+use crate::mermaid::lines_width;
+use crate::utils::display_width;
+use std::collections::{HashMap, HashSet};
+
+/// Directions a route leaves a cell in, along and across the flow.
+const MAIN_BACKWARD: u8 = 1;
+const MAIN_FORWARD: u8 = 2;
+const CROSS_BACKWARD: u8 = 4;
+const CROSS_FORWARD: u8 = 8;
+
+/// Blank columns between nodes of a top-down rank.
+const VERTICAL_SPACING: usize = 2;
+/// Blank rows between nodes of a left-to-right rank.
+const HORIZONTAL_SPACING: usize = 1;
+/// Distance from a node's center to the port used by edges that point back against the
+/// flow, so they run beside forward edges instead of merging with them.
+const VERTICAL_BACK_PORT: usize = 2;
+const HORIZONTAL_BACK_PORT: usize = 1;
+const ORDERING_SWEEPS: usize = 8;
+const PLACEMENT_SWEEPS: usize = 5;
+
+/// A node, or a dummy point that carries an edge across an intermediate rank.
+struct Item {
+    node: Option<usize>,
+    rank: usize,
+    /// Size along the flow.
+    main: usize,
+    /// Size across the flow.
+    cross: usize,
+    /// Space reserved across the flow, which also fits labels of incoming edges.
+    extent: usize,
+}
+
+/// Part of an edge between two adjacent ranks.
+struct Segment<'a> {
+    upper: usize,
+    lower: usize,
+    /// Whether the edge was reversed to break a cycle.
+    back: bool,
+    stroke: Stroke,
+    head_upper: bool,
+    head_lower: bool,
+    label: Option<&'a str>,
+}
+
+/// Segments in a gap that leave the same port, sharing a track when they jog sideways.
+struct Net {
+    upper: usize,
+    low: usize,
+    high: usize,
+    lowers: Vec<usize>,
+    members: Vec<usize>,
+}
+
+struct Gap {
+    size: usize,
+    first: usize,
+    /// Row of vertical labels, or the first column of the horizontal label band.
+    label_start: usize,
+    tracks: HashMap<usize, usize>,
+}
+
+pub(super) fn draw(chart: &Flowchart) -> Option<String> {
+    let vertical = chart.direction.is_vertical();
+    let (ranks, back_edges) = assign_ranks(chart)?;
+    let flip = matches!(
+        chart.direction,
+        super::Direction::Up | super::Direction::Left
+    );
+    let last_rank = ranks.iter().copied().max().unwrap_or(0);
+    let rank_of = |node: usize| {
+        if flip {
+            last_rank - ranks[node]
+        } else {
+            ranks[node]
+        }
+    };
+
+    let mut items: Vec<Item> = chart
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let (width, height) = (lines_width(&node.lines) + 4, node.lines.len() + 2);
+            let (main, cross) = if vertical {
+                (height, width)
+            } else {
+                (width, height)
+            };
+            Item {
+                node: Some(index),
+                rank: rank_of(index),
+                main,
+                cross,
+                extent: cross,
+            }
+        })
+        .collect();
+    let segments = build_segments(chart, &back_edges, &mut items, rank_of)?;
+    check_edge_ends(&items, &segments)?;
+    let back_port = if vertical {
+        VERTICAL_BACK_PORT
+    } else {
+        HORIZONTAL_BACK_PORT
+    };
+    for segment in segments.iter().filter(|segment| segment.back) {
+        for item in [segment.upper, segment.lower] {
+            let item = &mut items[item];
+            if item.node.is_some() {
+                // Leave room for the port between the center and the corner.
+                item.cross = item.cross.max(2 * back_port + 3);
+                item.extent = item.extent.max(item.cross);
+            }
+        }
+    }
+    if vertical {
+        for segment in segments.iter().filter(|segment| segment.label.is_some()) {
+            let width = segment.label.map_or(0, display_width) + 2;
+            let item = &mut items[segment.lower];
+            item.extent = item.extent.max(width);
+        }
+    }
+
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); last_rank + 1];
+    for (index, item) in items.iter().enumerate() {
+        layers[item.rank].push(index);
+    }
+    order_layers(&mut layers, &segments, items.len());
+
+    let spacing = if vertical {
+        VERTICAL_SPACING
+    } else {
+        HORIZONTAL_SPACING
+    };
+    let lefts = place_layers(&layers, &items, &segments, spacing);
+    let center = |item: usize| {
+        let item_ref = &items[item];
+        lefts[item] + (item_ref.extent - item_ref.cross) / 2 + (item_ref.cross - 1) / 2
+    };
+    let port = |item: usize, back: bool| {
+        center(item)
+            + if back && items[item].node.is_some() {
+                back_port
+            } else {
+                0
+            }
+    };
+
+    let rank_sizes: Vec<usize> = layers
+        .iter()
+        .map(|layer| {
+            layer
+                .iter()
+                .map(|&item| items[item].main)
+                .max()
+                .unwrap_or(1)
+        })
+        .collect();
+    let gaps: Vec<Gap> = (0..last_rank)
+        .map(|rank| plan_gap(rank, &items, &segments, &port, vertical))
+        .collect::<Option<_>>()?;
+    let mut rank_starts = vec![0usize; last_rank + 1];
+    for rank in 1..=last_rank {
+        rank_starts[rank] = rank_starts[rank - 1] + rank_sizes[rank - 1] + gaps[rank - 1].size;
+    }
+    let main_total = rank_starts[last_rank] + rank_sizes[last_rank];
+    let cross_total = (0..items.len())
+        .map(|item| lefts[item] + items[item].extent)
+        .max()
+        .unwrap_or(0);
+
+    let mut drawing = Drawing {
+        canvas: if vertical {
+            Canvas::new(cross_total, main_total)
+        } else {
+            Canvas::new(main_total, cross_total)
+        },
+        vertical,
+        routes: HashMap::new(),
+    };
+    for (index, item) in items.iter().enumerate() {
+        if let Some(node) = item.node {
+            let cross_start = lefts[index] + (item.extent - item.cross) / 2;
+            drawing.node(
+                &chart.nodes[node],
+                item,
+                rank_starts[item.rank],
+                cross_start,
+            );
+        }
+    }
+
+    for (index, segment) in segments.iter().enumerate() {
+        let (upper, lower) = (&items[segment.upper], &items[segment.lower]);
+        let (upper_center, lower_center) = (
+            port(segment.upper, segment.back),
+            port(segment.lower, segment.back),
+        );
+        let gap = &gaps[upper.rank];
+        let gap_start = rank_starts[upper.rank] + rank_sizes[upper.rank];
+        let lower_start = rank_starts[lower.rank];
+
+        let start = match upper.node {
+            Some(_) => rank_starts[upper.rank] + upper.main - 1 + usize::from(segment.head_upper),
+            None => rank_starts[upper.rank],
+        };
+        let end = lower_start - usize::from(lower.node.is_some() && segment.head_lower);
+        match gap.tracks.get(&index) {
+            Some(track) => {
+                let track = gap_start + gap.first + track;
+                drawing.along(index, upper_center, start, track, segment.stroke);
+                drawing.across(index, track, upper_center, lower_center, segment.stroke);
+                drawing.along(index, lower_center, track, end, segment.stroke);
+            }
+            None => drawing.along(index, upper_center, start, end, segment.stroke),
+        }
+        if segment.head_upper {
+            drawing.head(start, upper_center, false);
+        }
+        if segment.head_lower {
+            drawing.head(end, lower_center, true);
+        }
+    }
+    drawing.bridge_crossings(&segments)?;
+    // Labels go last so the check for free space sees every line.
+    for segment in &segments {
+        if let Some(label) = segment.label {
+            let rank = items[segment.upper].rank;
+            let gap = &gaps[rank];
+            let band_start = rank_starts[rank] + rank_sizes[rank] + gap.label_start;
+            drawing.label(label, band_start, port(segment.lower, segment.back), gap)?;
+        }
+    }
+
+    Some(drawing.canvas.render())
+}
+
+/// Ranks nodes by their longest path from a source, after reversing the edges that
+/// close cycles. Sources are then pulled down next to their first successor.
+///
+/// Also returns which edges were reversed.
+fn assign_ranks(chart: &Flowchart) -> Option<(Vec<usize>, Vec<bool>)> {
+    if chart.edges.iter().any(|edge| edge.from == edge.to) {
+        return None;
+    }
+    let count = chart.nodes.len();
+    let mut outgoing = vec![Vec::new(); count];
+    for (index, edge) in chart.edges.iter().enumerate() {
+        outgoing[edge.from].push((edge.to, index));
+    }
+
+    let mut reversed = vec![false; chart.edges.len()];
+    let mut state = vec![0u8; count];
+    for node in 0..count {
+        if state[node] == 0 {
+            mark_back_edges(node, &outgoing, &mut state, &mut reversed);
+        }
+    }
+
+    let oriented: Vec<(usize, usize)> = chart
+        .edges
+        .iter()
+        .zip(&reversed)
+        .map(|(edge, &reversed)| {
+            if reversed {
+                (edge.to, edge.from)
+            } else {
+                (edge.from, edge.to)
+            }
+        })
+        .collect();
+    let mut successors = vec![Vec::new(); count];
+    let mut indegree = vec![0usize; count];
+    for &(from, to) in &oriented {
+        successors[from].push(to);
+        indegree[to] += 1;
+    }
+
+    let mut ranks = vec![0usize; count];
+    let mut queue: std::collections::VecDeque<usize> =
+        (0..count).filter(|&node| indegree[node] == 0).collect();
+    let sources: Vec<usize> = queue.iter().copied().collect();
+    while let Some(node) = queue.pop_front() {
+        for &next in &successors[node] {
+            ranks[next] = ranks[next].max(ranks[node] + 1);
+            indegree[next] -= 1;
+            if indegree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+    for source in sources {
+        if let Some(closest) = successors[source].iter().map(|&next| ranks[next]).min() {
+            ranks[source] = closest - 1;
+        }
+    }
+    Some((ranks, reversed))
+}
+
+fn mark_back_edges(
+    node: usize,
+    outgoing: &[Vec<(usize, usize)>],
+    state: &mut [u8],
+    reversed: &mut [bool],
+) {
+    state[node] = 1;
+    for &(next, edge) in &outgoing[node] {
+        match state[next] {
+            0 => mark_back_edges(next, outgoing, state, reversed),
+            1 => reversed[edge] = true,
+            _ => {}
+        }
+    }
+    state[node] = 2;
+}
+
+/// Splits edges into per-gap segments, adding a dummy item in every rank an edge skips.
+fn build_segments<'a>(
+    chart: &'a Flowchart,
+    back_edges: &[bool],
+    items: &mut Vec<Item>,
+    rank_of: impl Fn(usize) -> usize,
+) -> Option<Vec<Segment<'a>>> {
+    let mut segments = Vec::new();
+    let mut pairs = HashSet::new();
+    for (edge, &back) in chart.edges.iter().zip(back_edges) {
+        let Edge { from, to, .. } = *edge;
+        let (upper, lower) = if rank_of(from) < rank_of(to) {
+            (from, to)
+        } else {
+            (to, from)
+        };
+        // A second edge between the same ports would be drawn on top of the first.
+        if !pairs.insert((upper, lower, back)) {
+            return None;
+        }
+        let (head_upper, head_lower) = if upper == from {
+            (edge.head_from, edge.head_to)
+        } else {
+            (edge.head_to, edge.head_from)
+        };
+
+        let mut previous = upper;
+        for rank in rank_of(upper) + 1..=rank_of(lower) {
+            let next = if rank == rank_of(lower) {
+                lower
+            } else {
+                items.push(Item {
+                    node: None,
+                    rank,
+                    main: 1,
+                    cross: 1,
+                    extent: 1,
+                });
+                items.len() - 1
+            };
+            segments.push(Segment {
+                upper: previous,
+                lower: next,
+                back,
+                stroke: edge.stroke,
+                head_upper: head_upper && previous == upper,
+                head_lower: head_lower && next == lower,
+                label: edge.label.as_deref().filter(|_| next == lower),
+            });
+            previous = next;
+        }
+    }
+    Some(segments)
+}
+
+/// Rejects edge ends that cannot be drawn unambiguously: a side of a node where some
+/// edges have arrowheads and others do not, and a labeled edge sharing its entry point.
+fn check_edge_ends(items: &[Item], segments: &[Segment<'_>]) -> Option<()> {
+    let mut heads: HashMap<(usize, bool, bool), bool> = HashMap::new();
+    let mut entries: HashMap<(usize, bool), usize> = HashMap::new();
+    for segment in segments {
+        for (item, lower_side, head) in [
+            (segment.upper, false, segment.head_upper),
+            (segment.lower, true, segment.head_lower),
+        ] {
+            let port = (item, lower_side, segment.back);
+            if items[item].node.is_some() && *heads.entry(port).or_insert(head) != head {
+                return None;
+            }
+        }
+        *entries.entry((segment.lower, segment.back)).or_default() += 1;
+    }
+    segments
+        .iter()
+        .all(|segment| segment.label.is_none() || entries[&(segment.lower, segment.back)] == 1)
+        .then_some(())
+}
+
+/// Orders each rank by the average position of its neighbors, keeping the ordering
+/// with the fewest crossings.
+fn order_layers(layers: &mut [Vec<usize>], segments: &[Segment<'_>], item_count: usize) {
+    let mut uppers = vec![Vec::new(); item_count];
+    let mut lowers = vec![Vec::new(); item_count];
+    for segment in segments {
+        uppers[segment.lower].push(segment.upper);
+        lowers[segment.upper].push(segment.lower);
+    }
+
+    let mut positions = vec![0usize; item_count];
+    let index_positions = |layers: &[Vec<usize>], positions: &mut [usize]| {
+        for layer in layers {
+            for (position, &item) in layer.iter().enumerate() {
+                positions[item] = position;
+            }
+        }
+    };
+    index_positions(layers, &mut positions);
+    let mut best = layers.to_vec();
+    let mut best_crossings = crossings(segments, &positions);
+
+    for sweep in 0..ORDERING_SWEEPS {
+        let downward = sweep % 2 == 0;
+        let ranks: Vec<usize> = if downward {
+            (1..layers.len()).collect()
+        } else {
+            (0..layers.len().saturating_sub(1)).rev().collect()
+        };
+        for rank in ranks {
+            let neighbors = if downward { &uppers } else { &lowers };
+            let barycenter = |item: usize| {
+                let around = &neighbors[item];
+                if around.is_empty() {
+                    positions[item] as f64
+                } else {
+                    around
+                        .iter()
+                        .map(|&other| positions[other] as f64)
+                        .sum::<f64>()
+                        / around.len() as f64
+                }
+            };
+            let mut keyed: Vec<(f64, usize)> = layers[rank]
+                .iter()
+                .map(|&item| (barycenter(item), item))
+                .collect();
+            keyed.sort_by(|left, right| left.0.total_cmp(&right.0));
+            layers[rank] = keyed.into_iter().map(|(_, item)| item).collect();
+            for (position, &item) in layers[rank].iter().enumerate() {
+                positions[item] = position;
+            }
+        }
+        let current = crossings(segments, &positions);
+        if current < best_crossings {
+            best_crossings = current;
+            best = layers.to_vec();
+        }
+    }
+    layers.clone_from_slice(&best);
+}
+
+fn crossings(segments: &[Segment<'_>], positions: &[usize]) -> usize {
+    let mut count = 0;
+    for (index, first) in segments.iter().enumerate() {
+        for second in &segments[index + 1..] {
+            let upper = positions[first.upper] as isize - positions[second.upper] as isize;
+            let lower = positions[first.lower] as isize - positions[second.lower] as isize;
+            if upper * lower < 0 {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Assigns cross-axis offsets that center items on their neighbors as closely as the
+/// spacing allows.
+fn place_layers(
+    layers: &[Vec<usize>],
+    items: &[Item],
+    segments: &[Segment<'_>],
+    spacing: usize,
+) -> Vec<usize> {
+    let mut uppers = vec![Vec::new(); items.len()];
+    let mut lowers = vec![Vec::new(); items.len()];
+    for segment in segments {
+        uppers[segment.lower].push(segment.upper);
+        lowers[segment.upper].push(segment.lower);
+    }
+    let offset = |item: usize| {
+        let item = &items[item];
+        ((item.extent - item.cross) / 2 + (item.cross - 1) / 2) as f64
+    };
+
+    let mut lefts = vec![0f64; items.len()];
+    for layer in layers {
+        let mut left = 0f64;
+        for &item in layer {
+            lefts[item] = left;
+            left += (items[item].extent + spacing) as f64;
+        }
+    }
+
+    for sweep in 0..PLACEMENT_SWEEPS {
+        let downward = sweep % 2 == 0;
+        let neighbors = if downward { &uppers } else { &lowers };
+        let ranks: Vec<usize> = if downward {
+            (1..layers.len()).collect()
+        } else {
+            (0..layers.len().saturating_sub(1)).rev().collect()
+        };
+        for rank in ranks {
+            let desired: Vec<f64> = layers[rank]
+                .iter()
+                .map(|&item| {
+                    let around = &neighbors[item];
+                    if around.is_empty() {
+                        return lefts[item];
+                    }
+                    let center = around
+                        .iter()
+                        .map(|&other| lefts[other] + offset(other))
+                        .sum::<f64>()
+                        / around.len() as f64;
+                    center - offset(item)
+                })
+                .collect();
+            let widths: Vec<usize> = layers[rank]
+                .iter()
+                .map(|&item| items[item].extent + spacing)
+                .collect();
+            for (&item, left) in layers[rank].iter().zip(pack(&desired, &widths)) {
+                lefts[item] = left;
+            }
+        }
+    }
+
+    let minimum = lefts.iter().copied().fold(f64::INFINITY, f64::min);
+    let minimum = if minimum.is_finite() { minimum } else { 0.0 };
+    lefts
+        .iter()
+        .map(|left| (left - minimum).round() as usize)
+        .collect()
+}
+
+/// Moves items as close to their desired offsets as possible without overlapping.
+///
+/// Subtracting each item's minimum offset turns this into isotonic regression, which the
+/// pool-adjacent-violators algorithm solves exactly.
+fn pack(desired: &[f64], widths: &[usize]) -> Vec<f64> {
+    let mut minimums = Vec::with_capacity(desired.len());
+    let mut total = 0usize;
+    for width in widths {
+        minimums.push(total as f64);
+        total += width;
+    }
+
+    let mut blocks: Vec<(f64, usize)> = Vec::new();
+    for (target, minimum) in desired.iter().zip(&minimums) {
+        blocks.push((target - minimum, 1));
+        while blocks.len() > 1 {
+            let (last_sum, last_count) = blocks[blocks.len() - 1];
+            let (previous_sum, previous_count) = blocks[blocks.len() - 2];
+            if previous_sum / previous_count as f64 <= last_sum / last_count as f64 {
+                break;
+            }
+            blocks.pop();
+            *blocks.last_mut().expect("merged block") =
+                (previous_sum + last_sum, previous_count + last_count);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .flat_map(|(sum, count)| std::iter::repeat_n((sum / count as f64).round(), count))
+        .zip(minimums)
+        .map(|(value, minimum)| value + minimum)
+        .collect()
+}
+
+/// Lays out the gap below `rank`: an optional row for arrowheads pointing back up, one
+/// track per group of segments that must jog sideways, an optional label band, and the
+/// row entering the next rank. Fails when the tracks cannot be ordered without two
+/// segments sharing a column.
+fn plan_gap(
+    rank: usize,
+    items: &[Item],
+    segments: &[Segment<'_>],
+    port: &impl Fn(usize, bool) -> usize,
+    vertical: bool,
+) -> Option<Gap> {
+    let in_gap: Vec<(usize, &Segment<'_>)> = segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| items[segment.upper].rank == rank)
+        .collect();
+
+    let mut nets: Vec<Net> = Vec::new();
+    let mut by_upper: HashMap<(usize, bool), usize> = HashMap::new();
+    for &(index, segment) in &in_gap {
+        let upper = port(segment.upper, segment.back);
+        let lower = port(segment.lower, segment.back);
+        let net = *by_upper
+            .entry((segment.upper, segment.back))
+            .or_insert_with(|| {
+                nets.push(Net {
+                    upper,
+                    low: upper,
+                    high: upper,
+                    lowers: Vec::new(),
+                    members: Vec::new(),
+                });
+                nets.len() - 1
+            });
+        let net = &mut nets[net];
+        net.low = net.low.min(lower);
+        net.high = net.high.max(lower);
+        net.lowers.push(lower);
+        net.members.push(index);
+    }
+    nets.retain(|net| net.low != net.high);
+    nets.sort_by_key(|net| (net.low, net.high));
+
+    // A net leaving a column that another one enters must turn off it on an earlier
+    // track, or the two would share the column between their tracks.
+    let mut pending: Vec<usize> = (0..nets.len()).collect();
+    let mut track_of: Vec<Option<usize>> = vec![None; nets.len()];
+    let mut occupied: Vec<Vec<(usize, usize)>> = Vec::new();
+    while !pending.is_empty() {
+        let position = pending.iter().position(|&net| {
+            pending
+                .iter()
+                .all(|&other| other == net || !nets[net].lowers.contains(&nets[other].upper))
+        })?;
+        let net = pending.remove(position);
+        let (low, high) = (nets[net].low, nets[net].high);
+        let earliest = (0..nets.len())
+            .filter(|&other| nets[net].lowers.contains(&nets[other].upper))
+            .filter_map(|other| track_of[other])
+            .map(|track| track + 1)
+            .max()
+            .unwrap_or(0);
+        let fits = |spans: &Vec<(usize, usize)>| {
+            spans
+                .iter()
+                .all(|&(start, end)| low > end + 1 || high + 1 < start)
+        };
+        let track = match (earliest..occupied.len()).find(|&track| fits(&occupied[track])) {
+            Some(track) => track,
+            None => {
+                occupied.resize(occupied.len().max(earliest) + 1, Vec::new());
+                occupied.len() - 1
+            }
+        };
+        occupied[track].push((low, high));
+        track_of[net] = Some(track);
+    }
+    let mut tracks = HashMap::new();
+    for (net, track) in nets.iter().zip(track_of) {
+        for &member in &net.members {
+            tracks.insert(member, track?);
+        }
+    }
+
+    let first = usize::from(!vertical || in_gap.iter().any(|(_, segment)| segment.head_upper));
+    let label_width = in_gap
+        .iter()
+        .filter_map(|(_, segment)| segment.label.map(display_width))
+        .max();
+    // A vertical label covers the line in its row, so a row of line stays above it.
+    let label_size = match label_width {
+        Some(_) if vertical => 2,
+        Some(width) => width + 2,
+        None => 0,
+    };
+    let minimum = if vertical { 2 } else { 3 };
+    let size = (first + occupied.len() + label_size + 1).max(minimum);
+    let label_start = if vertical {
+        size - 2
+    } else {
+        size - 1 - label_size
+    };
+    Some(Gap {
+        size,
+        first,
+        label_start,
+        tracks,
+    })
+}
+
+/// Canvas addressed by position along and across the flow.
+struct Drawing {
+    canvas: Canvas,
+    vertical: bool,
+    /// Segments drawn through each cell, keyed by position along and across the flow,
+    /// with the directions each one leaves the cell in.
+    routes: HashMap<(usize, usize), Vec<(usize, u8)>>,
+}
+
+impl Drawing {
+    fn cell(&self, main: usize, cross: usize) -> (usize, usize) {
+        if self.vertical {
+            (main, cross)
+        } else {
+            (cross, main)
+        }
+    }
+
+    fn along(&mut self, segment: usize, cross: usize, from: usize, to: usize, stroke: Stroke) {
+        let (first, last) = (from.min(to), from.max(to));
+        for main in first..=last {
+            let directions = if main > first { MAIN_BACKWARD } else { 0 }
+                | if main < last { MAIN_FORWARD } else { 0 };
+            self.route(segment, main, cross, directions);
+        }
+        if self.vertical {
+            self.canvas.vertical(cross, from, to, stroke);
+        } else {
+            self.canvas.horizontal(cross, from, to, stroke);
+        }
+    }
+
+    fn across(&mut self, segment: usize, main: usize, from: usize, to: usize, stroke: Stroke) {
+        let (first, last) = (from.min(to), from.max(to));
+        for cross in first..=last {
+            let directions = if cross > first { CROSS_BACKWARD } else { 0 }
+                | if cross < last { CROSS_FORWARD } else { 0 };
+            self.route(segment, main, cross, directions);
+        }
+        if self.vertical {
+            self.canvas.horizontal(main, from, to, stroke);
+        } else {
+            self.canvas.vertical(main, from, to, stroke);
+        }
+    }
+
+    fn route(&mut self, segment: usize, main: usize, cross: usize, directions: u8) {
+        let routes = self.routes.entry((main, cross)).or_default();
+        match routes.iter_mut().find(|(other, _)| *other == segment) {
+            Some((_, existing)) => *existing |= directions,
+            None => routes.push((segment, directions)),
+        }
+    }
+
+    /// Draws cells where unrelated edges pass straight through each other as a bridge,
+    /// with the line that runs down the screen unbroken. Fails when unrelated edges meet
+    /// in any other way, since the junction would join them.
+    fn bridge_crossings(&mut self, segments: &[Segment<'_>]) -> Option<()> {
+        let related = |first: &Segment<'_>, second: &Segment<'_>| {
+            first.back == second.back
+                && [first.upper, first.lower]
+                    .iter()
+                    .any(|item| [second.upper, second.lower].contains(item))
+        };
+        let screen_vertical = if self.vertical {
+            MAIN_BACKWARD | MAIN_FORWARD
+        } else {
+            CROSS_BACKWARD | CROSS_FORWARD
+        };
+        let mut bridges = Vec::new();
+        for (&(main, cross), routes) in &self.routes {
+            let mut bridge = None;
+            for (index, &(first, first_directions)) in routes.iter().enumerate() {
+                for &(second, second_directions) in &routes[index + 1..] {
+                    if related(&segments[first], &segments[second]) {
+                        continue;
+                    }
+                    let straight = |directions: u8| {
+                        directions == MAIN_BACKWARD | MAIN_FORWARD
+                            || directions == CROSS_BACKWARD | CROSS_FORWARD
+                    };
+                    if !straight(first_directions)
+                        || !straight(second_directions)
+                        || first_directions == second_directions
+                    {
+                        return None;
+                    }
+                    let upright = if first_directions == screen_vertical {
+                        first
+                    } else {
+                        second
+                    };
+                    bridge = Some(segments[upright].stroke);
+                }
+            }
+            if let Some(stroke) = bridge {
+                bridges.push((self.cell(main, cross), stroke));
+            }
+        }
+        for ((row, column), stroke) in bridges {
+            let glyph = match stroke {
+                Stroke::Light => '│',
+                Stroke::Dashed => '╎',
+                Stroke::Heavy => '┃',
+            };
+            self.canvas.put(row, column, glyph);
+        }
+        Some(())
+    }
+
+    fn head(&mut self, main: usize, cross: usize, forward: bool) {
+        let glyph = match (self.vertical, forward) {
+            (true, true) => '▼',
+            (true, false) => '▲',
+            (false, true) => '►',
+            (false, false) => '◄',
+        };
+        let (row, column) = self.cell(main, cross);
+        self.canvas.put(row, column, glyph);
+    }
+
+    fn node(&mut self, node: &Node, item: &Item, main_start: usize, cross_start: usize) {
+        let (width, height) = if self.vertical {
+            (item.cross, item.main)
+        } else {
+            (item.main, item.cross)
+        };
+        let (top, left) = self.cell(main_start, cross_start);
+        self.canvas
+            .frame(top, left, height, width, node.shape.corners());
+        let first_row = top + 1 + (height - 2 - node.lines.len()) / 2;
+        for (index, line) in node.lines.iter().enumerate() {
+            let padding = (width - 4 - display_width(line)) / 2;
+            self.canvas
+                .text(first_row + index, left + 2 + padding, line);
+        }
+    }
+
+    /// Writes an edge label on the segment entering `cross`, or beside it when that would
+    /// touch another line or label. Fails when neither fits.
+    fn label(&mut self, label: &str, main: usize, cross: usize, gap: &Gap) -> Option<()> {
+        let width = display_width(label);
+        if self.vertical {
+            let centered = cross.checked_sub(width.saturating_sub(1) / 2);
+            let beside = [centered, Some(cross + 2), cross.checked_sub(width + 1)];
+            let start = beside.into_iter().flatten().find(|&start| {
+                self.canvas.is_blank(
+                    main,
+                    start.saturating_sub(1)..start + width + 1,
+                    Some(cross),
+                )
+            })?;
+            self.canvas.text(main, start, label);
+        } else {
+            let band = gap.size - 1 - gap.label_start;
+            let start = main + (band - width) / 2;
+            self.canvas.put(cross, start - 1, ' ');
+            let end = self.canvas.text(cross, start, label);
+            self.canvas.put(cross, end, ' ');
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,213 @@
+use super::{Direction, Edge, Flowchart, Node, Shape};
+use crate::mermaid::canvas::Stroke;
+use crate::mermaid::label_lines;
+use regex::regex;
+use std::collections::HashMap;
+
+/// Styling statements that do not change the drawing.
+const IGNORED_STATEMENTS: &[&str] = &["classDef", "class", "style", "linkStyle", "click"];
+
+/// Node shape openers, longest first, with their closers.
+const SHAPES: &[(&str, &str, Shape)] = &[
+    ("([", "])", Shape::Rounded),
+    ("((", "))", Shape::Rounded),
+    ("[[", "]]", Shape::Square),
+    ("[(", ")]", Shape::Square),
+    ("[/", "/]", Shape::Square),
+    ("[\\", "\\]", Shape::Square),
+    ("{{", "}}", Shape::Decision),
+    ("[", "]", Shape::Square),
+    ("(", ")", Shape::Rounded),
+    ("{", "}", Shape::Decision),
+    (">", "]", Shape::Square),
+];
+
+struct Link {
+    stroke: Stroke,
+    head_from: bool,
+    head_to: bool,
+    label: Option<String>,
+}
+
+#[derive(Default)]
+struct Builder {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    ids: HashMap<String, usize>,
+}
+
+pub(super) fn parse(header: &str, body: &[&str]) -> Option<Flowchart> {
+    let source = std::iter::once(header)
+        .chain(body.iter().copied())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut statements = split_statements(&source).into_iter();
+    let direction = parse_direction(statements.next()?)?;
+
+    let mut builder = Builder::default();
+    for statement in statements {
+        builder.statement(statement)?;
+    }
+    Some(Flowchart {
+        direction,
+        nodes: builder.nodes,
+        edges: builder.edges,
+    })
+}
+
+fn parse_direction(header: &str) -> Option<Direction> {
+    let mut words = header.split_whitespace().skip(1);
+    let direction = match words.next() {
+        None | Some("TB" | "TD") => Direction::Down,
+        Some("BT") => Direction::Up,
+        Some("LR") => Direction::Right,
+        Some("RL") => Direction::Left,
+        Some(_) => return None,
+    };
+    words.next().is_none().then_some(direction)
+}
+
+/// Splits on newlines and `;` outside node labels, edge labels, and quotes.
+fn split_statements(source: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let (mut depth, mut quoted, mut piped) = (0usize, false, false);
+    let mut start = 0;
+    for (index, ch) in source.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '|' if !quoted => piped = !piped,
+            '[' | '(' | '{' if !quoted => depth += 1,
+            ']' | ')' | '}' if !quoted => depth = depth.saturating_sub(1),
+            '\n' | ';' if ch == '\n' || (depth == 0 && !quoted && !piped) => {
+                statements.push(source[start..index].trim());
+                start = index + 1;
+                (depth, quoted, piped) = (0, false, false);
+            }
+            _ => {}
+        }
+    }
+    statements.push(source[start..].trim());
+    statements.retain(|statement| !statement.is_empty());
+    statements
+}
+
+impl Builder {
+    fn statement(&mut self, statement: &str) -> Option<()> {
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        if IGNORED_STATEMENTS.contains(&keyword) {
+            return Some(());
+        }
+
+        let (mut previous, mut rest) = self.node(statement)?;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return Some(());
+            }
+            let (link, after_link) = parse_link(rest)?;
+            let (next, after_node) = self.node(after_link.trim_start())?;
+            self.edges.push(Edge {
+                from: previous,
+                to: next,
+                label: link.label,
+                stroke: link.stroke,
+                head_from: link.head_from,
+                head_to: link.head_to,
+            });
+            (previous, rest) = (next, after_node);
+        }
+    }
+
+    /// Parses `id`, `id[label]`, `id(label)`, `id{label}` and similar shapes.
+    fn node<'s>(&mut self, text: &'s str) -> Option<(usize, &'s str)> {
+        let id_end = text
+            .char_indices()
+            .find(|(_, ch)| !(ch.is_alphanumeric() || *ch == '_'))
+            .map_or(text.len(), |(index, _)| index);
+        let (id, mut rest) = text.split_at(id_end);
+        if id.is_empty() || matches!(id, "subgraph" | "end" | "direction") {
+            return None;
+        }
+
+        let mut shape = None;
+        if let Some(&(open, close, kind)) = SHAPES.iter().find(|(open, ..)| rest.starts_with(open))
+        {
+            let inner = &rest[open.len()..];
+            let label_end = match inner.strip_prefix('"') {
+                Some(quoted) => quoted.find('"')? + 2,
+                None => inner.find(close)?,
+            };
+            rest = inner[label_end..].strip_prefix(close)?;
+            shape = Some((label_lines(&inner[..label_end]), kind));
+        }
+        if let Some(class) = rest.strip_prefix(":::") {
+            rest =
+                class.trim_start_matches(|ch: char| ch.is_alphanumeric() || ch == '_' || ch == '-');
+        }
+        if rest.trim_start().starts_with('&') {
+            return None;
+        }
+
+        let index = match self.ids.get(id) {
+            Some(&index) => index,
+            None => {
+                self.nodes.push(Node {
+                    lines: vec![id.to_string()],
+                    shape: Shape::Square,
+                });
+                self.ids.insert(id.to_string(), self.nodes.len() - 1);
+                self.nodes.len() - 1
+            }
+        };
+        if let Some((lines, kind)) = shape {
+            self.nodes[index].lines = lines;
+            self.nodes[index].shape = kind;
+        }
+        Some((index, rest))
+    }
+}
+
+/// Parses `-->`, `---`, `-.->`, `==>`, their text forms such as `-- text -->`, and a
+/// following `|text|` label. Circle and cross heads are not supported.
+fn parse_link(text: &str) -> Option<(Link, &str)> {
+    let captures = regex!(
+        r"^(?P<start><)?(?:(?P<solid>-{2,}>|-{3,})|(?P<thick>={2,}>|={3,})|(?P<dotted>-\.+->?)|--\s*(?P<solid_text>[^\s>-][^>]*?)\s*(?P<solid_end>-{2,}>|-{3,})|==\s*(?P<thick_text>[^\s>=][^>]*?)\s*(?P<thick_end>={2,}>|={3,})|-\.\s*(?P<dotted_text>[^\s.>-][^>]*?)\s*(?P<dotted_end>\.+->?))"
+    )
+    .captures(text)?;
+
+    let group = |name: &str| captures.name(name).map(|found| found.as_str());
+    let (stroke, operator, label) = if let Some(operator) = group("solid") {
+        (Stroke::Light, operator, None)
+    } else if let Some(operator) = group("thick") {
+        (Stroke::Heavy, operator, None)
+    } else if let Some(operator) = group("dotted") {
+        (Stroke::Dashed, operator, None)
+    } else if let Some(label) = group("solid_text") {
+        (Stroke::Light, group("solid_end")?, Some(label))
+    } else if let Some(label) = group("thick_text") {
+        (Stroke::Heavy, group("thick_end")?, Some(label))
+    } else {
+        (Stroke::Dashed, group("dotted_end")?, group("dotted_text"))
+    };
+
+    let mut rest = &text[captures.get(0)?.end()..];
+    let mut label = label.map(str::to_string);
+    if let Some(piped) = rest.trim_start().strip_prefix('|') {
+        let end = piped.find('|')?;
+        label = Some(piped[..end].to_string());
+        rest = &piped[end + 1..];
+    }
+    let label = label
+        .map(|label| label_lines(&label).join(" "))
+        .filter(|label| !label.is_empty());
+
+    Some((
+        Link {
+            stroke,
+            head_from: group("start").is_some(),
+            head_to: operator.ends_with('>'),
+            label,
+        },
+        rest,
+    ))
+}
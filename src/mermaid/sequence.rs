@@ -0,0 +1,212 @@
+use super::canvas::Stroke;
+use super::label_lines;
+use regex::regex;
+use std::collections::HashMap;
+
+mod layout;
+
+/// Block statements that open a frame around the messages up to their `end`.
+const BLOCK_KEYWORDS: &[&str] = &["loop", "alt", "opt", "par", "critical", "break", "rect"];
+/// Statements that split an open frame into sections.
+const DIVIDER_KEYWORDS: &[&str] = &["else", "and", "option"];
+
+#[derive(Debug)]
+struct Participant {
+    label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Head {
+    Arrow,
+    Open,
+    Cross,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Left,
+    Right,
+    Over,
+}
+
+#[derive(Debug)]
+enum Event {
+    Message {
+        from: usize,
+        to: usize,
+        lines: Vec<String>,
+        stroke: Stroke,
+        head: Head,
+    },
+    Note {
+        first: usize,
+        last: usize,
+        placement: Placement,
+        lines: Vec<String>,
+    },
+    Open {
+        label: String,
+    },
+    Divider {
+        label: String,
+    },
+    Close,
+}
+
+#[derive(Debug, Default)]
+struct SequenceDiagram {
+    participants: Vec<Participant>,
+    events: Vec<Event>,
+}
+
+pub(super) fn render(header: &str, body: &[&str]) -> Option<String> {
+    if header != "sequenceDiagram" {
+        return None;
+    }
+    let diagram = parse(body)?;
+    layout::draw(&diagram)
+}
+
+fn parse(lines: &[&str]) -> Option<SequenceDiagram> {
+    let mut diagram = SequenceDiagram::default();
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    let mut number: Option<usize> = None;
+    let mut depth = 0usize;
+
+    for line in lines {
+        let (keyword, rest) = line
+            .split_once(char::is_whitespace)
+            .map_or((*line, ""), |(keyword, rest)| (keyword, rest.trim()));
+
+        match keyword {
+            "participant" | "actor" => {
+                let (id, label) = rest
+                    .split_once(" as ")
+                    .map_or((rest, rest), |(id, label)| (id.trim(), label.trim()));
+                let index = participant(&mut diagram, &mut ids, id)?;
+                diagram.participants[index].label = label_lines(label).join(" ");
+            }
+            "autonumber" => number = Some(1),
+            "activate" | "deactivate" => {
+                participant(&mut diagram, &mut ids, rest)?;
+            }
+            _ if keyword.eq_ignore_ascii_case("note") => {
+                let event = parse_note(&mut diagram, &mut ids, rest)?;
+                diagram.events.push(event);
+            }
+            _ if BLOCK_KEYWORDS.contains(&keyword) => {
+                depth += 1;
+                let label = if keyword == "rect" {
+                    String::new()
+                } else {
+                    format!("{keyword} {rest}").trim_end().to_string()
+                };
+                diagram.events.push(Event::Open { label });
+            }
+            _ if DIVIDER_KEYWORDS.contains(&keyword) && depth > 0 => {
+                let label = format!("{keyword} {rest}").trim_end().to_string();
+                diagram.events.push(Event::Divider { label });
+            }
+            "end" if rest.is_empty() => {
+                depth = depth.checked_sub(1)?;
+                diagram.events.push(Event::Close);
+            }
+            _ => {
+                let mut event = parse_message(&mut diagram, &mut ids, line)?;
+                if let (Some(current), Event::Message { lines, .. }) = (number.as_mut(), &mut event)
+                {
+                    lines[0] = format!("{current}. {}", lines[0]).trim_end().to_string();
+                    *current += 1;
+                }
+                diagram.events.push(event);
+            }
+        }
+    }
+
+    (depth == 0 && !diagram.participants.is_empty()).then_some(diagram)
+}
+
+fn participant(
+    diagram: &mut SequenceDiagram,
+    ids: &mut HashMap<String, usize>,
+    id: &str,
+) -> Option<usize> {
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(*ids.entry(id.to_string()).or_insert_with(|| {
+        diagram.participants.push(Participant {
+            label: id.to_string(),
+        });
+        diagram.participants.len() - 1
+    }))
+}
+
+/// Parses the part of `Note right of A: text` after `Note`.
+fn parse_note(
+    diagram: &mut SequenceDiagram,
+    ids: &mut HashMap<String, usize>,
+    rest: &str,
+) -> Option<Event> {
+    let captures = regex!(
+        r"(?i)^(?P<placement>left of|right of|over)\s+(?P<targets>[^:]+?)\s*:\s*(?P<text>.*)$"
+    )
+    .captures(rest)?;
+    let placement = match captures["placement"].to_ascii_lowercase().as_str() {
+        "left of" => Placement::Left,
+        "right of" => Placement::Right,
+        _ => Placement::Over,
+    };
+    let targets: Vec<&str> = captures["targets"].split(',').map(str::trim).collect();
+    let (first, last) = match targets.as_slice() {
+        [only] => {
+            let index = participant(diagram, ids, only)?;
+            (index, index)
+        }
+        [first, last] if placement == Placement::Over => {
+            let (first, last) = (
+                participant(diagram, ids, first)?,
+                participant(diagram, ids, last)?,
+            );
+            (first.min(last), first.max(last))
+        }
+        _ => return None,
+    };
+    Some(Event::Note {
+        first,
+        last,
+        placement,
+        lines: label_lines(&captures["text"]),
+    })
+}
+
+/// Parses `A->>B: text` and the other arrows: `->`, `-->`, `-->>`, `-x`, `--x`, `-)`, `--)`.
+fn parse_message(
+    diagram: &mut SequenceDiagram,
+    ids: &mut HashMap<String, usize>,
+    line: &str,
+) -> Option<Event> {
+    let captures = regex!(
+        r"^(?P<from>[^\s:+<>-]+)\s*(?P<arrow>-->>|->>|-->|->|--x|-x|--\)|-\))\s*[+-]?\s*(?P<to>[^\s:+<>-]+)\s*(?::\s*(?P<text>.*))?$"
+    )
+    .captures(line)?;
+    let arrow = &captures["arrow"];
+    let head = match arrow.trim_start_matches('-') {
+        ">>" => Head::Arrow,
+        "x" => Head::Cross,
+        ")" => Head::Open,
+        _ => Head::None,
+    };
+    Some(Event::Message {
+        from: participant(diagram, ids, &captures["from"])?,
+        to: participant(diagram, ids, &captures["to"])?,
+        lines: label_lines(captures.name("text").map_or("", |text| text.as_str())),
+        stroke: if arrow.starts_with("--") {
+            Stroke::Dashed
+        } else {
+            Stroke::Light
+        },
+        head,
+    })
+}
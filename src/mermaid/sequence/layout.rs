@@ -0,0 +1,334 @@
+use super::{Event, Head, Placement, SequenceDiagram};
+use crate::mermaid::canvas::{Canvas, Stroke};
+use crate::mermaid::lines_width;
+use crate::utils::display_width;
+
+/// Rows taken by a participant box.
+const BOX_HEIGHT: usize = 3;
+/// Columns a frame adds on each side of the frames it encloses.
+const FRAME_INSET: usize = 2;
+
+/// Lifeline distance that `first`..`last` needs.
+struct Span {
+    first: usize,
+    last: usize,
+    distance: i64,
+}
+
+pub(super) fn draw(diagram: &SequenceDiagram) -> Option<String> {
+    let widths: Vec<i64> = diagram
+        .participants
+        .iter()
+        .map(|participant| (display_width(&participant.label) + 4) as i64)
+        .collect();
+    let centers = lifeline_centers(diagram, &widths);
+
+    // Horizontal extent of everything drawn, relative to the first lifeline.
+    let mut low = 0i64;
+    let mut high = 0i64;
+    let mut include = |from: i64, to: i64| {
+        low = low.min(from);
+        high = high.max(to);
+    };
+    for (center, width) in centers.iter().zip(&widths) {
+        include(
+            center - (width - 1) / 2,
+            center - (width - 1) / 2 + width - 1,
+        );
+    }
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut frame_label = 0usize;
+    let mut rows = BOX_HEIGHT;
+    for event in &diagram.events {
+        match event {
+            Event::Message {
+                from, to, lines, ..
+            } if from == to => {
+                let center = centers[*from];
+                include(center, center + 4 + lines_width(lines) as i64);
+                rows += lines.len().max(1) + 1;
+            }
+            Event::Message { lines, .. } => rows += lines.len().max(1) + 1,
+            Event::Note {
+                first,
+                last,
+                placement,
+                lines,
+            } => {
+                let (left, right) = note_columns(&centers, *first, *last, *placement, lines);
+                include(left, right);
+                rows += lines.len() + 2;
+            }
+            Event::Open { label } => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+                frame_label = frame_label.max(display_width(label) + 2 * FRAME_INSET * depth);
+                rows += 1;
+            }
+            Event::Divider { label } => {
+                frame_label = frame_label.max(display_width(label) + 2 * FRAME_INSET * depth);
+                rows += 1;
+            }
+            Event::Close => {
+                depth -= 1;
+                rows += 1;
+            }
+        }
+    }
+    rows += 1 + BOX_HEIGHT;
+
+    let margin = (FRAME_INSET * max_depth) as i64;
+    let shift = margin - low;
+    let width = ((high - low + 1) as usize + 2 * FRAME_INSET * max_depth).max(frame_label + 4);
+    let column = |center: i64| (center + shift) as usize;
+
+    let mut canvas = Canvas::new(width, rows);
+    let footer = rows - BOX_HEIGHT;
+    for (index, participant) in diagram.participants.iter().enumerate() {
+        let center = column(centers[index]);
+        let box_width = widths[index] as usize;
+        let left = center - (box_width - 1) / 2;
+        canvas.vertical(center, BOX_HEIGHT - 1, footer, Stroke::Light);
+        for top in [0, footer] {
+            canvas.frame(top, left, BOX_HEIGHT, box_width, ['┌', '┐', '└', '┘']);
+            canvas.text(top + 1, left + 2, &participant.label);
+        }
+    }
+
+    let mut row = BOX_HEIGHT;
+    let mut frames: Vec<usize> = Vec::new();
+    for event in &diagram.events {
+        match event {
+            Event::Message {
+                from,
+                to,
+                lines,
+                stroke,
+                head,
+            } if from == to => {
+                let center = column(centers[*from]);
+                let last = row + lines.len().max(1);
+                canvas.horizontal(row, center, center + 2, *stroke);
+                canvas.vertical(center + 2, row, last, *stroke);
+                canvas.horizontal(last, center + 1, center + 2, *stroke);
+                canvas.put(last, center + 1, head_glyph(*head, false).unwrap_or('─'));
+                for (offset, line) in lines.iter().enumerate() {
+                    canvas.text(row + offset, center + 4, line);
+                }
+                row = last + 1;
+            }
+            Event::Message {
+                from,
+                to,
+                lines,
+                stroke,
+                head,
+            } => {
+                let (from, to) = (column(centers[*from]), column(centers[*to]));
+                let (left, right) = (from.min(to), from.max(to));
+                let text_rows = lines.len().max(1);
+                for (offset, line) in lines.iter().enumerate() {
+                    let padding = (right - left - 1).saturating_sub(display_width(line)) / 2;
+                    canvas.text(
+                        row + text_rows - lines.len() + offset,
+                        left + 1 + padding,
+                        line,
+                    );
+                }
+                row += text_rows;
+                let forward = to > from;
+                match head_glyph(*head, forward) {
+                    Some(glyph) => {
+                        let tip = if forward { to - 1 } else { to + 1 };
+                        canvas.horizontal(row, from, tip, *stroke);
+                        canvas.put(row, tip, glyph);
+                    }
+                    None => canvas.horizontal(row, from, to, *stroke),
+                }
+                row += 1;
+            }
+            Event::Note {
+                first,
+                last,
+                placement,
+                lines,
+            } => {
+                let (left, right) = note_columns(&centers, *first, *last, *placement, lines);
+                let (left, right) = (column(left), column(right));
+                canvas.opaque_box(row, left, lines.len() + 2, right - left + 1);
+                for (offset, line) in lines.iter().enumerate() {
+                    let padding = (right - left - 3).saturating_sub(display_width(line)) / 2;
+                    canvas.text(row + 1 + offset, left + 2 + padding, line);
+                }
+                row += lines.len() + 2;
+            }
+            Event::Open { label } => {
+                frames.push(row);
+                let (left, right) = frame_columns(frames.len(), width);
+                canvas.horizontal(row, left, right, Stroke::Light);
+                frame_label_text(&mut canvas, row, left, label);
+                row += 1;
+            }
+            Event::Divider { label } => {
+                let (left, right) = frame_columns(frames.len(), width);
+                canvas.horizontal(row, left, right, Stroke::Dashed);
+                frame_label_text(&mut canvas, row, left, label);
+                row += 1;
+            }
+            Event::Close => {
+                let top = frames.pop()?;
+                let (left, right) = frame_columns(frames.len() + 1, width);
+                canvas.horizontal(row, left, right, Stroke::Light);
+                canvas.vertical(left, top, row, Stroke::Light);
+                canvas.vertical(right, top, row, Stroke::Light);
+                row += 1;
+            }
+        }
+    }
+
+    Some(canvas.render())
+}
+
+/// Places lifelines from left to right, widening each gap just enough for the boxes,
+/// messages, and notes between its two lifelines.
+fn lifeline_centers(diagram: &SequenceDiagram, widths: &[i64]) -> Vec<i64> {
+    let count = widths.len();
+    let half_left = |index: usize| (widths[index] - 1) / 2;
+    let half_right = |index: usize| widths[index] - 1 - half_left(index);
+    let mut gaps: Vec<i64> = (1..count)
+        .map(|index| half_right(index - 1) + half_left(index) + 3)
+        .collect();
+
+    let mut spans = Vec::new();
+    for event in &diagram.events {
+        match event {
+            Event::Message {
+                from, to, lines, ..
+            } => {
+                let text = lines_width(lines) as i64;
+                if from == to {
+                    spans.push(Span {
+                        first: *from,
+                        last: from + 1,
+                        distance: text + 6,
+                    });
+                } else {
+                    spans.push(Span {
+                        first: *from.min(to),
+                        last: *from.max(to),
+                        distance: (text + 3).max(4),
+                    });
+                }
+            }
+            Event::Note {
+                first,
+                last,
+                placement,
+                lines,
+            } => {
+                let note = lines_width(lines) as i64 + 4;
+                match placement {
+                    Placement::Right => spans.push(Span {
+                        first: *first,
+                        last: first + 1,
+                        distance: note + 3,
+                    }),
+                    Placement::Left if *first > 0 => spans.push(Span {
+                        first: first - 1,
+                        last: *first,
+                        distance: note + 3,
+                    }),
+                    Placement::Over if first == last => {
+                        spans.push(Span {
+                            first: *first,
+                            last: first + 1,
+                            distance: note - 1 - (note - 1) / 2 + 2,
+                        });
+                        if *first > 0 {
+                            spans.push(Span {
+                                first: first - 1,
+                                last: *first,
+                                distance: (note - 1) / 2 + 2,
+                            });
+                        }
+                    }
+                    Placement::Over => spans.push(Span {
+                        first: *first,
+                        last: *last,
+                        distance: note - 5,
+                    }),
+                    Placement::Left => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    spans.retain(|span| span.last < count && span.first < span.last);
+    spans.sort_by_key(|span| span.last - span.first);
+    for span in spans {
+        let current: i64 = gaps[span.first..span.last].iter().sum();
+        let missing = span.distance - current;
+        if missing <= 0 {
+            continue;
+        }
+        let parts = (span.last - span.first) as i64;
+        for (offset, gap) in gaps[span.first..span.last].iter_mut().enumerate() {
+            *gap += missing / parts + i64::from((offset as i64) < missing % parts);
+        }
+    }
+
+    std::iter::once(0)
+        .chain(gaps.iter().scan(0, |center, gap| {
+            *center += gap;
+            Some(*center)
+        }))
+        .collect()
+}
+
+/// Returns the first and last columns of a note box, relative to the first lifeline.
+fn note_columns(
+    centers: &[i64],
+    first: usize,
+    last: usize,
+    placement: Placement,
+    lines: &[String],
+) -> (i64, i64) {
+    let width = lines_width(lines) as i64 + 4;
+    match placement {
+        Placement::Right => (centers[first] + 2, centers[first] + 1 + width),
+        Placement::Left => (centers[first] - 1 - width, centers[first] - 2),
+        Placement::Over if first == last => {
+            let left = centers[first] - (width - 1) / 2;
+            (left, left + width - 1)
+        }
+        Placement::Over => {
+            let (left, right) = (centers[first] - 2, centers[last] + 2);
+            let extra = (width - (right - left + 1)).max(0);
+            (left - extra / 2, right + extra - extra / 2)
+        }
+    }
+}
+
+fn frame_columns(depth: usize, width: usize) -> (usize, usize) {
+    let inset = FRAME_INSET * (depth - 1);
+    (inset, width - 1 - inset)
+}
+
+fn frame_label_text(canvas: &mut Canvas, row: usize, left: usize, label: &str) {
+    if !label.is_empty() {
+        canvas.text(row, left + 2, &format!(" {label} "));
+    }
+}
+
+fn head_glyph(head: Head, forward: bool) -> Option<char> {
+    match (head, forward) {
+        (Head::Arrow, true) => Some('►'),
+        (Head::Arrow, false) => Some('◄'),
+        (Head::Open, true) => Some('>'),
+        (Head::Open, false) => Some('<'),
+        (Head::Cross, _) => Some('x'),
+        (Head::None, _) => None,
+    }
+}
@@ -0,0 +1,158 @@
+use super::*;
+
+fn render(source: &str) -> String {
+    render_mermaid(source, 200).expect("diagram should render")
+}
+
+#[test]
+fn mermaid_language_hint_accepts_first_token_only() {
+    assert!(is_mermaid_language_hint("mermaid"));
+    assert!(is_mermaid_language_hint("Mermaid title=flow"));
+    assert!(!is_mermaid_language_hint("rust"));
+    assert!(!is_mermaid_language_hint("text mermaid"));
+}
+
+#[test]
+fn top_down_flowchart_draws_boxes_and_arrows() {
+    let rendered = render("graph TD\n    A[Start] --> B(Finish)\n");
+
+    assert_eq!(
+        rendered,
+        "┌───────┐\n│ Start │\n└───┬───┘\n    │\n    ▼\n╭────────╮\n│ Finish │\n╰────────╯\n"
+    );
+}
+
+#[test]
+fn left_right_flowchart_keeps_edge_label_on_the_line() {
+    let rendered = render("flowchart LR\n    A[Client] -->|request| B[Server]");
+
+    let middle = rendered.lines().nth(1).unwrap();
+    assert_eq!(middle, "│ Client ├─ request ►│ Server │");
+}
+
+#[test]
+fn flowchart_strokes_follow_link_syntax() {
+    let rendered = render("graph TD\n    A ==> B\n    B -.-> C");
+
+    assert!(rendered.contains('┃'));
+    assert!(rendered.contains('╎'));
+}
+
+#[test]
+fn flowchart_with_cycle_renders_back_edge() {
+    let rendered = render(
+        "graph TD\n    A[Start] --> B{Ok?}\n    B -->|Yes| C[Ship]\n    B -->|No| D[Debug]\n    D --> B",
+    );
+
+    assert!(rendered.contains("╱─────╲"));
+    assert!(rendered.contains("Yes"));
+    assert!(rendered.contains("No"));
+    assert!(rendered.contains('▲'));
+}
+
+#[test]
+fn flowchart_labeled_edge_keeps_a_line_above_the_label() {
+    let rendered = render("graph TD\n    A[Start] ==>|go| B[End]");
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!(lines[3].trim(), "┃");
+    assert_eq!(lines[4].trim(), "go");
+    assert_eq!(lines[5].trim(), "▼");
+}
+
+#[test]
+fn flowchart_crossing_edges_draw_a_bridge() {
+    let rendered = render(
+        "graph TD\n    A[Start] --> B{Ok?}\n    B -->|Yes| C[Ship]\n    B -->|No| D[Debug]\n    D --> B",
+    );
+
+    assert!(!rendered.contains('┼'));
+    assert!(rendered.contains("─│─"));
+}
+
+#[test]
+fn flowchart_with_routes_sharing_a_column_falls_back() {
+    let source =
+        "graph TD\n    A --> D\n    B --> C\n    A --> C\n    B --> E\n    X --> E\n    X --> D";
+
+    assert!(render_mermaid(source, 200).is_none());
+}
+
+#[test]
+fn flowchart_ignores_styling_statements_and_comments() {
+    let rendered = render(
+        "%%{init: {'theme': 'dark'}}%%\ngraph LR\n    %% comment\n    A --> B\n    classDef hot fill:#f00\n    class A hot\n    style B fill:#0f0",
+    );
+
+    assert!(rendered.contains("│ A ├"));
+    assert!(rendered.contains("►│ B │"));
+}
+
+#[test]
+fn flowchart_splits_statements_on_semicolons() {
+    let rendered = render("graph LR; A-->B; B-->C");
+
+    assert!(rendered.contains("│ C │"));
+}
+
+#[test]
+fn unsupported_flowchart_syntax_falls_back() {
+    assert!(render_mermaid("graph TD\n    subgraph one\n    A --> B\n    end", 200).is_none());
+    assert!(render_mermaid("graph TD\n    A & B --> C", 200).is_none());
+    assert!(render_mermaid("graph TD\n    A --> A", 200).is_none());
+    assert!(render_mermaid("graph XY\n    A --> B", 200).is_none());
+}
+
+#[test]
+fn other_diagram_types_fall_back() {
+    assert!(render_mermaid("pie title Pets\n    \"Dogs\" : 386", 200).is_none());
+    assert!(render_mermaid("classDiagram\n    Animal <|-- Duck", 200).is_none());
+    assert!(render_mermaid("", 200).is_none());
+}
+
+#[test]
+fn diagram_wider_than_limit_falls_back() {
+    let source = "flowchart LR\n    A[First node] --> B[Second node] --> C[Third node]";
+
+    let rendered = render(source);
+    let width = rendered.lines().map(display_width).max().unwrap();
+
+    assert!(render_mermaid(source, width).is_some());
+    assert!(render_mermaid(source, width - 1).is_none());
+}
+
+#[test]
+fn sequence_diagram_draws_lifelines_and_messages() {
+    let rendered =
+        render("sequenceDiagram\n    participant A as Alice\n    A->>B: Hello\n    B-->>A: Hi");
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert!(lines[1].starts_with("│ Alice │"));
+    assert!(rendered.contains("Hello"));
+    assert!(rendered.contains("───►"));
+    assert!(rendered.contains("◄╌╌"));
+    assert_eq!(lines[lines.len() - 3], "┌───┴───┐  ┌─┴─┐");
+}
+
+#[test]
+fn sequence_diagram_numbers_messages_and_draws_frames() {
+    let rendered = render(
+        "sequenceDiagram\n    autonumber\n    loop Every minute\n        A->>B: Ping\n    end\n    Note right of B: Pong",
+    );
+
+    assert!(rendered.contains("1. Ping"));
+    assert!(rendered.contains(" loop Every minute "));
+    assert!(rendered.contains("Pong"));
+}
+
+#[test]
+fn sequence_diagram_with_unknown_statement_falls_back() {
+    assert!(render_mermaid("sequenceDiagram\n    A->>B: Hi\n    box Aqua Group", 200).is_none());
+    assert!(render_mermaid("sequenceDiagram\n    loop Forever\n    A->>B: Hi", 200).is_none());
+}
+
+#[test]
+fn label_lines_strip_quotes_and_split_breaks() {
+    assert_eq!(label_lines("\"One<br/>Two\""), vec!["One", "Two"]);
+    assert_eq!(label_lines("`Markdown`"), vec!["Markdown"]);
+}
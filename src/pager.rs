@@ -1,46 +1,78 @@
+use crate::clipboard::{Clipboard, ClipboardBackend};
 use crate::editor::EditorCommand;
+use crate::keybindings::{KeyAction, KeyBindings};
+use crate::reading::ReadingStore;
+use crate::renderer::{DocumentMap, MappedCodeBlock, MappedHeading, MappedLink};
 use anyhow::{Context, Result, anyhow};
 use minus::hooks::Hook;
 use minus::input::{HashedEventRegister, InputClassifier, InputEvent};
-use minus::{Pager, PagerState, PromptLine};
+use minus::{Pager, PagerState, PromptError, PromptLine, SearchMode};
 use notify::{EventKind, RecursiveMode, Watcher};
 use std::collections::hash_map::RandomState;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock, mpsc};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, mpsc};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+mod bookmarks;
+mod colors;
 mod document;
 mod footer;
 mod help;
 mod input;
+mod links;
+mod navigation;
 mod operations;
 mod page;
+mod reload;
+mod search;
+mod tabs;
+mod toc;
 mod watcher;
+mod yank;
 
-pub(super) use document::{PagerDocument, PagerScreen, RefreshCallback};
+use bookmarks::{BookmarkAction, lock_reading, save_position, saved_line, viewport_progress};
+pub(super) use colors::PagerColors;
+pub(super) use document::{DocumentLoader, PagerDocument, PagerScreen};
+use document::{RefreshCallback, SharedDocument};
 pub(super) use page::page;
 
-use footer::PagerFooter;
-use help::build_help_panel;
-use input::PagerInputClassifier;
+use footer::{PagerFooter, document_label};
+use help::{build_help_panel, panel_line};
+use input::{PagerInputClassifier, input_register};
+use links::{HintInput, LinkHints, LinkTarget, build_hint_panel, resolve_link, visible_links};
+use navigation::{Destination, HistoryStep, PagerNavigation, destination_line, lock_navigation};
 use operations::{
-    apply_refreshed_document, copy_document_contents, replace_document, report_operation_result,
-    single_line_message,
+    copy_document_contents, report_copy_result, report_operation_result, single_line_message,
 };
-use watcher::ActiveWatcher;
+use reload::{CHANGE_MARK_DURATION, anchored_line, changed_lines, mark_lines, source_changes};
+use search::{SearchInput, SourceSearch, SourceSearchPrompt, build_search_panel};
+use tabs::{PagerTab, PagerTabs, lock_tabs};
+use toc::{ContentsInput, TableOfContents, build_contents_panel};
+use watcher::{ActiveWatcher, comparable_path};
+use yank::{YankHints, YankInput, build_yank_panel, visible_targets};
 
 const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(3);
 
 #[cfg(test)]
 use input::{
-    HelpInputAction, help_input_action, is_copy_key, is_editor_key, is_help_key, is_reload_key,
+    HelpInputAction, bookmark_action, help_input_action, is_close_tab_key, is_contents_key,
+    is_history_back_key, is_history_forward_key, is_link_hint_key, is_new_tab_link_hint_key,
+    is_source_search_key, is_yank_key, key_action, source_match_step, tab_step,
 };
 #[cfg(test)]
+use links::hint_labels;
+#[cfg(test)]
+use navigation::{PagerHistory, PagerLocation};
+#[cfg(test)]
 use operations::clipboard_text;
 #[cfg(test)]
-use watcher::{comparable_path, event_targets_file};
+use search::{CaseMode, SearchOptions, compile_query, source_match_lines, step_match};
+#[cfg(test)]
+use watcher::event_targets_file;
+#[cfg(test)]
+use yank::{YankKind, YankTarget, section_text};
 
 #[cfg(test)]
 mod tests;
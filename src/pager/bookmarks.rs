@@ -0,0 +1,121 @@
+use super::*;
+use crate::reading::{ReadingPosition, ReadingStore};
+use input::PagerOverlay;
+use std::time::SystemTime;
+
+/// Pending `m` or `'` key, completed by a bookmark letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BookmarkAction {
+    Set,
+    Jump,
+}
+
+impl BookmarkAction {
+    const fn prompt(self) -> &'static str {
+        match self {
+            Self::Set => "Set bookmark: press a letter",
+            Self::Jump => "Go to bookmark: press a letter",
+        }
+    }
+}
+
+/// Line at the top of the viewport and the share of the document above its bottom.
+pub(super) fn viewport_progress(state: &PagerState) -> (usize, u8) {
+    let line = state.row_to_line(state.upper_mark).unwrap_or(0);
+    let rows = state.screen.formatted_lines_count();
+    let progress = ((state.upper_mark + state.content_rows()).min(rows) * 100)
+        .checked_div(rows)
+        .unwrap_or(100);
+    (line, u8::try_from(progress).unwrap_or(100))
+}
+
+pub(super) fn lock_reading(
+    reading: &Mutex<ReadingStore>,
+) -> Result<std::sync::MutexGuard<'_, ReadingStore>> {
+    reading
+        .lock()
+        .map_err(|_| anyhow!("Pager reading state lock poisoned"))
+}
+
+/// Line where `file` was left the last time it was read.
+pub(super) fn saved_line(
+    reading: &Mutex<ReadingStore>,
+    file: Option<&Path>,
+    map: &DocumentMap,
+) -> Result<Option<usize>> {
+    Ok(lock_reading(reading)?
+        .record(file)
+        .and_then(|record| record.position.as_ref())
+        .map(|position| position.resolve(map)))
+}
+
+pub(super) fn save_position(
+    reading: &Mutex<ReadingStore>,
+    file: Option<&Path>,
+    map: &DocumentMap,
+    (line, progress): (usize, u8),
+) -> Result<()> {
+    let position = ReadingPosition::capture(map, line);
+    lock_reading(reading)?.update(file, |record| {
+        record.mark_read(position, progress, SystemTime::now());
+    })
+}
+
+impl PagerInputClassifier {
+    pub(super) fn start_bookmark(&self, action: BookmarkAction) -> Result<()> {
+        self.pager
+            .send_message_for(action.prompt(), STATUS_MESSAGE_TIMEOUT)?;
+        self.set_overlay(Some(PagerOverlay::Bookmark(action)))
+    }
+
+    /// Records the reading position of the current document before another one replaces it.
+    pub(super) fn remember_position(&self, state: &PagerState) -> Result<()> {
+        let (document, navigation) = (self.document()?, self.navigation()?);
+        let file = lock_navigation(&navigation)?.file.clone();
+        let document = document.read()?;
+        save_position(
+            &self.reading,
+            file.as_deref(),
+            &document.map,
+            viewport_progress(state),
+        )
+    }
+
+    pub(super) fn apply_bookmark(
+        &self,
+        action: BookmarkAction,
+        letter: char,
+        state: &PagerState,
+    ) -> Result<()> {
+        let (document, navigation) = (self.document()?, self.navigation()?);
+        let file = lock_navigation(&navigation)?.file.clone();
+        let document = document.read()?;
+        match action {
+            BookmarkAction::Set => {
+                let line = state.row_to_line(state.upper_mark).unwrap_or(0);
+                let position = ReadingPosition::capture(&document.map, line);
+                lock_reading(&self.reading)?.update(file.as_deref(), |record| {
+                    record.bookmarks.insert(letter, position);
+                })?;
+                self.pager
+                    .send_message_for(format!("Set bookmark {letter}"), STATUS_MESSAGE_TIMEOUT)?;
+                Ok(())
+            }
+            BookmarkAction::Jump => {
+                let line = lock_reading(&self.reading)?
+                    .record(file.as_deref())
+                    .and_then(|record| record.bookmarks.get(&letter))
+                    .map(|position| position.resolve(&document.map));
+                drop(document);
+                let Some(line) = line else {
+                    self.pager.send_message_for(
+                        format!("Bookmark {letter} is not set"),
+                        STATUS_MESSAGE_TIMEOUT,
+                    )?;
+                    return Ok(());
+                };
+                self.jump_to_line(line, state)
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::theme::{Color, Theme};
+use crossterm::style::{ResetColor, SetForegroundColor};
+use minus::{PromptColor, PromptStyle, SearchColors};
+
+const CHANGE_MARKER: &str = "▎";
+
+/// Theme colors of the status bar, the panels below it, and search matches. A color
+/// set to `reset` keeps the terminal default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PagerColors {
+    /// Drops the status bar and panel backgrounds and separates footer sections with `|`.
+    pub(crate) transparent: bool,
+    footer_foreground: Option<PromptColor>,
+    footer_background: Option<PromptColor>,
+    footer_accent: Option<PromptColor>,
+    progress: Option<PromptColor>,
+    help_foreground: Option<PromptColor>,
+    help_background: Option<PromptColor>,
+    search_current: Option<PromptColor>,
+    search_match: Option<PromptColor>,
+    change_marker: Option<PromptColor>,
+}
+
+impl PagerColors {
+    pub(crate) fn from_theme(theme: &Theme) -> Self {
+        Self {
+            transparent: theme.pager_status_bar_transparent,
+            footer_foreground: prompt_color(&theme.pager_footer_foreground),
+            footer_background: prompt_color(&theme.pager_footer_background),
+            footer_accent: prompt_color(&theme.pager_footer_accent),
+            progress: prompt_color(&theme.pager_progress),
+            help_foreground: prompt_color(&theme.pager_help_foreground),
+            help_background: prompt_color(&theme.pager_help_background),
+            search_current: prompt_color(&theme.pager_search_current_background),
+            search_match: prompt_color(&theme.pager_search_match_background),
+            change_marker: prompt_color(&theme.warning),
+        }
+    }
+
+    /// Drops the color of the change marker, for output without colors.
+    pub(crate) const fn with_plain_change_marker(mut self) -> Self {
+        self.change_marker = None;
+        self
+    }
+
+    /// Title, messages, and the shown tab.
+    pub(super) fn footer_style(&self) -> PromptStyle {
+        style(
+            self.footer_foreground,
+            self.background(self.footer_background),
+        )
+    }
+
+    /// Brand and help sections at both ends of the footer.
+    pub(super) fn accent_style(&self) -> PromptStyle {
+        style(self.footer_foreground, self.background(self.footer_accent))
+    }
+
+    /// Progress, search position, and the other tabs.
+    pub(super) fn progress_style(&self) -> PromptStyle {
+        style(self.progress, self.background(self.footer_background))
+    }
+
+    /// Every panel drawn below the footer.
+    pub(super) fn panel_style(&self) -> PromptStyle {
+        style(self.help_foreground, self.background(self.help_background))
+    }
+
+    /// Backgrounds of search matches; without both, minus blends them from the text.
+    pub(super) fn search_colors(&self) -> Option<SearchColors> {
+        Some(SearchColors {
+            current: self.search_current?,
+            other: self.search_match?,
+        })
+    }
+
+    /// Gutter marker of the lines a reload changed, in the theme's warning color.
+    pub(super) fn change_marker(&self) -> String {
+        self.change_marker.map_or_else(
+            || CHANGE_MARKER.to_string(),
+            |color| {
+                format!(
+                    "{}{CHANGE_MARKER}{ResetColor}",
+                    SetForegroundColor(color.into())
+                )
+            },
+        )
+    }
+
+    const fn background(&self, color: Option<PromptColor>) -> Option<PromptColor> {
+        if self.transparent { None } else { color }
+    }
+}
+
+impl Default for PagerColors {
+    fn default() -> Self {
+        Self::from_theme(&Theme::default())
+    }
+}
+
+fn style(foreground: Option<PromptColor>, background: Option<PromptColor>) -> PromptStyle {
+    let style = foreground.map_or_else(PromptStyle::default, |color| {
+        PromptStyle::default().foreground(color)
+    });
+    background.map_or(style, |color| style.background(color))
+}
+
+const fn prompt_color(color: &Color) -> Option<PromptColor> {
+    Some(match *color {
+        Color::Black => PromptColor::Black,
+        Color::DarkRed => PromptColor::DarkRed,
+        Color::DarkGreen => PromptColor::DarkGreen,
+        Color::DarkYellow => PromptColor::DarkYellow,
+        Color::DarkBlue => PromptColor::DarkBlue,
+        Color::DarkMagenta => PromptColor::DarkMagenta,
+        Color::DarkCyan => PromptColor::DarkCyan,
+        Color::Grey => PromptColor::Grey,
+        Color::DarkGrey => PromptColor::DarkGrey,
+        Color::Red => PromptColor::Red,
+        Color::Green => PromptColor::Green,
+        Color::Yellow => PromptColor::Yellow,
+        Color::Blue => PromptColor::Blue,
+        Color::Magenta => PromptColor::Magenta,
+        Color::Cyan => PromptColor::Cyan,
+        Color::White => PromptColor::White,
+        Color::AnsiValue(value) => PromptColor::AnsiValue(value),
+        Color::Rgb { r, g, b } => PromptColor::Rgb { r, g, b },
+        Color::Reset => return None,
+    })
+}
@@ -4,7 +4,8 @@ pub(crate) struct PagerDocument {
     pub(in crate::pager) output: String,
     pub(in crate::pager) source: String,
     pub(in crate::pager) title: Option<String>,
-    status_bar_transparent: bool,
+    pub(in crate::pager) map: DocumentMap,
+    colors: PagerColors,
 }
 
 impl PagerDocument {
@@ -13,27 +14,170 @@ impl PagerDocument {
             output,
             source,
             title: None,
-            status_bar_transparent: false,
+            map: DocumentMap::default(),
+            colors: PagerColors::default(),
         }
     }
 
+    pub(crate) fn with_map(mut self, map: DocumentMap) -> Self {
+        self.map = map;
+        self
+    }
+
     pub(crate) fn with_title(mut self, title: impl Into<String>) -> Self {
         self.title = Some(title.into());
         self
     }
 
-    pub(crate) const fn with_status_bar_transparent(mut self, transparent: bool) -> Self {
-        self.status_bar_transparent = transparent;
+    pub(crate) const fn with_colors(mut self, colors: PagerColors) -> Self {
+        self.colors = colors;
         self
     }
 
-    pub(crate) const fn status_bar_transparent(&self) -> bool {
-        self.status_bar_transparent
+    pub(crate) const fn colors(&self) -> PagerColors {
+        self.colors
+    }
+}
+
+/// Document of one pager tab; its text reaches the pager only while the tab is shown.
+#[derive(Clone)]
+pub(in crate::pager) struct SharedDocument {
+    document: Arc<RwLock<PagerDocument>>,
+    shown: Arc<AtomicBool>,
+    /// Counts replacements, so a delayed redraw can tell that it is out of date.
+    revision: Arc<AtomicUsize>,
+}
+
+impl SharedDocument {
+    pub(in crate::pager) fn new(document: PagerDocument) -> Self {
+        Self {
+            document: Arc::new(RwLock::new(document)),
+            shown: Arc::new(AtomicBool::new(false)),
+            revision: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(in crate::pager) fn read(&self) -> Result<RwLockReadGuard<'_, PagerDocument>> {
+        self.document
+            .read()
+            .map_err(|_| anyhow!("Pager document lock poisoned"))
+    }
+
+    fn write(&self) -> Result<RwLockWriteGuard<'_, PagerDocument>> {
+        self.document
+            .write()
+            .map_err(|_| anyhow!("Pager document lock poisoned"))
+    }
+
+    /// Replaces the document and, while its tab is shown, the pager text.
+    pub(in crate::pager) fn replace(&self, pager: &Pager, refreshed: PagerDocument) -> Result<()> {
+        let mut document = self.write()?;
+        *document = refreshed;
+        self.revision.fetch_add(1, Ordering::SeqCst);
+        if self.is_shown() {
+            pager.set_text(document.output.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the document with a new rendering of the same file. While the tab is
+    /// shown, the view stays on the same content and the changed lines are marked in
+    /// the gutter for a moment.
+    pub(in crate::pager) fn reload(&self, pager: &Pager, refreshed: PagerDocument) -> Result<()> {
+        let mut document = self.write()?;
+        let previous = std::mem::replace(&mut *document, refreshed);
+        let revision = self.revision.fetch_add(1, Ordering::SeqCst) + 1;
+        if !self.is_shown() {
+            return Ok(());
+        }
+
+        let runs = source_changes(&previous.source, &document.source);
+        let changed = changed_lines(&document.map, &runs);
+        let marked = changed.contains(&true);
+        let output = if marked {
+            mark_lines(&document.output, &changed, &document.colors.change_marker())
+        } else {
+            document.output.clone()
+        };
+        let map = document.map.clone();
+        pager.set_text_anchored(output, move |line| {
+            anchored_line(&previous.map, &map, &runs, line)
+        })?;
+        drop(document);
+
+        if marked {
+            let shared = self.clone();
+            let pager = pager.clone();
+            thread::spawn(move || {
+                thread::sleep(CHANGE_MARK_DURATION);
+                let _ = shared.clear_marks(&pager, revision);
+            });
+        }
+        Ok(())
+    }
+
+    /// Redraws the document without change markers unless it was replaced since.
+    fn clear_marks(&self, pager: &Pager, revision: usize) -> Result<()> {
+        let document = self.write()?;
+        if self.revision.load(Ordering::SeqCst) == revision && self.is_shown() {
+            pager.set_text(document.output.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the document while no pager is running.
+    pub(in crate::pager) fn replace_hidden(&self, refreshed: PagerDocument) -> Result<()> {
+        *self.write()? = refreshed;
+        self.revision.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Shows the document in `pager`. The write lock orders this with `replace`, so a
+    /// refresh of another tab can never overwrite the text shown here.
+    pub(in crate::pager) fn show(&self, pager: &Pager) -> Result<()> {
+        let document = self.write()?;
+        self.shown.store(true, Ordering::SeqCst);
+        pager.set_text(document.output.clone())?;
+        Ok(())
+    }
+
+    /// Shows the document in `pager` at `line`, passing `left` the top line of the text it
+    /// replaces once the pager swaps them.
+    pub(in crate::pager) fn show_at(
+        &self,
+        pager: &Pager,
+        line: usize,
+        left: impl FnOnce(usize) + Send + Sync + 'static,
+    ) -> Result<()> {
+        let document = self.write()?;
+        self.shown.store(true, Ordering::SeqCst);
+        pager.set_text_anchored(document.output.clone(), move |previous| {
+            left(previous);
+            line
+        })?;
+        Ok(())
+    }
+
+    pub(in crate::pager) fn same(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.document, &other.document)
+    }
+
+    pub(in crate::pager) fn is_shown(&self) -> bool {
+        self.shown.load(Ordering::SeqCst)
+    }
+
+    pub(in crate::pager) fn hide(&self) -> Result<()> {
+        let _document = self.write()?;
+        self.shown.store(false, Ordering::SeqCst);
+        Ok(())
     }
 }
 
 pub(crate) type RefreshCallback = Arc<dyn Fn() -> Result<PagerDocument> + Send + Sync>;
 
+/// Renders any Markdown file, allowing the pager to reload and follow links.
+pub(crate) type DocumentLoader = Arc<dyn Fn(&Path) -> Result<PagerDocument> + Send + Sync>;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum PagerScreen {
     Alternate,
@@ -1,71 +1,111 @@
-use minus::{PromptColor, PromptContext, PromptError, PromptLine, PromptSpan, PromptStyle};
+use super::PagerColors;
+use minus::{PromptAttribute, PromptContext, PromptError, PromptLine, PromptSpan, PromptStyle};
 use std::path::Path;
 
 const BRAND_TEXT: &str = " MDV ";
 const HELP_TEXT: &str = " ? Help ";
 
-const ACCENT_BACKGROUND: PromptColor = PromptColor::Rgb {
-    r: 50,
-    g: 50,
-    b: 50,
-};
-const MAIN_FOREGROUND: PromptColor = PromptColor::Rgb {
-    r: 125,
-    g: 125,
-    b: 125,
-};
-const MAIN_BACKGROUND: PromptColor = PromptColor::Rgb {
-    r: 36,
-    g: 36,
-    b: 36,
-};
-const PROGRESS_FOREGROUND: PromptColor = PromptColor::Rgb {
-    r: 90,
-    g: 90,
-    b: 90,
-};
-
 pub(super) struct PagerFooter {
     title: String,
-    transparent: bool,
+    tabs: Vec<String>,
+    active_tab: usize,
+    colors: PagerColors,
 }
 
 impl PagerFooter {
-    pub(super) fn new(title: Option<&str>, file: Option<&Path>, transparent: bool) -> Self {
-        let title = title
-            .map(str::to_owned)
-            .filter(|name| !name.trim().is_empty())
-            .or_else(|| {
-                file.and_then(Path::file_name)
-                    .map(|name| name.to_string_lossy().into_owned())
-                    .filter(|name| !name.trim().is_empty())
-            })
-            .unwrap_or_else(|| "stdin".to_string());
+    pub(super) fn new(title: Option<&str>, file: Option<&Path>, colors: PagerColors) -> Self {
+        Self {
+            title: document_label(title, file),
+            tabs: Vec::new(),
+            active_tab: 0,
+            colors,
+        }
+    }
 
-        Self { title, transparent }
+    /// Lists the tab labels instead of the title while more than one tab is open.
+    pub(super) fn with_tabs(mut self, tabs: Vec<String>, active_tab: usize) -> Self {
+        self.tabs = tabs;
+        self.active_tab = active_tab;
+        self
     }
 
     pub(super) fn render(&self, context: &PromptContext<'_>) -> Result<PromptLine, PromptError> {
-        let content = context.message().unwrap_or(&self.title);
+        let content = match context.message() {
+            Some(message) => FooterContent::Text(message),
+            None if self.tabs.len() > 1 => FooterContent::Tabs(&self.tabs, self.active_tab),
+            None => FooterContent::Text(&self.title),
+        };
         build_footer(
             content,
             context.scroll_percentage(),
             context.search_position(),
-            self.transparent,
+            &self.colors,
         )
     }
 }
 
-fn build_footer(
-    content: &str,
+/// Title shown for a document: its explicit title, else its file name.
+pub(super) fn document_label(title: Option<&str>, file: Option<&Path>) -> String {
+    title
+        .map(str::to_owned)
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| {
+            file.and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned())
+                .filter(|name| !name.trim().is_empty())
+        })
+        .unwrap_or_else(|| "stdin".to_string())
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FooterContent<'a> {
+    Text(&'a str),
+    /// Tab labels and the index of the shown tab.
+    Tabs(&'a [String], usize),
+}
+
+impl<'a> From<&'a str> for FooterContent<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::Text(text)
+    }
+}
+
+fn build_footer<'a>(
+    content: impl Into<FooterContent<'a>>,
     percentage: u8,
     search_position: Option<(usize, usize)>,
-    transparent: bool,
+    colors: &PagerColors,
 ) -> Result<PromptLine, PromptError> {
-    if transparent {
-        build_transparent_footer(content, percentage, search_position)
+    let content = content.into();
+    if colors.transparent {
+        build_transparent_footer(content, percentage, search_position, colors)
     } else {
-        build_opaque_footer(content, percentage, search_position)
+        build_opaque_footer(content, percentage, search_position, colors)
+    }
+}
+
+fn add_content(
+    mut footer: PromptLine,
+    content: FooterContent<'_>,
+    style: PromptStyle,
+    inactive_style: PromptStyle,
+) -> Result<PromptLine, PromptError> {
+    match content {
+        FooterContent::Text(text) => Ok(footer.left(PromptSpan::new(format!(" {text}"), style)?)),
+        FooterContent::Tabs(tabs, active) => {
+            for (index, label) in tabs.iter().enumerate() {
+                let tab_style = if index == active {
+                    style.attribute(PromptAttribute::Bold)
+                } else {
+                    inactive_style
+                };
+                footer = footer.left(PromptSpan::new(
+                    format!(" {}:{label}", index + 1),
+                    tab_style,
+                )?);
+            }
+            Ok(footer)
+        }
     }
 }
 
@@ -82,42 +122,38 @@ fn add_progress(
 }
 
 fn build_opaque_footer(
-    content: &str,
+    content: FooterContent<'_>,
     percentage: u8,
     search_position: Option<(usize, usize)>,
+    colors: &PagerColors,
 ) -> Result<PromptLine, PromptError> {
-    let brand_style = PromptStyle::default()
-        .foreground(MAIN_FOREGROUND)
-        .background(ACCENT_BACKGROUND);
-    let main_style = PromptStyle::default()
-        .foreground(MAIN_FOREGROUND)
-        .background(MAIN_BACKGROUND);
-    let progress_style = main_style.foreground(PROGRESS_FOREGROUND);
-    let help_style = main_style.background(ACCENT_BACKGROUND);
+    let accent_style = colors.accent_style();
+    let main_style = colors.footer_style();
+    let progress_style = colors.progress_style();
 
-    let footer = PromptLine::new()
-        .left(PromptSpan::new(BRAND_TEXT, brand_style)?)
-        .left(PromptSpan::new(format!(" {content}"), main_style)?);
+    let footer = PromptLine::new().left(PromptSpan::new(BRAND_TEXT, accent_style)?);
+    let footer = add_content(footer, content, main_style, progress_style)?;
     let footer = add_progress(footer, percentage, search_position, progress_style)?;
 
     Ok(footer
-        .right(PromptSpan::new(HELP_TEXT, help_style)?)
+        .right(PromptSpan::new(HELP_TEXT, accent_style)?)
         .fill_style(main_style)
         .truncation_indicator(PromptSpan::new("…", main_style)?))
 }
 
 fn build_transparent_footer(
-    content: &str,
+    content: FooterContent<'_>,
     percentage: u8,
     search_position: Option<(usize, usize)>,
+    colors: &PagerColors,
 ) -> Result<PromptLine, PromptError> {
-    let main_style = PromptStyle::default().foreground(MAIN_FOREGROUND);
-    let progress_style = main_style.foreground(PROGRESS_FOREGROUND);
+    let main_style = colors.footer_style();
+    let progress_style = colors.progress_style();
 
     let footer = PromptLine::new()
         .left(PromptSpan::new(BRAND_TEXT, main_style)?)
-        .left(PromptSpan::new("|", main_style)?)
-        .left(PromptSpan::new(format!(" {content}"), main_style)?);
+        .left(PromptSpan::new("|", main_style)?);
+    let footer = add_content(footer, content, main_style, progress_style)?;
     let footer = add_progress(footer, percentage, search_position, progress_style)?;
 
     Ok(footer
@@ -129,11 +165,19 @@ fn build_transparent_footer(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::theme::{Color, Theme};
     use unicode_width::UnicodeWidthStr;
 
+    fn transparent() -> PagerColors {
+        PagerColors::from_theme(&Theme {
+            pager_status_bar_transparent: true,
+            ..Theme::default()
+        })
+    }
+
     #[test]
     fn footer_layout_contains_all_sections() {
-        let plain = build_footer("AGENTS.md", 22, None, false)
+        let plain = build_footer("AGENTS.md", 22, None, &PagerColors::default())
             .unwrap()
             .render_plain(80);
 
@@ -144,45 +188,86 @@ mod tests {
 
     #[test]
     fn search_position_appears_before_document_progress() {
-        let footer = build_footer("AGENTS.md", 22, Some((2, 5)), false).unwrap();
+        let footer = build_footer("AGENTS.md", 22, Some((2, 5)), &PagerColors::default()).unwrap();
         let plain = footer.render_plain(80);
         let rendered = footer.render(80);
-        let transparent = build_footer("AGENTS.md", 22, Some((2, 5)), true)
+        let transparent = build_footer("AGENTS.md", 22, Some((2, 5)), &transparent())
             .unwrap()
             .render_plain(80);
 
         assert!(plain.ends_with(" 2/5  22%  ? Help "));
         assert!(transparent.ends_with(" 2/5  22% | ? Help "));
         assert!(
-            rendered.matches("38;2;90;90;90").count() >= 2,
+            rendered.matches("38;5;8").count() >= 2,
             "{}",
             rendered.escape_debug()
         );
     }
 
+    #[test]
+    fn open_tabs_replace_the_title_and_mark_the_shown_one() {
+        let tabs = ["spec.md".to_string(), "adr.md".to_string()];
+        let footer = build_footer(
+            FooterContent::Tabs(&tabs, 1),
+            22,
+            None,
+            &PagerColors::default(),
+        )
+        .unwrap();
+        let plain = footer.render_plain(80);
+
+        assert!(plain.starts_with(" MDV  1:spec.md 2:adr.md"));
+        assert!(
+            footer.render(80).contains("\x1b[1m"),
+            "{}",
+            footer.render(80).escape_debug()
+        );
+    }
+
     #[test]
     fn explicit_title_overrides_the_file_name() {
-        let footer = PagerFooter::new(Some("Help"), Some(Path::new("README.md")), false);
+        let footer = PagerFooter::new(
+            Some("Help"),
+            Some(Path::new("README.md")),
+            PagerColors::default(),
+        );
 
         assert_eq!(footer.title, "Help");
     }
 
     #[test]
     fn footer_uses_expected_colors() {
-        let rendered = build_footer("AGENTS.md", 22, None, false)
+        let rendered = build_footer("AGENTS.md", 22, None, &PagerColors::default())
             .unwrap()
             .render(80);
 
-        assert!(rendered.contains("38;2;125;125;125"));
-        assert!(rendered.contains("38;2;90;90;90"));
-        assert!(rendered.contains("48;2;36;36;36"));
-        assert!(rendered.matches("48;2;50;50;50").count() >= 2);
+        assert!(rendered.contains("38;5;7"));
+        assert!(rendered.contains("38;5;8"));
+        assert!(rendered.contains("48;5;0"));
+        assert!(rendered.matches("48;5;8").count() >= 2);
         assert!(rendered.ends_with("\x1b[0m"));
     }
 
+    #[test]
+    fn footer_takes_its_colors_from_the_theme() {
+        let theme = Theme {
+            pager_footer_background: Color::Rgb { r: 1, g: 2, b: 3 },
+            pager_progress: Color::AnsiValue(100),
+            pager_footer_foreground: Color::Reset,
+            ..Theme::default()
+        };
+        let rendered = build_footer("AGENTS.md", 22, None, &PagerColors::from_theme(&theme))
+            .unwrap()
+            .render(80);
+
+        assert!(rendered.contains("48;2;1;2;3"));
+        assert!(rendered.contains("38;5;100"));
+        assert!(!rendered.contains("38;2;125;125;125"));
+    }
+
     #[test]
     fn transparent_footer_uses_separators_without_background() {
-        let footer = build_footer("AGENTS.md", 22, None, true).unwrap();
+        let footer = build_footer("AGENTS.md", 22, None, &transparent()).unwrap();
         let plain = footer.render_plain(80);
 
         assert!(plain.starts_with(" MDV | AGENTS.md"));
@@ -194,7 +279,7 @@ mod tests {
 
     #[test]
     fn long_unicode_file_name_is_truncated_to_terminal_width() {
-        let plain = build_footer("очень-длинный-файл-📚.md", 7, None, false)
+        let plain = build_footer("очень-длинный-файл-📚.md", 7, None, &PagerColors::default())
             .unwrap()
             .render_plain(32);
 
@@ -204,7 +289,7 @@ mod tests {
 
     #[test]
     fn narrow_footer_never_exceeds_terminal_width() {
-        let footer = build_footer("README.md", 100, None, false).unwrap();
+        let footer = build_footer("README.md", 100, None, &PagerColors::default()).unwrap();
         for columns in 0..20 {
             assert_eq!(footer.render_plain(columns).width(), columns);
         }
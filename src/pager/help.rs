@@ -1,58 +1,106 @@
-use minus::{PromptColor, PromptError, PromptLine, PromptSpan, PromptStyle};
-
-const HELP_FOREGROUND: PromptColor = PromptColor::Rgb {
-    r: 125,
-    g: 125,
-    b: 125,
-};
-const HELP_BACKGROUND: PromptColor = PromptColor::Rgb {
-    r: 27,
-    g: 27,
-    b: 27,
-};
+use super::PagerColors;
+use super::{KeyAction, KeyBindings};
+use minus::{PromptError, PromptLine, PromptSpan, PromptStyle};
+
 const LEFT_COLUMN_WIDTH: usize = 30;
+const LEFT_KEY_WIDTH: usize = 8;
+const RIGHT_KEY_WIDTH: usize = 7;
+
+type HelpEntry = Option<(String, &'static str)>;
 
 pub(super) fn build_help_panel(
+    keys: &KeyBindings,
     editor_enabled: bool,
     reload_enabled: bool,
-    transparent: bool,
+    colors: &PagerColors,
 ) -> Result<Vec<PromptLine>, PromptError> {
-    let style = PromptStyle::default().foreground(HELP_FOREGROUND);
-    let style = if transparent {
-        style
-    } else {
-        style.background(HELP_BACKGROUND)
+    let style = colors.panel_style();
+    let bound = |action: KeyAction, label: &'static str| {
+        let key = keys.label(action);
+        (!key.is_empty()).then_some((key, label))
+    };
+    let fixed = |key: &str, label: &'static str| Some((key.to_string(), label));
+    let close_help = match keys.label(KeyAction::Help) {
+        help if help.is_empty() => "esc".to_string(),
+        help => format!("esc/{help}"),
     };
-    let edit = editor_enabled.then_some("e/E     edit this document");
-    let reload = reload_enabled.then_some("r       reload this document");
-
-    [
-        None,
-        Some(("k/↑      up", Some("g/home  go to top"))),
-        Some(("j/↓      down", Some("G/end   go to bottom"))),
-        Some(("b/pgup   page up", Some("esc/?   close help"))),
-        Some(("f/pgdn   page down", edit)),
-        Some(("u        ½ page up", Some("c       copy contents"))),
-        Some(("d        ½ page down", reload)),
-        Some(("q        quit", Some("/       search"))),
-        None,
-    ]
-    .into_iter()
-    .map(|columns| help_line(columns, style))
-    .collect()
-}
 
-fn help_line(
-    columns: Option<(&str, Option<&str>)>,
-    style: PromptStyle,
-) -> Result<PromptLine, PromptError> {
-    let text = columns.map_or_else(String::new, |(left, right)| {
-        right.map_or_else(
-            || format!("  {left}"),
-            |right| format!("  {left:<LEFT_COLUMN_WIDTH$}{right}"),
-        )
-    });
+    let rows: [(HelpEntry, HelpEntry); 13] = [
+        (
+            bound(KeyAction::ScrollUp, "up"),
+            bound(KeyAction::Top, "go to top"),
+        ),
+        (
+            bound(KeyAction::ScrollDown, "down"),
+            bound(KeyAction::Bottom, "go to bottom"),
+        ),
+        (
+            bound(KeyAction::PageUp, "page up"),
+            Some((close_help, "close help")),
+        ),
+        (
+            bound(KeyAction::PageDown, "page down"),
+            bound(KeyAction::Edit, "edit this document").filter(|_| editor_enabled),
+        ),
+        (
+            bound(KeyAction::HalfPageUp, "½ page up"),
+            bound(KeyAction::Copy, "copy contents"),
+        ),
+        (
+            bound(KeyAction::HalfPageDown, "½ page down"),
+            bound(KeyAction::Reload, "reload this document").filter(|_| reload_enabled),
+        ),
+        (fixed("o", "follow a link"), fixed("[/]", "back/forward")),
+        (fixed("t", "table of contents"), fixed("s", "search source")),
+        (
+            fixed("m<a-zA-Z>", "set a bookmark"),
+            fixed("'<a-zA-Z>", "go to a bookmark"),
+        ),
+        (fixed("O", "link in a new tab"), fixed("x", "close tab")),
+        (fixed("tab", "next tab"), fixed("⇧tab", "previous tab")),
+        (fixed("y", "yank from screen"), None),
+        (
+            bound(KeyAction::Quit, "quit"),
+            bound(KeyAction::Search, "search"),
+        ),
+    ];
+
+    let key_width = |entries: &mut dyn Iterator<Item = &HelpEntry>, minimum: usize| {
+        entries
+            .flatten()
+            .map(|(key, _)| key.chars().count())
+            .fold(minimum, usize::max)
+            + 1
+    };
+    let left_key_width = key_width(&mut rows.iter().map(|(left, _)| left), LEFT_KEY_WIDTH);
+    let right_key_width = key_width(&mut rows.iter().map(|(_, right)| right), RIGHT_KEY_WIDTH);
+    let left_column_width = rows
+        .iter()
+        .filter_map(|(left, _)| left.as_ref())
+        .map(|(_, label)| left_key_width + label.chars().count() + 2)
+        .fold(LEFT_COLUMN_WIDTH, usize::max);
+
+    let mut lines = vec![panel_line(String::new(), style)?];
+    for (left, right) in rows {
+        if left.is_none() && right.is_none() {
+            continue;
+        }
+        let left = left.map_or_else(String::new, |(key, label)| {
+            format!("{key:<left_key_width$}{label}")
+        });
+        let text = match right {
+            Some((key, label)) => {
+                format!("  {left:<left_column_width$}{key:<right_key_width$}{label}")
+            }
+            None => format!("  {left}"),
+        };
+        lines.push(panel_line(text, style)?);
+    }
+    lines.push(panel_line(String::new(), style)?);
+    Ok(lines)
+}
 
+pub(super) fn panel_line(text: String, style: PromptStyle) -> Result<PromptLine, PromptError> {
     Ok(PromptLine::new()
         .left(PromptSpan::new(text, style)?)
         .fill_style(style))
@@ -61,11 +109,17 @@ fn help_line(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::keybindings::{KeyBindingOverrides, KeyScope};
+    use crate::theme::Theme;
     use unicode_width::UnicodeWidthStr;
 
+    fn pager_keys() -> KeyBindings {
+        KeyBindingOverrides::default().resolve(KeyScope::Pager)
+    }
+
     #[test]
     fn help_panel_contains_expected_shortcuts() {
-        let lines = build_help_panel(true, true, false).unwrap();
+        let lines = build_help_panel(&pager_keys(), true, true, &PagerColors::default()).unwrap();
         let rendered_lines = lines
             .iter()
             .map(|line| line.render_plain(100))
@@ -73,20 +127,31 @@ mod tests {
         let text = rendered_lines.join("\n");
 
         for shortcut in [
-            "k/↑      up",
-            "j/↓      down",
-            "b/pgup   page up",
-            "f/pgdn   page down",
-            "u        ½ page up",
-            "d        ½ page down",
-            "g/home  go to top",
-            "G/end   go to bottom",
-            "c       copy contents",
-            "e/E     edit this document",
-            "r       reload this document",
-            "/       search",
-            "q        quit",
-            "esc/?   close help",
+            "k/↑       up",
+            "j/↓       down",
+            "b/pgup    page up",
+            "f/pgdn    page down",
+            "u         ½ page up",
+            "d         ½ page down",
+            "g/home    go to top",
+            "G/end     go to bottom",
+            "c         copy contents",
+            "e/E       edit this document",
+            "r         reload this document",
+            "/         search",
+            "q         quit",
+            "esc/?     close help",
+            "o         follow a link",
+            "[/]       back/forward",
+            "t         table of contents",
+            "s         search source",
+            "m<a-zA-Z> set a bookmark",
+            "'<a-zA-Z> go to a bookmark",
+            "O         link in a new tab",
+            "x         close tab",
+            "tab       next tab",
+            "⇧tab      previous tab",
+            "y         yank from screen",
         ] {
             assert!(text.contains(shortcut), "missing shortcut: {shortcut}");
         }
@@ -95,15 +160,15 @@ mod tests {
         let lowercase = text.to_ascii_lowercase();
         assert!(!lowercase.contains("ctrl+f"));
         assert!(!lowercase.contains("c-f"));
-        assert!(rendered_lines[2].contains("G/end   go to bottom"));
-        assert!(rendered_lines[3].contains("esc/?   close help"));
-        assert!(rendered_lines[4].contains("e/E     edit this document"));
-        assert!(rendered_lines[5].contains("c       copy contents"));
+        assert!(rendered_lines[2].contains("G/end     go to bottom"));
+        assert!(rendered_lines[3].contains("esc/?     close help"));
+        assert!(rendered_lines[4].contains("e/E       edit this document"));
+        assert!(rendered_lines[5].contains("c         copy contents"));
     }
 
     #[test]
     fn help_panel_has_symmetric_vertical_padding() {
-        let lines = build_help_panel(true, true, false).unwrap();
+        let lines = build_help_panel(&pager_keys(), true, true, &PagerColors::default()).unwrap();
 
         assert!(lines.first().unwrap().render_plain(80).trim().is_empty());
         assert!(lines.last().unwrap().render_plain(80).trim().is_empty());
@@ -111,7 +176,7 @@ mod tests {
 
     #[test]
     fn help_panel_omits_unavailable_file_actions() {
-        let lines = build_help_panel(false, false, false).unwrap();
+        let lines = build_help_panel(&pager_keys(), false, false, &PagerColors::default()).unwrap();
         let text = lines
             .iter()
             .map(|line| line.render_plain(100))
@@ -124,7 +189,7 @@ mod tests {
 
     #[test]
     fn help_panel_fills_the_terminal_width() {
-        let lines = build_help_panel(true, true, false).unwrap();
+        let lines = build_help_panel(&pager_keys(), true, true, &PagerColors::default()).unwrap();
 
         for columns in [20, 80, 120] {
             assert!(
@@ -137,18 +202,25 @@ mod tests {
 
     #[test]
     fn help_panel_uses_expected_colors() {
-        let rendered = build_help_panel(true, true, false).unwrap()[1].render(80);
+        let rendered = build_help_panel(&pager_keys(), true, true, &PagerColors::default())
+            .unwrap()[1]
+            .render(80);
 
-        assert!(rendered.contains("38;2;125;125;125"));
-        assert!(rendered.contains("48;2;27;27;27"));
+        assert!(rendered.contains("38;5;7"));
+        assert!(!rendered.contains("\x1b[48;"));
         assert!(rendered.ends_with("\x1b[0m"));
     }
 
     #[test]
     fn transparent_help_panel_does_not_set_a_background() {
-        let rendered = build_help_panel(true, true, true).unwrap()[1].render(80);
-
-        assert!(rendered.contains("38;2;125;125;125"));
+        let transparent = PagerColors::from_theme(&Theme {
+            pager_status_bar_transparent: true,
+            ..Theme::default()
+        });
+        let rendered =
+            build_help_panel(&pager_keys(), true, true, &transparent).unwrap()[1].render(80);
+
+        assert!(rendered.contains("38;5;7"));
         assert!(!rendered.contains("\x1b[48;"));
         assert!(rendered.ends_with("\x1b[0m"));
     }
@@ -1,13 +1,27 @@
 use super::*;
 
+/// Modal panel that receives key presses before the pager does.
+pub(super) enum PagerOverlay {
+    LinkHints(LinkHints),
+    Contents(TableOfContents),
+    SourceSearch(SourceSearchPrompt),
+    Bookmark(BookmarkAction),
+    Yank(YankHints),
+}
+
 pub(super) struct PagerInputClassifier {
     pub(super) default: HashedEventRegister<RandomState>,
     pub(super) editor_requested: Arc<AtomicBool>,
     pub(super) editor_enabled: bool,
+    pub(super) keys: KeyBindings,
+    pub(super) clipboard: Clipboard,
     pub(super) help_panel: Vec<PromptLine>,
     pub(super) pager: Pager,
-    pub(super) document: Arc<RwLock<PagerDocument>>,
-    pub(super) refresh: Option<RefreshCallback>,
+    pub(super) tabs: Arc<Mutex<PagerTabs>>,
+    pub(super) loader: Option<DocumentLoader>,
+    pub(super) overlay: Mutex<Option<PagerOverlay>>,
+    pub(super) source_search: Mutex<SourceSearch>,
+    pub(super) reading: Arc<Mutex<ReadingStore>>,
     pub(super) reload_in_progress: Arc<AtomicBool>,
 }
 
@@ -29,40 +43,284 @@ impl PagerInputClassifier {
         self.set_help_visible(!visible);
     }
 
-    fn copy_contents(&self, selected_text: Option<String>) {
+    fn copy_contents(&self, selected_text: Option<String>) -> Result<()> {
         let pager = self.pager.clone();
-        let document = self.document.clone();
+        let document = self.document()?;
+        let clipboard = self.clipboard.clone();
         thread::spawn(move || {
-            report_operation_result(
+            report_copy_result(
                 &pager,
-                copy_document_contents(&document, selected_text),
+                copy_document_contents(&document, selected_text, &clipboard),
                 "Copied contents",
                 "Failed to copy contents",
             );
         });
+        Ok(())
     }
 
-    fn reload_document(&self) {
-        let Some(refresh) = self.refresh.clone() else {
-            return;
+    fn copy_target(&self, text: String, success_message: &'static str) {
+        let pager = self.pager.clone();
+        let clipboard = self.clipboard.clone();
+        thread::spawn(move || {
+            report_copy_result(
+                &pager,
+                clipboard.copy(&text),
+                success_message,
+                "Failed to copy",
+            );
+        });
+    }
+
+    fn refresh(&self) -> Option<RefreshCallback> {
+        let navigation = self.navigation().ok()?;
+        lock_navigation(&navigation)
+            .ok()?
+            .refresh(self.loader.as_ref())
+    }
+
+    fn reload_document(&self) -> Result<()> {
+        let Some(refresh) = self.refresh() else {
+            return Ok(());
         };
         if self
             .reload_in_progress
             .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
             .is_err()
         {
-            return;
+            return Ok(());
         }
 
         let pager = self.pager.clone();
-        let document = self.document.clone();
+        let document = self.document()?;
         let reload_in_progress = self.reload_in_progress.clone();
         thread::spawn(move || {
-            let result = refresh()
-                .and_then(|refreshed| apply_refreshed_document(&pager, &document, refreshed));
+            let result = refresh().and_then(|refreshed| document.reload(&pager, refreshed));
             reload_in_progress.store(false, Ordering::SeqCst);
             report_operation_result(&pager, result, "Reloaded document", "Failed to reload file");
         });
+        Ok(())
+    }
+
+    /// File of the active tab, if it has one.
+    fn active_file(&self) -> Option<PathBuf> {
+        let navigation = self.navigation().ok()?;
+        lock_navigation(&navigation).ok()?.file.clone()
+    }
+
+    fn report_error(&self, result: Result<()>) {
+        if let Err(error) = result {
+            let _ = self
+                .pager
+                .send_message(single_line_message(&format!("{error:#}")));
+        }
+    }
+
+    fn start_link_hints(&self, state: &PagerState, new_tab: bool) -> Result<()> {
+        let first_line = state.row_to_line(state.upper_mark).unwrap_or(0);
+        let last_line = state
+            .row_to_line(state.upper_mark + state.content_rows().saturating_sub(1))
+            .unwrap_or(first_line);
+        let document = self.document()?;
+        let document = document.read()?;
+        let links = visible_links(&document.map, first_line, last_line);
+        if links.is_empty() {
+            self.pager
+                .send_message_for("No links on screen", STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        }
+
+        let hints = LinkHints::new(links, new_tab);
+        self.pager
+            .set_prompt_panel(build_hint_panel(&hints, &document.colors())?)?;
+        self.set_overlay(Some(PagerOverlay::LinkHints(hints)))
+    }
+
+    fn start_yank(&self, state: &PagerState) -> Result<()> {
+        let first_line = state.row_to_line(state.upper_mark).unwrap_or(0);
+        let last_line = state
+            .row_to_line(state.upper_mark + state.content_rows().saturating_sub(1))
+            .unwrap_or(first_line);
+        let document = self.document()?;
+        let document = document.read()?;
+        let targets = visible_targets(&document, first_line, last_line);
+        if targets.is_empty() {
+            self.pager
+                .send_message_for("Nothing to copy on screen", STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        }
+
+        let hints = YankHints::new(targets);
+        self.pager
+            .set_prompt_panel(build_yank_panel(&hints, &document.colors())?)?;
+        self.set_overlay(Some(PagerOverlay::Yank(hints)))
+    }
+
+    fn start_contents(&self, state: &PagerState) -> Result<()> {
+        let document = self.document()?;
+        let document = document.read()?;
+        if document.map.headings.is_empty() {
+            self.pager
+                .send_message_for("No headings in document", STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        }
+
+        let current_line = state.row_to_line(state.upper_mark).unwrap_or(0);
+        let contents =
+            TableOfContents::new(document.map.headings.clone(), current_line, state.rows);
+        self.pager
+            .set_prompt_panel(build_contents_panel(&contents, &document.colors())?)?;
+        self.set_overlay(Some(PagerOverlay::Contents(contents)))
+    }
+
+    fn start_source_search(&self) -> Result<()> {
+        let prompt = SourceSearchPrompt::new(self.search_options()?);
+        self.pager
+            .set_prompt_panel(build_search_panel(&prompt, &self.colors())?)?;
+        self.set_overlay(Some(PagerOverlay::SourceSearch(prompt)))
+    }
+
+    pub(super) fn set_overlay(&self, overlay: Option<PagerOverlay>) -> Result<()> {
+        *self
+            .overlay
+            .lock()
+            .map_err(|_| anyhow!("Pager overlay lock poisoned"))? = overlay;
+        Ok(())
+    }
+
+    fn colors(&self) -> PagerColors {
+        self.document()
+            .ok()
+            .and_then(|document| document.read().ok().map(|document| document.colors()))
+            .unwrap_or_default()
+    }
+
+    /// Routes key presses to the active overlay, if any.
+    fn overlay_input(
+        &self,
+        event: &minus::input::crossterm_event::Event,
+        state: &PagerState,
+    ) -> Option<Result<()>> {
+        use minus::input::crossterm_event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        let mut overlay = self.overlay.lock().ok()?;
+        let plain_character = match key.code {
+            KeyCode::Char(character)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(character)
+            }
+            _ => None,
+        };
+
+        Some(match overlay.as_mut()? {
+            PagerOverlay::LinkHints(hints) => {
+                match plain_character.map_or(HintInput::NoMatch, |character| hints.push(character))
+                {
+                    HintInput::Pending => build_hint_panel(hints, &self.colors())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|panel| Ok(self.pager.set_prompt_panel(panel)?)),
+                    HintInput::Follow(link) => {
+                        let new_tab = hints.opens_new_tab();
+                        *overlay = None;
+                        drop(overlay);
+                        self.pager
+                            .clear_prompt_panel()
+                            .map_err(anyhow::Error::from)
+                            .and_then(|()| {
+                                if new_tab {
+                                    self.open_link_in_tab(&link, state)
+                                } else {
+                                    self.follow_link(&link, state)
+                                }
+                            })
+                    }
+                    HintInput::NoMatch => {
+                        *overlay = None;
+                        self.pager.clear_prompt_panel().map_err(anyhow::Error::from)
+                    }
+                }
+            }
+            PagerOverlay::Yank(hints) => {
+                match plain_character.map_or(YankInput::NoMatch, |character| hints.push(character))
+                {
+                    YankInput::Pending => build_yank_panel(hints, &self.colors())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|panel| Ok(self.pager.set_prompt_panel(panel)?)),
+                    YankInput::Copy(target) => {
+                        *overlay = None;
+                        drop(overlay);
+                        self.copy_target(target.text, target.kind.copied_message());
+                        self.pager.clear_prompt_panel().map_err(anyhow::Error::from)
+                    }
+                    YankInput::NoMatch => {
+                        *overlay = None;
+                        self.pager.clear_prompt_panel().map_err(anyhow::Error::from)
+                    }
+                }
+            }
+            PagerOverlay::Contents(contents) => {
+                let input = if key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                {
+                    ContentsInput::Pending
+                } else {
+                    contents.push(key.code)
+                };
+                match input {
+                    ContentsInput::Pending => build_contents_panel(contents, &self.colors())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|panel| Ok(self.pager.set_prompt_panel(panel)?)),
+                    ContentsInput::Jump(line) => {
+                        *overlay = None;
+                        drop(overlay);
+                        self.pager
+                            .clear_prompt_panel()
+                            .map_err(anyhow::Error::from)
+                            .and_then(|()| self.jump_to_line(line, state))
+                    }
+                    ContentsInput::Close => {
+                        *overlay = None;
+                        self.pager.clear_prompt_panel().map_err(anyhow::Error::from)
+                    }
+                }
+            }
+            PagerOverlay::SourceSearch(prompt) => match prompt.push(key.code, key.modifiers) {
+                SearchInput::Pending => build_search_panel(prompt, &self.colors())
+                    .map_err(anyhow::Error::from)
+                    .and_then(|panel| Ok(self.pager.set_prompt_panel(panel)?)),
+                SearchInput::Submit => {
+                    let Some(PagerOverlay::SourceSearch(prompt)) = overlay.take() else {
+                        return None;
+                    };
+                    drop(overlay);
+                    self.pager
+                        .clear_prompt_panel()
+                        .map_err(anyhow::Error::from)
+                        .and_then(|()| self.submit_source_search(&prompt, state))
+                }
+                SearchInput::Close => {
+                    *overlay = None;
+                    self.pager.clear_prompt_panel().map_err(anyhow::Error::from)
+                }
+            },
+            PagerOverlay::Bookmark(action) => {
+                let action = *action;
+                *overlay = None;
+                drop(overlay);
+                plain_character
+                    .filter(char::is_ascii_alphabetic)
+                    .map_or(Ok(()), |letter| self.apply_bookmark(action, letter, state))
+            }
+        })
     }
 }
 
@@ -72,8 +330,14 @@ impl InputClassifier for PagerInputClassifier {
         event: minus::input::crossterm_event::Event,
         state: &PagerState,
     ) -> Option<InputEvent> {
+        if let Some(result) = self.overlay_input(&event, state) {
+            self.report_error(result);
+            return None;
+        }
+
         let help_visible = state.prompt_panel_rows() > 0;
-        match help_input_action(&event, help_visible, state.search_is_active()) {
+        let action = key_action(&self.keys, &event);
+        match help_input_action(&event, action, help_visible, state.search_is_active()) {
             HelpInputAction::Toggle => {
                 self.toggle_help(help_visible);
                 return None;
@@ -86,43 +350,130 @@ impl InputClassifier for PagerInputClassifier {
             HelpInputAction::Forward => {}
         }
 
-        if is_copy_key(&event) {
-            self.copy_contents(state.selected_text());
+        if action == Some(KeyAction::Search) {
+            self.clear_source_search();
+        } else if self.source_search_active()
+            && let Some(forward) = source_match_step(&event)
+        {
+            self.report_error(self.show_source_match(state, forward, false));
+            return None;
+        }
+
+        match action {
+            Some(KeyAction::Copy) => {
+                self.report_error(self.copy_contents(state.selected_text()));
+                return None;
+            }
+            Some(KeyAction::Reload) if self.refresh().is_some() => {
+                self.report_error(self.reload_document());
+                return None;
+            }
+            Some(KeyAction::Edit) if self.editor_enabled && self.active_file().is_some() => {
+                self.editor_requested.store(true, Ordering::SeqCst);
+                return Some(InputEvent::Exit);
+            }
+            Some(action) => {
+                if let Some(input) = bound_input(action, state) {
+                    return Some(input);
+                }
+            }
+            None => {}
+        }
+
+        if is_link_hint_key(&event) {
+            self.report_error(self.start_link_hints(state, false));
+            None
+        } else if is_new_tab_link_hint_key(&event) {
+            self.report_error(self.start_link_hints(state, true));
+            None
+        } else if let Some(forward) = tab_step(&event) {
+            self.report_error(self.switch_tab(forward, state));
+            None
+        } else if is_close_tab_key(&event) {
+            self.report_error(self.close_tab(state));
+            None
+        } else if is_yank_key(&event) {
+            self.report_error(self.start_yank(state));
             None
-        } else if self.refresh.is_some() && is_reload_key(&event) {
-            self.reload_document();
+        } else if is_contents_key(&event) {
+            self.report_error(self.start_contents(state));
+            None
+        } else if is_source_search_key(&event) {
+            self.report_error(self.start_source_search());
+            None
+        } else if let Some(action) = bookmark_action(&event) {
+            self.report_error(self.start_bookmark(action));
+            None
+        } else if is_history_back_key(&event) {
+            self.report_error(self.step_history(HistoryStep::Back, state));
+            None
+        } else if is_history_forward_key(&event) {
+            self.report_error(self.step_history(HistoryStep::Forward, state));
             None
-        } else if self.editor_enabled && is_editor_key(&event) {
-            self.editor_requested.store(true, Ordering::SeqCst);
-            Some(InputEvent::Exit)
         } else {
             self.default.classify_input(event, state)
         }
     }
 }
 
-pub(super) fn is_editor_key(event: &minus::input::crossterm_event::Event) -> bool {
-    use minus::input::crossterm_event::{Event, KeyCode, KeyEventKind, KeyModifiers};
-
-    matches!(
-        event,
-        Event::Key(key)
-            if key.kind == KeyEventKind::Press
-                && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
-                && matches!(key.code, KeyCode::Char('E' | 'e' | 'У' | 'у'))
-    )
+/// Minus bindings with the default keys of configurable actions removed, so that only
+/// the configured chords trigger them. Extra aliases such as `space` and `enter` stay.
+pub(super) fn input_register() -> HashedEventRegister<RandomState> {
+    let mut register = HashedEventRegister::default();
+    register.remove_key_events(&[
+        "k", "up", "j", "down", "u", "c-u", "d", "c-d", "b", "pageup", "f", "pagedown", "g",
+        "home", "G", "s-g", "end", "/", "c-f", "?", "q",
+    ]);
+    register
 }
 
-pub(super) fn is_help_key(event: &minus::input::crossterm_event::Event) -> bool {
-    use minus::input::crossterm_event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+/// Configured action of a key press.
+pub(super) fn key_action(
+    keys: &KeyBindings,
+    event: &minus::input::crossterm_event::Event,
+) -> Option<KeyAction> {
+    use minus::input::crossterm_event::{Event, KeyEventKind};
 
-    matches!(
-        event,
-        Event::Key(key)
-            if key.kind == KeyEventKind::Press
-                && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
-                && key.code == KeyCode::Char('?')
-    )
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => keys.action(key),
+        _ => None,
+    }
+}
+
+/// Pager input for the movement, search, and quit actions, following minus's own keys.
+pub(super) fn bound_input(action: KeyAction, state: &PagerState) -> Option<InputEvent> {
+    let count = state.prefix_num.parse::<usize>().ok();
+    let upper_mark = state.upper_mark;
+    Some(match action {
+        KeyAction::ScrollUp => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_sub(count.unwrap_or(1)))
+        }
+        KeyAction::ScrollDown => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_add(count.unwrap_or(1)))
+        }
+        KeyAction::HalfPageUp => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_sub(state.content_rows() / 2))
+        }
+        KeyAction::HalfPageDown => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_add(state.content_rows() / 2))
+        }
+        KeyAction::PageUp => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_sub(state.content_rows()))
+        }
+        KeyAction::PageDown => {
+            InputEvent::UpdateUpperMark(upper_mark.saturating_add(state.content_rows()))
+        }
+        KeyAction::Top => InputEvent::UpdateUpperMark(0),
+        // A count jumps to that line, as with minus's `G`.
+        KeyAction::Bottom => InputEvent::UpdateUpperMark(
+            count
+                .filter(|&line| line > 1)
+                .map_or(usize::MAX - 1, |line| state.line_to_row(line - 1)),
+        ),
+        KeyAction::Search => InputEvent::Search(SearchMode::Forward),
+        KeyAction::Quit => InputEvent::Exit,
+        _ => return None,
+    })
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -135,14 +486,15 @@ pub(super) enum HelpInputAction {
 
 pub(super) fn help_input_action(
     event: &minus::input::crossterm_event::Event,
+    action: Option<KeyAction>,
     help_visible: bool,
     search_active: bool,
 ) -> HelpInputAction {
-    if is_help_key(event) {
+    if action == Some(KeyAction::Help) {
         HelpInputAction::Toggle
     } else if help_visible && is_escape_key(event) && !search_active {
         HelpInputAction::Dismiss
-    } else if help_visible && is_search_key(event) {
+    } else if help_visible && action == Some(KeyAction::Search) {
         HelpInputAction::DismissAndForward
     } else {
         HelpInputAction::Forward
@@ -161,25 +513,80 @@ pub(super) fn is_escape_key(event: &minus::input::crossterm_event::Event) -> boo
     )
 }
 
-pub(super) fn is_search_key(event: &minus::input::crossterm_event::Event) -> bool {
+pub(super) fn is_link_hint_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 'o')
+}
+
+pub(super) fn is_new_tab_link_hint_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 'O')
+}
+
+pub(super) fn is_yank_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 'y')
+}
+
+pub(super) fn is_close_tab_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 'x')
+}
+
+/// Direction of `Tab` and `Shift+Tab`, which switch between pager tabs.
+pub(super) fn tab_step(event: &minus::input::crossterm_event::Event) -> Option<bool> {
     use minus::input::crossterm_event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 
-    is_plain_character_key(event, '/')
-        || matches!(
-            event,
-            Event::Key(key)
-                if key.kind == KeyEventKind::Press
-                    && key.modifiers == KeyModifiers::CONTROL
-                    && key.code == KeyCode::Char('f')
-        )
+    let Event::Key(key) = event else {
+        return None;
+    };
+    if key.kind != KeyEventKind::Press
+        || key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return None;
+    }
+    match key.code {
+        KeyCode::Tab if key.modifiers == KeyModifiers::NONE => Some(true),
+        KeyCode::BackTab => Some(false),
+        _ => None,
+    }
+}
+
+pub(super) fn is_contents_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 't')
+}
+
+pub(super) fn is_source_search_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, 's')
+}
+
+/// Direction of `n` and `p`, which step through source matches while a source search is active.
+pub(super) fn source_match_step(event: &minus::input::crossterm_event::Event) -> Option<bool> {
+    if is_plain_character_key(event, 'n') {
+        Some(true)
+    } else if is_plain_character_key(event, 'p') {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+pub(super) fn bookmark_action(
+    event: &minus::input::crossterm_event::Event,
+) -> Option<BookmarkAction> {
+    if is_plain_character_key(event, 'm') {
+        Some(BookmarkAction::Set)
+    } else if is_plain_character_key(event, '\'') {
+        Some(BookmarkAction::Jump)
+    } else {
+        None
+    }
 }
 
-pub(super) fn is_copy_key(event: &minus::input::crossterm_event::Event) -> bool {
-    is_plain_character_key(event, 'c')
+pub(super) fn is_history_back_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, '[')
 }
 
-pub(super) fn is_reload_key(event: &minus::input::crossterm_event::Event) -> bool {
-    is_plain_character_key(event, 'r')
+pub(super) fn is_history_forward_key(event: &minus::input::crossterm_event::Event) -> bool {
+    is_plain_character_key(event, ']')
 }
 
 pub(super) fn is_plain_character_key(
@@ -0,0 +1,152 @@
+use super::*;
+use crate::utils::{is_markdown_path, percent_decode, url_scheme};
+
+const HINT_KEYS: [char; 26] = [
+    'a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p',
+    'z', 'x', 'c', 'v', 'b', 'n', 'm',
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum LinkTarget {
+    Document {
+        path: PathBuf,
+        fragment: Option<String>,
+    },
+    Fragment(String),
+}
+
+/// Resolves a link destination against the document that contains it.
+pub(super) fn resolve_link(url: &str, current_file: Option<&Path>) -> Result<LinkTarget> {
+    let (location, fragment) = url
+        .split_once('#')
+        .map_or((url, None), |(location, fragment)| {
+            (location, Some(percent_decode(fragment)))
+        });
+    let fragment = fragment.filter(|fragment| !fragment.is_empty());
+    if location.is_empty() {
+        return fragment
+            .map(LinkTarget::Fragment)
+            .ok_or_else(|| anyhow!("Link has no target"));
+    }
+
+    let location = location.split_once('?').map_or(location, |(path, _)| path);
+    let path = match url_scheme(location) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file") => {
+            let path = &location[scheme.len() + 1..];
+            PathBuf::from(percent_decode(path.strip_prefix("//").unwrap_or(path)))
+        }
+        Some(_) => return Err(anyhow!("Not a local link: {url}")),
+        None => PathBuf::from(percent_decode(location)),
+    };
+    let path = match current_file.and_then(Path::parent) {
+        Some(directory) if path.is_relative() => directory.join(path),
+        _ => path,
+    };
+    if !is_markdown_path(&path) {
+        return Err(anyhow!("Not a Markdown document: {}", path.display()));
+    }
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("File not found: {}", path.display()))?;
+    Ok(LinkTarget::Document { path, fragment })
+}
+
+/// Returns the links rendered between two output lines, inclusive.
+pub(super) fn visible_links(
+    map: &DocumentMap,
+    first_line: usize,
+    last_line: usize,
+) -> Vec<MappedLink> {
+    map.links
+        .iter()
+        .filter(|link| (first_line..=last_line).contains(&link.line))
+        .cloned()
+        .collect()
+}
+
+pub(super) fn hint_labels(count: usize) -> Vec<String> {
+    if count <= HINT_KEYS.len() {
+        return HINT_KEYS[..count].iter().map(char::to_string).collect();
+    }
+    HINT_KEYS
+        .iter()
+        .flat_map(|first| {
+            HINT_KEYS
+                .iter()
+                .map(move |second| format!("{first}{second}"))
+        })
+        .take(count)
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum HintInput {
+    Pending,
+    Follow(MappedLink),
+    NoMatch,
+}
+
+/// Link-hint mode: visible links are labelled and followed by typing a label.
+pub(super) struct LinkHints {
+    hints: Vec<(String, MappedLink)>,
+    typed: String,
+    new_tab: bool,
+}
+
+impl LinkHints {
+    /// With `new_tab`, the chosen link opens in a new pager tab instead of the current one.
+    pub(super) fn new(links: Vec<MappedLink>, new_tab: bool) -> Self {
+        Self {
+            hints: hint_labels(links.len()).into_iter().zip(links).collect(),
+            typed: String::new(),
+            new_tab,
+        }
+    }
+
+    pub(super) const fn opens_new_tab(&self) -> bool {
+        self.new_tab
+    }
+
+    pub(super) fn push(&mut self, character: char) -> HintInput {
+        self.typed.push(character.to_ascii_lowercase());
+        if let Some((_, link)) = self.hints.iter().find(|(label, _)| *label == self.typed) {
+            return HintInput::Follow(link.clone());
+        }
+        if self.matching().next().is_some() {
+            HintInput::Pending
+        } else {
+            HintInput::NoMatch
+        }
+    }
+
+    pub(super) fn matching(&self) -> impl Iterator<Item = &(String, MappedLink)> {
+        self.hints
+            .iter()
+            .filter(|(label, _)| label.starts_with(&self.typed))
+    }
+}
+
+pub(super) fn build_hint_panel(
+    hints: &LinkHints,
+    colors: &PagerColors,
+) -> Result<Vec<PromptLine>, PromptError> {
+    let style = colors.panel_style();
+    let label_width = hints
+        .hints
+        .first()
+        .map_or(1, |(label, _)| label.chars().count());
+    let mut lines = vec![panel_line(String::new(), style)?];
+    for (label, link) in hints.matching() {
+        let text = if link.text.is_empty() || link.text == link.url {
+            link.url.clone()
+        } else {
+            format!("{}  {}", link.text, link.url)
+        };
+        lines.push(panel_line(
+            format!("  {label:<label_width$}  {}", single_line_message(&text)),
+            style,
+        )?);
+    }
+    lines.push(panel_line(String::new(), style)?);
+    Ok(lines)
+}
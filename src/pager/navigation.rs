@@ -0,0 +1,241 @@
+use super::*;
+use crate::reading::ReadingPosition;
+
+/// Viewport position inside a document, recorded in the navigation history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct PagerLocation {
+    pub(super) file: Option<PathBuf>,
+    pub(super) line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum HistoryStep {
+    Visit,
+    Back,
+    Forward,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct PagerHistory {
+    back: Vec<PagerLocation>,
+    forward: Vec<PagerLocation>,
+}
+
+impl PagerHistory {
+    pub(super) fn peek(&self, step: HistoryStep) -> Option<&PagerLocation> {
+        match step {
+            HistoryStep::Visit => None,
+            HistoryStep::Back => self.back.last(),
+            HistoryStep::Forward => self.forward.last(),
+        }
+    }
+
+    /// Records `current` once the destination of `step` has been opened.
+    pub(super) fn commit(&mut self, step: HistoryStep, current: PagerLocation) {
+        match step {
+            HistoryStep::Visit => {
+                self.back.push(current);
+                self.forward.clear();
+            }
+            HistoryStep::Back => {
+                self.back.pop();
+                self.forward.push(current);
+            }
+            HistoryStep::Forward => {
+                self.forward.pop();
+                self.back.push(current);
+            }
+        }
+    }
+}
+
+/// Document currently shown by the pager, shared with the input classifier.
+pub(super) struct PagerNavigation {
+    pub(super) file: Option<PathBuf>,
+    pub(super) history: PagerHistory,
+    watcher: Option<ActiveWatcher>,
+}
+
+impl PagerNavigation {
+    pub(super) fn new(file: Option<PathBuf>) -> Self {
+        Self {
+            file: file.map(|file| comparable_path(&file).unwrap_or(file)),
+            history: PagerHistory::default(),
+            watcher: None,
+        }
+    }
+
+    pub(super) fn refresh(&self, loader: Option<&DocumentLoader>) -> Option<RefreshCallback> {
+        let loader = loader?.clone();
+        let file = self.file.clone()?;
+        Some(Arc::new(move || loader(&file)))
+    }
+
+    pub(super) fn start_watcher(
+        &mut self,
+        pager: &Pager,
+        loader: Option<&DocumentLoader>,
+        document: &SharedDocument,
+    ) -> Result<()> {
+        self.watcher = None;
+        if let (Some(file), Some(refresh)) = (&self.file, self.refresh(loader)) {
+            self.watcher = Some(ActiveWatcher::start(
+                file,
+                pager.clone(),
+                refresh,
+                document.clone(),
+            )?);
+        }
+        Ok(())
+    }
+
+    pub(super) fn stop_watcher(&mut self) {
+        self.watcher = None;
+    }
+}
+
+pub(super) fn lock_navigation(
+    navigation: &Mutex<PagerNavigation>,
+) -> Result<std::sync::MutexGuard<'_, PagerNavigation>> {
+    navigation
+        .lock()
+        .map_err(|_| anyhow!("Pager navigation lock poisoned"))
+}
+
+pub(super) enum Destination {
+    Line(usize),
+    Fragment(String),
+    /// Where the document was left the last time it was read, or its top.
+    Resume(Option<ReadingPosition>),
+}
+
+impl PagerInputClassifier {
+    pub(super) fn current_location(&self, state: &PagerState) -> Result<PagerLocation> {
+        let navigation = self.navigation()?;
+        Ok(PagerLocation {
+            file: lock_navigation(&navigation)?.file.clone(),
+            line: state.row_to_line(state.upper_mark).unwrap_or(0),
+        })
+    }
+
+    pub(super) fn follow_link(&self, link: &MappedLink, state: &PagerState) -> Result<()> {
+        let current = self.current_location(state)?;
+        match resolve_link(&link.url, current.file.as_deref())? {
+            LinkTarget::Fragment(fragment) => self.navigate(
+                current.file.clone(),
+                Destination::Fragment(fragment),
+                HistoryStep::Visit,
+                current,
+                state,
+            ),
+            LinkTarget::Document { path, fragment } => {
+                let destination = match fragment {
+                    Some(fragment) => Destination::Fragment(fragment),
+                    None => Destination::Resume(
+                        lock_reading(&self.reading)?
+                            .record(Some(&path))
+                            .and_then(|record| record.position.clone()),
+                    ),
+                };
+                self.navigate(Some(path), destination, HistoryStep::Visit, current, state)
+            }
+        }
+    }
+
+    /// Scrolls the current document to `line`, recording the jump in the history.
+    pub(super) fn jump_to_line(&self, line: usize, state: &PagerState) -> Result<()> {
+        let current = self.current_location(state)?;
+        self.navigate(
+            current.file.clone(),
+            Destination::Line(line),
+            HistoryStep::Visit,
+            current,
+            state,
+        )
+    }
+
+    pub(super) fn step_history(&self, step: HistoryStep, state: &PagerState) -> Result<()> {
+        let current = self.current_location(state)?;
+        let navigation = self.navigation()?;
+        let destination = lock_navigation(&navigation)?.history.peek(step).cloned();
+        let Some(destination) = destination else {
+            let edge = if step == HistoryStep::Back {
+                "No previous document"
+            } else {
+                "No next document"
+            };
+            self.pager.send_message_for(edge, STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        };
+        self.navigate(
+            destination.file,
+            Destination::Line(destination.line),
+            step,
+            current,
+            state,
+        )
+    }
+
+    fn navigate(
+        &self,
+        file: Option<PathBuf>,
+        destination: Destination,
+        step: HistoryStep,
+        current: PagerLocation,
+        state: &PagerState,
+    ) -> Result<()> {
+        let (document, navigation) = (self.document()?, self.navigation()?);
+        if file == current.file {
+            let line = destination_line(&document.read()?.map, &destination)?;
+            self.pager.scroll_to_line(line)?;
+            lock_navigation(&navigation)?.history.commit(step, current);
+            return Ok(());
+        }
+
+        let (Some(path), Some(loader)) = (file, self.loader.clone()) else {
+            return Err(anyhow!("Linked documents cannot be opened here"));
+        };
+        if let Err(error) = self.remember_position(state) {
+            log::warn!("Failed to save the reading position: {error:#}");
+        }
+        let pager = self.pager.clone();
+        let tabs = self.tabs.clone();
+        thread::spawn(move || {
+            let result = loader(&path).and_then(|loaded| {
+                let line = destination_line(&loaded.map, &destination);
+                let mut navigation = lock_navigation(&navigation)?;
+                navigation.stop_watcher();
+                document.replace(&pager, loaded)?;
+                if document.is_shown() {
+                    pager.scroll_to_line(line.as_ref().copied().unwrap_or(0))?;
+                }
+                navigation.history.commit(step, current);
+                navigation.file = Some(path);
+                navigation.start_watcher(&pager, Some(&loader), &document)?;
+                drop(navigation);
+                lock_tabs(&tabs)?.update_footer(&pager)?;
+                Ok(line.err())
+            });
+            let message = match result {
+                Ok(None) => return,
+                Ok(Some(warning)) => format!("{warning:#}"),
+                Err(error) => format!("Failed to open link: {error:#}"),
+            };
+            let _ = pager.send_message(single_line_message(&message));
+        });
+        Ok(())
+    }
+}
+
+pub(super) fn destination_line(map: &DocumentMap, destination: &Destination) -> Result<usize> {
+    match destination {
+        Destination::Line(line) => Ok(*line),
+        Destination::Resume(position) => Ok(position
+            .as_ref()
+            .map_or(0, |position| position.resolve(map))),
+        Destination::Fragment(fragment) => map
+            .heading_by_slug(fragment)
+            .map(|heading| heading.line)
+            .ok_or_else(|| anyhow!("Heading not found: #{fragment}")),
+    }
+}
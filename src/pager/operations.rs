@@ -1,48 +1,34 @@
 use super::*;
 
-pub(super) fn apply_refreshed_document(
-    pager: &Pager,
-    document: &RwLock<PagerDocument>,
-    refreshed: PagerDocument,
-) -> Result<()> {
-    let output = refreshed.output.clone();
-    replace_document(document, refreshed)?;
-    pager.set_text(output)?;
-    Ok(())
-}
-
-pub(super) fn replace_document(
-    document: &RwLock<PagerDocument>,
-    refreshed: PagerDocument,
-) -> Result<()> {
-    *document
-        .write()
-        .map_err(|_| anyhow!("Pager document lock poisoned"))? = refreshed;
-    Ok(())
-}
-
 pub(super) fn copy_document_contents(
-    document: &RwLock<PagerDocument>,
+    document: &SharedDocument,
     selected_text: Option<String>,
-) -> Result<()> {
-    let text = clipboard_text(document, selected_text)?;
-    let mut clipboard = arboard::Clipboard::new().context("Failed to access system clipboard")?;
-    clipboard
-        .set_text(text)
-        .context("Failed to write system clipboard")
+    clipboard: &Clipboard,
+) -> Result<ClipboardBackend> {
+    clipboard.copy(&clipboard_text(document, selected_text)?)
+}
+
+/// Reports a copy with the backend that took the text.
+pub(super) fn report_copy_result(
+    pager: &Pager,
+    result: Result<ClipboardBackend>,
+    success_message: &str,
+    failure_message: &str,
+) {
+    let success_message = match &result {
+        Ok(backend) => format!("{success_message} via {backend}"),
+        Err(_) => String::new(),
+    };
+    report_operation_result(pager, result.map(drop), &success_message, failure_message);
 }
 
 pub(super) fn clipboard_text(
-    document: &RwLock<PagerDocument>,
+    document: &SharedDocument,
     selected_text: Option<String>,
 ) -> Result<String> {
     match selected_text {
         Some(text) => Ok(text),
-        None => Ok(document
-            .read()
-            .map_err(|_| anyhow!("Pager document lock poisoned"))?
-            .source
-            .clone()),
+        None => Ok(document.read()?.source.clone()),
     }
 }
 
@@ -1,75 +1,154 @@
 use super::*;
 
+/// Pages `documents` as tabs, starting with the one at `active`. A `source_line`, counted
+/// from 1, opens that tab there instead of at its saved position.
 pub(crate) fn page(
-    document: PagerDocument,
-    file: Option<PathBuf>,
-    refresh: Option<RefreshCallback>,
+    documents: Vec<(PagerDocument, Option<PathBuf>)>,
+    active: usize,
+    source_line: Option<usize>,
+    loader: Option<DocumentLoader>,
     screen: PagerScreen,
+    keys: KeyBindings,
+    clipboard: Clipboard,
 ) -> Result<()> {
     let editor = EditorCommand::from_env();
-    let editor_enabled = !matches!(editor, Ok(None)) && file.is_some();
-    let document = Arc::new(RwLock::new(document));
+    let reading = Arc::new(Mutex::new(ReadingStore::load()));
+    let tabs = documents
+        .into_iter()
+        .map(|(document, file)| {
+            let mut tab = PagerTab::new(document, file, 0);
+            let file = lock_navigation(&tab.navigation)?.file.clone();
+            tab.line =
+                saved_line(&reading, file.as_deref(), &tab.document.read()?.map)?.unwrap_or(0);
+            Ok(tab)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if tabs.is_empty() {
+        return Ok(());
+    }
+    let mut start_line = match (source_line, tabs.get(active)) {
+        (Some(line), Some(tab)) => tab
+            .document
+            .read()?
+            .map
+            .rendered_lines_for_source(line)
+            .map(|lines| lines.start),
+        _ => None,
+    };
+    let tabs = Arc::new(Mutex::new(PagerTabs::new(tabs, active)));
     let mut pending_message = None;
 
     loop {
         let editor_requested = Arc::new(AtomicBool::new(false));
         let pager = Pager::new();
-        let (output, title, status_bar_transparent) = {
-            let document = document
-                .read()
-                .map_err(|_| anyhow!("Pager document lock poisoned"))?;
-            (
-                document.output.clone(),
-                document.title.clone(),
-                document.status_bar_transparent(),
-            )
+        let (colors, has_file, resume_line) = {
+            let tabs = lock_tabs(&tabs)?;
+            let has_file = tabs
+                .iter()
+                .map(|tab| Ok(lock_navigation(&tab.navigation)?.file.is_some()))
+                .collect::<Result<Vec<_>>>()?
+                .contains(&true);
+            let active = tabs.active();
+            active.document.show(&pager)?;
+            tabs.update_footer(&pager)?;
+            let file = lock_navigation(&active.navigation)?.file.clone();
+            let document = active.document.read()?;
+            let resume_line = match start_line.take() {
+                Some(line) => line,
+                None => {
+                    saved_line(&reading, file.as_deref(), &document.map)?.unwrap_or(active.line)
+                }
+            };
+            (document.colors(), has_file, resume_line)
         };
-        let help_panel =
-            build_help_panel(editor_enabled, refresh.is_some(), status_bar_transparent)?;
-        let footer = PagerFooter::new(title.as_deref(), file.as_deref(), status_bar_transparent);
-        pager.set_text(output)?;
-        pager.set_prompt_renderer(move |context| footer.render(context))?;
+        let editor_enabled = !matches!(editor, Ok(None)) && has_file;
+        let reload_enabled = loader.is_some() && has_file;
+        let help_panel = build_help_panel(&keys, editor_enabled, reload_enabled, &colors)?;
         pager.set_search_prompt("Find: ")?;
+        if let Some(search_colors) = colors.search_colors() {
+            pager.set_search_colors(search_colors)?;
+        }
         pager.remove_hook(Hook::PostPagerExit, 1)?;
         pager.set_input_classifier(Box::new(PagerInputClassifier {
-            default: HashedEventRegister::default(),
+            default: input_register(),
             editor_requested: editor_requested.clone(),
             editor_enabled,
+            keys: keys.clone(),
+            clipboard: clipboard.clone(),
             help_panel: help_panel.clone(),
             pager: pager.clone(),
-            document: document.clone(),
-            refresh: refresh.clone(),
+            tabs: tabs.clone(),
+            loader: loader.clone(),
+            overlay: Mutex::new(None),
+            source_search: Mutex::new(SourceSearch::default()),
+            reading: reading.clone(),
             reload_in_progress: Arc::new(AtomicBool::new(false)),
         }))?;
+        if resume_line > 0 {
+            let resume = pager.clone();
+            pager.add_hook(
+                Hook::PostPagerStart,
+                0,
+                Box::new(move |_| {
+                    let _ = resume.scroll_to_line(resume_line);
+                }),
+            )?;
+        }
+        pager.add_hook(Hook::PrePagerExit, 0, {
+            let reading = reading.clone();
+            let tabs = tabs.clone();
+            Box::new(move |state| {
+                let result = lock_tabs(&tabs).and_then(|mut tabs| {
+                    let (line, progress) = viewport_progress(state);
+                    let active = tabs.active_mut();
+                    active.line = line;
+                    let navigation = lock_navigation(&active.navigation)?;
+                    save_position(
+                        &reading,
+                        navigation.file.as_deref(),
+                        &active.document.read()?.map,
+                        (line, progress),
+                    )
+                });
+                if let Err(error) = result {
+                    log::warn!("Failed to save the reading position: {error:#}");
+                }
+            })
+        })?;
         if let Some(message) = pending_message.take() {
             pager.send_message(message)?;
         }
 
-        let watcher = match (&file, &refresh) {
-            (Some(path), Some(refresh)) => Some(ActiveWatcher::start(
-                path,
-                pager.clone(),
-                refresh.clone(),
-                document.clone(),
-            )?),
-            _ => None,
-        };
+        for tab in lock_tabs(&tabs)?.iter() {
+            lock_navigation(&tab.navigation)?.start_watcher(
+                &pager,
+                loader.as_ref(),
+                &tab.document,
+            )?;
+        }
 
-        match screen {
-            PagerScreen::Alternate => minus::dynamic_paging(pager)?,
-            PagerScreen::InPlace => minus::dynamic_paging_in_place(pager)?,
+        let paging_result = match screen {
+            PagerScreen::Alternate => minus::dynamic_paging(pager),
+            PagerScreen::InPlace => minus::dynamic_paging_in_place(pager),
+        };
+        for tab in lock_tabs(&tabs)?.iter() {
+            lock_navigation(&tab.navigation)?.stop_watcher();
         }
-        drop(watcher);
+        paging_result?;
 
         if !editor_requested.load(Ordering::SeqCst) {
             return Ok(());
         }
 
-        let Some(file) = &file else {
+        let document = lock_tabs(&tabs)?.active().document.clone();
+        let Some(file) = lock_navigation(&lock_tabs(&tabs)?.active().navigation)?
+            .file
+            .clone()
+        else {
             return Ok(());
         };
         let editor_opened = match &editor {
-            Ok(Some(editor)) => match editor.open(file) {
+            Ok(Some(editor)) => match editor.open(&file) {
                 Ok(()) => true,
                 Err(error) => {
                     pending_message = Some(single_line_message(&format!(
@@ -87,9 +166,9 @@ pub(crate) fn page(
             Ok(None) => return Ok(()),
         };
 
-        if editor_opened && let Some(refresh) = &refresh {
-            match refresh() {
-                Ok(refreshed) => replace_document(&document, refreshed)?,
+        if editor_opened && let Some(loader) = &loader {
+            match loader(&file) {
+                Ok(refreshed) => document.replace_hidden(refreshed)?,
                 Err(error) => {
                     pending_message = Some(single_line_message(&format!(
                         "Failed to refresh file: {error:#}"
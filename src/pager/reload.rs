@@ -0,0 +1,108 @@
+use super::*;
+use crate::diff::{DiffRun, diff, follow};
+use crate::reading::ReadingPosition;
+use regex::regex;
+use std::ops::Range;
+
+/// How long the lines changed by a reload stay marked in the gutter.
+pub(super) const CHANGE_MARK_DURATION: Duration = Duration::from_secs(2);
+
+/// Line runs of the Markdown source before and after a reload.
+pub(super) fn source_changes(old: &str, new: &str) -> Vec<DiffRun> {
+    diff(
+        &old.lines().collect::<Vec<_>>(),
+        &new.lines().collect::<Vec<_>>(),
+    )
+}
+
+/// Rendered line of the reloaded document showing what `old` showed at `line`. The view
+/// follows the source line that starts there, and falls back to the closest heading when
+/// the documents carry no source lines.
+pub(super) fn anchored_line(
+    old: &DocumentMap,
+    new: &DocumentMap,
+    runs: &[DiffRun],
+    line: usize,
+) -> usize {
+    let start = old
+        .source_lines
+        .iter()
+        .take(line + 1)
+        .enumerate()
+        .rev()
+        .find_map(|(index, source)| source.map(|source| (index, source)));
+    if let Some((start, source)) = start
+        && let Some(lines) =
+            new.rendered_lines_for_source(follow(runs, source.saturating_sub(1)) + 1)
+    {
+        return (lines.start + (line - start)).min(lines.end.max(lines.start + 1) - 1);
+    }
+    ReadingPosition::capture(old, line).resolve(new)
+}
+
+/// Rendered lines of `map` whose source block was added or edited. A deletion marks the
+/// line that follows it.
+pub(super) fn changed_lines(map: &DocumentMap, runs: &[DiffRun]) -> Vec<bool> {
+    let changed: Vec<Range<usize>> = runs
+        .iter()
+        .filter_map(|run| match run {
+            DiffRun::Changed { new, .. } => Some(new.start..new.end.max(new.start + 1)),
+            DiffRun::Same { .. } => None,
+        })
+        .collect();
+    if changed.is_empty() {
+        return vec![false; map.source_lines.len()];
+    }
+
+    let mut next_sources = vec![None; map.source_lines.len()];
+    let mut next = None;
+    for (index, source) in map.source_lines.iter().enumerate().rev() {
+        next_sources[index] = next;
+        if source.is_some() {
+            next = *source;
+        }
+    }
+
+    let mut block_changed = false;
+    map.source_lines
+        .iter()
+        .zip(next_sources)
+        .map(|(source, next)| {
+            if let Some(source) = source {
+                let start = source.saturating_sub(1);
+                let end = next
+                    .map_or(usize::MAX, |next| next.saturating_sub(1))
+                    .max(start + 1);
+                block_changed = changed
+                    .iter()
+                    .any(|range| range.start < end && start < range.end);
+            }
+            block_changed
+        })
+        .collect()
+}
+
+/// Draws `marker` over the left margin of every line flagged in `changed`. Lines without
+/// a margin stay as they are, so marking never moves or wraps their content.
+pub(super) fn mark_lines(output: &str, changed: &[bool], marker: &str) -> String {
+    let styles = regex!(r"^(?:\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\))*");
+    let mut marked = String::with_capacity(output.len());
+    for (line, &changed) in output
+        .split_inclusive('\n')
+        .zip(changed.iter().chain(std::iter::repeat(&false)))
+    {
+        if !changed {
+            marked.push_str(line);
+            continue;
+        }
+        let prefix = styles.find(line).map_or(0, |styles| styles.end());
+        let Some(rest) = line[prefix..].strip_prefix(' ') else {
+            marked.push_str(line);
+            continue;
+        };
+        marked.push_str(marker);
+        marked.push_str(&line[..prefix]);
+        marked.push_str(rest);
+    }
+    marked
+}
@@ -0,0 +1,282 @@
+use super::*;
+use crate::utils::strip_terminal_sequences;
+use minus::input::crossterm_event::{KeyCode, KeyModifiers};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum CaseMode {
+    /// Ignores case unless the query contains an uppercase letter.
+    #[default]
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+
+impl CaseMode {
+    const fn next(self) -> Self {
+        match self {
+            Self::Smart => Self::Sensitive,
+            Self::Sensitive => Self::Insensitive,
+            Self::Insensitive => Self::Smart,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Smart => "smart",
+            Self::Sensitive => "sensitive",
+            Self::Insensitive => "ignore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct SearchOptions {
+    pub(super) regex: bool,
+    pub(super) case: CaseMode,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum SearchInput {
+    Pending,
+    Submit,
+    Close,
+}
+
+/// Query prompt shown by the `s` key.
+pub(super) struct SourceSearchPrompt {
+    pub(super) query: String,
+    pub(super) options: SearchOptions,
+}
+
+impl SourceSearchPrompt {
+    pub(super) const fn new(options: SearchOptions) -> Self {
+        Self {
+            query: String::new(),
+            options,
+        }
+    }
+
+    pub(super) fn push(&mut self, code: KeyCode, modifiers: KeyModifiers) -> SearchInput {
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            match code {
+                KeyCode::Char('r') => self.options.regex = !self.options.regex,
+                KeyCode::Char('t') => self.options.case = self.options.case.next(),
+                KeyCode::Char('c') => return SearchInput::Close,
+                _ => {}
+            }
+            return SearchInput::Pending;
+        }
+        match code {
+            KeyCode::Char(character) if !modifiers.contains(KeyModifiers::ALT) => {
+                self.query.push(character);
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Enter if self.query.is_empty() => return SearchInput::Close,
+            KeyCode::Enter => return SearchInput::Submit,
+            KeyCode::Esc => return SearchInput::Close,
+            _ => {}
+        }
+        SearchInput::Pending
+    }
+}
+
+pub(super) fn build_search_panel(
+    prompt: &SourceSearchPrompt,
+    colors: &PagerColors,
+) -> Result<Vec<PromptLine>, PromptError> {
+    let style = colors.panel_style();
+    let query: String = prompt
+        .query
+        .chars()
+        .filter(|character| !character.is_control())
+        .collect();
+    let regex = if prompt.options.regex { "on" } else { "off" };
+    Ok(vec![
+        panel_line(String::new(), style)?,
+        panel_line(format!("  Source: {query}█"), style)?,
+        panel_line(
+            format!(
+                "  ctrl+r regex: {regex}   ctrl+t case: {}   enter search   esc cancel",
+                prompt.options.case.label()
+            ),
+            style,
+        )?,
+        panel_line(String::new(), style)?,
+    ])
+}
+
+/// Compiles a query; literal queries match their text exactly.
+pub(super) fn compile_query(query: &str, options: SearchOptions) -> Result<Regex> {
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let ignore_case = match options.case {
+        CaseMode::Smart => !query.chars().any(char::is_uppercase),
+        CaseMode::Sensitive => false,
+        CaseMode::Insensitive => true,
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(ignore_case)
+        .multi_line(true)
+        .build()
+        .map_err(|error| anyhow!("Invalid pattern: {error}"))
+}
+
+/// Rendered lines showing each match of `regex` in the Markdown source, in order.
+///
+/// A match lands on the rendered lines of its source line; when the matched text is
+/// visible on one of them, that line is used, otherwise the first one.
+pub(super) fn source_match_lines(document: &PagerDocument, regex: &Regex) -> Vec<usize> {
+    let rendered: Vec<String> = document
+        .output
+        .split('\n')
+        .map(|line| strip_terminal_sequences(line).to_lowercase())
+        .collect();
+    let mut source_line = 1;
+    let mut counted = 0;
+    let mut lines = Vec::new();
+    for found in regex.find_iter(&document.source) {
+        if found.is_empty() {
+            continue;
+        }
+        source_line += document.source[counted..found.start()]
+            .matches('\n')
+            .count();
+        counted = found.start();
+        let Some(range) = document.map.rendered_lines_for_source(source_line) else {
+            continue;
+        };
+        let text = found
+            .as_str()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let line = range
+            .clone()
+            .find(|&line| rendered.get(line).is_some_and(|row| row.contains(&text)))
+            .unwrap_or(range.start);
+        lines.push(line);
+    }
+    lines.sort_unstable();
+    lines.dedup();
+    lines
+}
+
+/// Index of the match to show from `current`: the first one at or after it when
+/// `include_current` is set, otherwise the next one in the given direction, wrapping
+/// around the document.
+pub(super) fn step_match(
+    lines: &[usize],
+    current: usize,
+    forward: bool,
+    include_current: bool,
+) -> Option<usize> {
+    if lines.is_empty() {
+        return None;
+    }
+    Some(if forward {
+        let next = if include_current {
+            lines.partition_point(|&line| line < current)
+        } else {
+            lines.partition_point(|&line| line <= current)
+        };
+        if next == lines.len() { 0 } else { next }
+    } else {
+        lines
+            .partition_point(|&line| line < current)
+            .checked_sub(1)
+            .unwrap_or(lines.len() - 1)
+    })
+}
+
+impl PagerInputClassifier {
+    pub(super) fn search_options(&self) -> Result<SearchOptions> {
+        Ok(self
+            .source_search
+            .lock()
+            .map_err(|_| anyhow!("Pager search lock poisoned"))?
+            .options)
+    }
+
+    /// Runs the submitted query and shows the first match from the top of the viewport.
+    pub(super) fn submit_source_search(
+        &self,
+        prompt: &SourceSearchPrompt,
+        state: &PagerState,
+    ) -> Result<()> {
+        let mut search = self
+            .source_search
+            .lock()
+            .map_err(|_| anyhow!("Pager search lock poisoned"))?;
+        search.options = prompt.options;
+        search.active = None;
+        let regex = compile_query(&prompt.query, prompt.options)?;
+        search.active = Some((prompt.query.clone(), regex));
+        drop(search);
+        self.show_source_match(state, true, true)
+    }
+
+    /// Clears the source search so `n` and `p` reach the pager's own search again.
+    pub(super) fn clear_source_search(&self) {
+        if let Ok(mut search) = self.source_search.lock() {
+            search.active = None;
+        }
+    }
+
+    pub(super) fn source_search_active(&self) -> bool {
+        self.source_search
+            .lock()
+            .is_ok_and(|search| search.active.is_some())
+    }
+
+    /// Scrolls to the next source match. The search runs again on every step, so it
+    /// follows the document through reloads.
+    pub(super) fn show_source_match(
+        &self,
+        state: &PagerState,
+        forward: bool,
+        include_current: bool,
+    ) -> Result<()> {
+        let Some((query, regex)) = self
+            .source_search
+            .lock()
+            .map_err(|_| anyhow!("Pager search lock poisoned"))?
+            .active
+            .clone()
+        else {
+            return Ok(());
+        };
+        let lines = {
+            let document = self.document()?;
+            let document = document.read()?;
+            source_match_lines(&document, &regex)
+        };
+        let current = state.row_to_line(state.upper_mark).unwrap_or(0);
+        let Some(index) = step_match(&lines, current, forward, include_current) else {
+            self.pager.send_message_for(
+                format!("Pattern not found: {query}"),
+                STATUS_MESSAGE_TIMEOUT,
+            )?;
+            return Ok(());
+        };
+        self.pager.scroll_to_line(lines[index])?;
+        self.pager.send_message_for(
+            format!("Match {} of {}", index + 1, lines.len()),
+            STATUS_MESSAGE_TIMEOUT,
+        )?;
+        Ok(())
+    }
+}
+
+/// Source search settings and the last submitted query of a pager session.
+#[derive(Debug, Default)]
+pub(super) struct SourceSearch {
+    options: SearchOptions,
+    active: Option<(String, Regex)>,
+}
@@ -0,0 +1,250 @@
+use super::*;
+
+/// Document open in a pager tab, with its own history and file watcher.
+pub(super) struct PagerTab {
+    pub(super) document: SharedDocument,
+    pub(super) navigation: Arc<Mutex<PagerNavigation>>,
+    /// Top line of the viewport, restored when the tab is shown again.
+    pub(super) line: usize,
+}
+
+impl PagerTab {
+    pub(super) fn new(document: PagerDocument, file: Option<PathBuf>, line: usize) -> Self {
+        Self {
+            document: SharedDocument::new(document),
+            navigation: Arc::new(Mutex::new(PagerNavigation::new(file))),
+            line,
+        }
+    }
+
+    fn label(&self) -> Result<String> {
+        let navigation = lock_navigation(&self.navigation)?;
+        let document = self.document.read()?;
+        Ok(document_label(
+            document.title.as_deref(),
+            navigation.file.as_deref(),
+        ))
+    }
+}
+
+/// Tabs of the pager; exactly one of them is shown at a time.
+pub(super) struct PagerTabs {
+    tabs: Vec<PagerTab>,
+    active: usize,
+}
+
+impl PagerTabs {
+    pub(super) fn new(tabs: Vec<PagerTab>, active: usize) -> Self {
+        let active = active.min(tabs.len().saturating_sub(1));
+        Self { tabs, active }
+    }
+
+    pub(super) fn active(&self) -> &PagerTab {
+        &self.tabs[self.active]
+    }
+
+    pub(super) fn active_mut(&mut self) -> &mut PagerTab {
+        &mut self.tabs[self.active]
+    }
+
+    pub(super) const fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &PagerTab> {
+        self.tabs.iter()
+    }
+
+    /// Moves to the next or previous tab, wrapping around at either end.
+    pub(super) fn step(&mut self, forward: bool) {
+        let len = self.tabs.len();
+        self.active = if forward {
+            (self.active + 1) % len
+        } else {
+            (self.active + len - 1) % len
+        };
+    }
+
+    /// Adds a tab after the last one and selects it.
+    pub(super) fn push(&mut self, tab: PagerTab) {
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Removes the active tab and selects its right neighbour; the last tab stays open.
+    pub(super) fn close_active(&mut self) -> Option<PagerTab> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+        let closed = self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len() - 1);
+        Some(closed)
+    }
+
+    pub(super) fn footer(&self) -> Result<PagerFooter> {
+        let footer = {
+            let active = self.active();
+            let navigation = lock_navigation(&active.navigation)?;
+            let document = active.document.read()?;
+            PagerFooter::new(
+                document.title.as_deref(),
+                navigation.file.as_deref(),
+                document.colors(),
+            )
+        };
+        if self.tabs.len() < 2 {
+            return Ok(footer);
+        }
+        let labels = self
+            .tabs
+            .iter()
+            .map(PagerTab::label)
+            .collect::<Result<_>>()?;
+        Ok(footer.with_tabs(labels, self.active))
+    }
+
+    pub(super) fn update_footer(&self, pager: &Pager) -> Result<()> {
+        let footer = self.footer()?;
+        pager.set_prompt_renderer(move |context| footer.render(context))?;
+        Ok(())
+    }
+
+    /// Records where the active tab was left and takes its text off the screen.
+    fn leave_active(&mut self, line: usize) -> Result<()> {
+        let active = self.active_mut();
+        active.line = line;
+        active.document.hide()
+    }
+
+    /// Adds `tab` after the last one and shows it. The tab that was shown records the line
+    /// at the top of the view when the pager replaces its text, so scrolling done while
+    /// `tab` was loading is kept.
+    fn open(&mut self, tab: PagerTab, pager: &Pager, tabs: &Arc<Mutex<Self>>) -> Result<()> {
+        let left = self.active().document.clone();
+        left.hide()?;
+        self.push(tab);
+        let tabs = Arc::clone(tabs);
+        let active = self.active();
+        active.document.show_at(pager, active.line, move |line| {
+            if let Ok(mut tabs) = lock_tabs(&tabs)
+                && let Some(tab) = tabs.tabs.iter_mut().find(|tab| tab.document.same(&left))
+            {
+                tab.line = line;
+            }
+        })?;
+        self.update_footer(pager)
+    }
+
+    fn show_active(&self, pager: &Pager) -> Result<()> {
+        let active = self.active();
+        active.document.show(pager)?;
+        pager.scroll_to_line(active.line)?;
+        self.update_footer(pager)
+    }
+}
+
+pub(super) fn lock_tabs(tabs: &Mutex<PagerTabs>) -> Result<std::sync::MutexGuard<'_, PagerTabs>> {
+    tabs.lock().map_err(|_| anyhow!("Pager tabs lock poisoned"))
+}
+
+impl PagerInputClassifier {
+    /// Document of the active tab. The tabs lock is released before this returns.
+    pub(super) fn document(&self) -> Result<SharedDocument> {
+        Ok(lock_tabs(&self.tabs)?.active().document.clone())
+    }
+
+    pub(super) fn navigation(&self) -> Result<Arc<Mutex<PagerNavigation>>> {
+        Ok(lock_tabs(&self.tabs)?.active().navigation.clone())
+    }
+
+    pub(super) fn switch_tab(&self, forward: bool, state: &PagerState) -> Result<()> {
+        if lock_tabs(&self.tabs)?.len() < 2 {
+            self.pager
+                .send_message_for("No other tabs", STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        }
+        self.change_tabs(state, |tabs| {
+            tabs.step(forward);
+            Ok(())
+        })
+    }
+
+    pub(super) fn close_tab(&self, state: &PagerState) -> Result<()> {
+        if lock_tabs(&self.tabs)?.len() < 2 {
+            self.pager
+                .send_message_for("Cannot close the last tab", STATUS_MESSAGE_TIMEOUT)?;
+            return Ok(());
+        }
+        self.change_tabs(state, |tabs| {
+            if let Some(closed) = tabs.close_active() {
+                lock_navigation(&closed.navigation)?.stop_watcher();
+            }
+            Ok(())
+        })
+    }
+
+    fn change_tabs(
+        &self,
+        state: &PagerState,
+        change: impl FnOnce(&mut PagerTabs) -> Result<()>,
+    ) -> Result<()> {
+        if let Err(error) = self.remember_position(state) {
+            log::warn!("Failed to save the reading position: {error:#}");
+        }
+        self.clear_source_search();
+        let mut tabs = lock_tabs(&self.tabs)?;
+        tabs.leave_active(state.row_to_line(state.upper_mark).unwrap_or(0))?;
+        change(&mut tabs)?;
+        tabs.show_active(&self.pager)
+    }
+
+    /// Opens the target of `link` in a new tab after the last one.
+    pub(super) fn open_link_in_tab(&self, link: &MappedLink, state: &PagerState) -> Result<()> {
+        let current = self.current_location(state)?;
+        let (path, fragment) = match resolve_link(&link.url, current.file.as_deref())? {
+            LinkTarget::Document { path, fragment } => (path, fragment),
+            LinkTarget::Fragment(fragment) => match current.file {
+                Some(path) => (path, Some(fragment)),
+                None => return self.follow_link(link, state),
+            },
+        };
+        let Some(loader) = self.loader.clone() else {
+            return Err(anyhow!("Linked documents cannot be opened here"));
+        };
+        let destination = match fragment {
+            Some(fragment) => Destination::Fragment(fragment),
+            None => Destination::Resume(
+                lock_reading(&self.reading)?
+                    .record(Some(&path))
+                    .and_then(|record| record.position.clone()),
+            ),
+        };
+        if let Err(error) = self.remember_position(state) {
+            log::warn!("Failed to save the reading position: {error:#}");
+        }
+        self.clear_source_search();
+
+        let pager = self.pager.clone();
+        let tabs = self.tabs.clone();
+        thread::spawn(move || {
+            let result = loader(&path).and_then(|loaded| {
+                let line = destination_line(&loaded.map, &destination);
+                let tab = PagerTab::new(loaded, Some(path), line.as_ref().copied().unwrap_or(0));
+                lock_navigation(&tab.navigation)?.start_watcher(
+                    &pager,
+                    Some(&loader),
+                    &tab.document,
+                )?;
+                lock_tabs(&tabs)?.open(tab, &pager, &tabs)?;
+                Ok(line.err())
+            });
+            let message = match result {
+                Ok(None) => return,
+                Ok(Some(warning)) => format!("{warning:#}"),
+                Err(error) => format!("Failed to open link: {error:#}"),
+            };
+            let _ = pager.send_message(single_line_message(&message));
+        });
+        Ok(())
+    }
+}
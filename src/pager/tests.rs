@@ -1,52 +1,74 @@
 use super::*;
+use crate::keybindings::{KeyBindingOverrides, KeyScope};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use notify::{EventKind, event::CreateKind};
 use std::sync::atomic::AtomicUsize;
 use tempfile::TempDir;
 
+fn pager_action(event: &Event) -> Option<KeyAction> {
+    key_action(
+        &KeyBindingOverrides::default().resolve(KeyScope::Pager),
+        event,
+    )
+}
+
 #[test]
 fn editor_key_accepts_supported_layouts_without_control_modifiers() {
     for character in ['E', 'e', 'У', 'у'] {
         let event = Event::Key(KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE));
 
-        assert!(is_editor_key(&event), "character: {character}");
+        assert_eq!(
+            pager_action(&event),
+            Some(KeyAction::Edit),
+            "character: {character}"
+        );
     }
 
-    assert!(is_editor_key(&Event::Key(KeyEvent::new(
-        KeyCode::Char('E'),
-        KeyModifiers::SHIFT,
-    ))));
-    assert!(!is_editor_key(&Event::Key(KeyEvent::new(
-        KeyCode::Char('r'),
-        KeyModifiers::NONE,
-    ))));
-    assert!(!is_editor_key(&Event::Key(KeyEvent::new(
-        KeyCode::Char('e'),
-        KeyModifiers::CONTROL,
-    ))));
+    assert_eq!(
+        pager_action(&Event::Key(KeyEvent::new(
+            KeyCode::Char('E'),
+            KeyModifiers::SHIFT,
+        ))),
+        Some(KeyAction::Edit)
+    );
+    assert_ne!(
+        pager_action(&Event::Key(KeyEvent::new(
+            KeyCode::Char('r'),
+            KeyModifiers::NONE,
+        ))),
+        Some(KeyAction::Edit)
+    );
+    assert_eq!(
+        pager_action(&Event::Key(KeyEvent::new(
+            KeyCode::Char('e'),
+            KeyModifiers::CONTROL,
+        ))),
+        None
+    );
 }
 
 #[test]
 fn modified_question_mark_does_not_open_help() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::CONTROL));
 
-    assert!(!is_help_key(&event));
+    assert_eq!(pager_action(&event), None);
 }
 
 #[test]
 fn escape_routing_respects_help_and_search_state() {
     let event = Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    let action = pager_action(&event);
 
     assert_eq!(
-        help_input_action(&event, true, false),
+        help_input_action(&event, action, true, false),
         HelpInputAction::Dismiss
     );
     assert_eq!(
-        help_input_action(&event, false, false),
+        help_input_action(&event, action, false, false),
         HelpInputAction::Forward
     );
     assert_eq!(
-        help_input_action(&event, true, true),
+        help_input_action(&event, action, true, true),
         HelpInputAction::Forward
     );
 }
@@ -54,13 +76,14 @@ fn escape_routing_respects_help_and_search_state() {
 #[test]
 fn search_closes_visible_help_before_reaching_minus() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+    let action = pager_action(&event);
 
     assert_eq!(
-        help_input_action(&event, true, false),
+        help_input_action(&event, action, true, false),
         HelpInputAction::DismissAndForward
     );
     assert_eq!(
-        help_input_action(&event, false, false),
+        help_input_action(&event, action, false, false),
         HelpInputAction::Forward
     );
 }
@@ -68,13 +91,14 @@ fn search_closes_visible_help_before_reaching_minus() {
 #[test]
 fn control_f_closes_visible_help_before_reaching_minus() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
+    let action = pager_action(&event);
 
     assert_eq!(
-        help_input_action(&event, true, false),
+        help_input_action(&event, action, true, false),
         HelpInputAction::DismissAndForward
     );
     assert_eq!(
-        help_input_action(&event, false, false),
+        help_input_action(&event, action, false, false),
         HelpInputAction::Forward
     );
 }
@@ -82,13 +106,14 @@ fn control_f_closes_visible_help_before_reaching_minus() {
 #[test]
 fn question_mark_always_toggles_help() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT));
+    let action = pager_action(&event);
 
     assert_eq!(
-        help_input_action(&event, false, false),
+        help_input_action(&event, action, false, false),
         HelpInputAction::Toggle
     );
     assert_eq!(
-        help_input_action(&event, true, false),
+        help_input_action(&event, action, true, false),
         HelpInputAction::Toggle
     );
 }
@@ -98,8 +123,8 @@ fn copy_key_accepts_only_unmodified_c() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
     let modified = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
 
-    assert!(is_copy_key(&event));
-    assert!(!is_copy_key(&modified));
+    assert_eq!(pager_action(&event), Some(KeyAction::Copy));
+    assert_eq!(pager_action(&modified), None);
 }
 
 #[test]
@@ -107,8 +132,8 @@ fn reload_key_accepts_only_unmodified_r() {
     let event = Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
     let modified = Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::ALT));
 
-    assert!(is_reload_key(&event));
-    assert!(!is_reload_key(&modified));
+    assert_eq!(pager_action(&event), Some(KeyAction::Reload));
+    assert_eq!(pager_action(&modified), None);
 }
 
 #[test]
@@ -138,7 +163,7 @@ fn pager_messages_are_single_line() {
 
 #[test]
 fn clipboard_text_prefers_the_selection() {
-    let document = RwLock::new(PagerDocument::new(
+    let document = SharedDocument::new(PagerDocument::new(
         "rendered output".to_string(),
         "whole source".to_string(),
     ));
@@ -151,7 +176,7 @@ fn clipboard_text_prefers_the_selection() {
 
 #[test]
 fn clipboard_text_uses_the_source_without_a_selection() {
-    let document = RwLock::new(PagerDocument::new(
+    let document = SharedDocument::new(PagerDocument::new(
         "rendered output".to_string(),
         "whole source".to_string(),
     ));
@@ -166,10 +191,10 @@ fn active_watcher_refreshes_modified_file() {
     std::fs::write(&file, "# Before").unwrap();
     let refresh_count = Arc::new(AtomicUsize::new(0));
     let callback_count = refresh_count.clone();
-    let document = Arc::new(std::sync::RwLock::new(PagerDocument::new(
+    let document = SharedDocument::new(PagerDocument::new(
         "rendered before".to_string(),
         "# Before".to_string(),
-    )));
+    ));
     let refresh = Arc::new(move || {
         callback_count.fetch_add(1, Ordering::SeqCst);
         Ok(PagerDocument::new(
@@ -189,3 +214,555 @@ fn active_watcher_refreshes_modified_file() {
     assert!(refresh_count.load(Ordering::SeqCst) >= 1);
     assert_eq!(document.read().unwrap().source, "# After");
 }
+
+#[test]
+fn link_keys_accept_only_unmodified_characters() {
+    let key = |character| Event::Key(KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE));
+    let modified = Event::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+
+    assert!(is_link_hint_key(&key('o')));
+    assert!(is_contents_key(&key('t')));
+    assert!(is_history_back_key(&key('[')));
+    assert!(is_history_forward_key(&key(']')));
+    assert!(!is_link_hint_key(&modified));
+}
+
+#[test]
+fn relative_links_resolve_against_the_current_document() {
+    let temp_dir = TempDir::new().unwrap();
+    let current = temp_dir.path().join("README.md");
+    let target = temp_dir.path().join("docs").join("Getting Started.md");
+    std::fs::create_dir(temp_dir.path().join("docs")).unwrap();
+    std::fs::write(&current, "# Readme").unwrap();
+    std::fs::write(&target, "# Guide").unwrap();
+
+    assert_eq!(
+        resolve_link("docs/Getting%20Started.md#first-steps", Some(&current)).unwrap(),
+        LinkTarget::Document {
+            path: target.canonicalize().unwrap(),
+            fragment: Some("first-steps".to_string()),
+        }
+    );
+    assert_eq!(
+        resolve_link("#usage", Some(&current)).unwrap(),
+        LinkTarget::Fragment("usage".to_string())
+    );
+}
+
+#[test]
+fn external_and_missing_links_are_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let current = temp_dir.path().join("README.md");
+    std::fs::write(&current, "# Readme").unwrap();
+
+    assert!(resolve_link("https://example.com/guide.md", Some(&current)).is_err());
+    assert!(resolve_link("image.png", Some(&current)).is_err());
+    assert!(resolve_link("missing.md", Some(&current)).is_err());
+    assert!(resolve_link("#", Some(&current)).is_err());
+}
+
+#[test]
+fn hint_labels_grow_to_two_letters_past_the_alphabet() {
+    assert_eq!(hint_labels(3), ["a", "s", "d"]);
+
+    let labels = hint_labels(30);
+    assert_eq!(labels.len(), 30);
+    assert_eq!(labels[0], "aa");
+    assert_eq!(labels[28], "sd");
+}
+
+#[test]
+fn link_hints_follow_a_complete_label() {
+    let links = (0..27)
+        .map(|line| MappedLink {
+            url: format!("doc-{line}.md"),
+            text: String::new(),
+            line,
+        })
+        .collect();
+    let mut hints = LinkHints::new(links, false);
+
+    assert_eq!(hints.push('A'), HintInput::Pending);
+    assert_eq!(hints.matching().count(), 26);
+    let HintInput::Follow(link) = hints.push('s') else {
+        panic!("expected a complete label");
+    };
+    assert_eq!(link.url, "doc-1.md");
+    assert_eq!(
+        LinkHints::new(Vec::new(), false).push('a'),
+        HintInput::NoMatch
+    );
+}
+
+#[test]
+fn visible_links_are_limited_to_the_viewport() {
+    let link = |line| MappedLink {
+        url: "guide.md".to_string(),
+        text: "Guide".to_string(),
+        line,
+    };
+    let map = DocumentMap {
+        headings: Vec::new(),
+        links: vec![link(2), link(10), link(25)],
+        ..DocumentMap::default()
+    };
+
+    assert_eq!(visible_links(&map, 5, 25), [link(10), link(25)]);
+}
+
+#[test]
+fn yank_lists_visible_code_links_and_sections_in_order() {
+    let source =
+        "# Guide\n\nSee [docs](https://docs.rs).\n\n## Usage\n\n```sh\nmake\n```\n\n## Notes\n";
+    let heading = |level, text: &str, line| MappedHeading {
+        level,
+        text: text.to_string(),
+        slug: text.to_lowercase(),
+        line,
+    };
+    let map = DocumentMap {
+        headings: vec![
+            heading(1, "Guide", 0),
+            heading(2, "Usage", 4),
+            heading(2, "Notes", 8),
+        ],
+        links: vec![MappedLink {
+            url: "https://docs.rs".to_string(),
+            text: "docs".to_string(),
+            line: 2,
+        }],
+        code_blocks: vec![MappedCodeBlock {
+            language: "sh".to_string(),
+            code: "make".to_string(),
+            lines: 6..7,
+        }],
+        source_lines: vec![
+            Some(1),
+            None,
+            Some(3),
+            None,
+            Some(5),
+            None,
+            Some(8),
+            None,
+            Some(11),
+        ],
+    };
+    let document = PagerDocument::new(String::new(), source.to_string()).with_map(map);
+
+    let targets = visible_targets(&document, 2, 8);
+
+    assert_eq!(
+        targets
+            .iter()
+            .map(|target| (target.kind, target.title.as_str(), target.text.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (YankKind::Link, "docs  https://docs.rs", "https://docs.rs"),
+            (YankKind::Section, "Usage", "## Usage\n\n```sh\nmake\n```"),
+            (YankKind::Code, "sh  make", "make"),
+            (YankKind::Section, "Notes", "## Notes"),
+        ]
+    );
+    assert_eq!(
+        section_text(source, &document.map, 0).unwrap(),
+        source.trim_end()
+    );
+    let mut hints = YankHints::new(targets);
+    assert_eq!(
+        hints.push('d'),
+        YankInput::Copy(YankTarget {
+            kind: YankKind::Code,
+            title: "sh  make".to_string(),
+            text: "make".to_string(),
+        })
+    );
+}
+
+#[test]
+fn yank_key_is_plain_y() {
+    let event = Event::Key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+    let modified = Event::Key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+
+    assert!(is_yank_key(&event));
+    assert!(!is_yank_key(&modified));
+}
+
+#[test]
+fn reload_keeps_the_view_on_the_same_source_line() {
+    let old_source = "# Guide\n\nIntro.\n\n## Usage\n\nRun it.\n";
+    let new_source = "# Guide\n\nIntro.\n\nAdded.\n\nMore.\n\n## Usage\n\nRun it.\n";
+    let map = |source_lines: Vec<Option<usize>>| DocumentMap {
+        source_lines,
+        ..DocumentMap::default()
+    };
+    let old = map(vec![Some(1), None, Some(3), None, Some(5), None, Some(7)]);
+    let new = map(vec![
+        Some(1),
+        None,
+        Some(3),
+        None,
+        Some(5),
+        None,
+        Some(7),
+        None,
+        Some(9),
+        None,
+        Some(11),
+    ]);
+    let runs = source_changes(old_source, new_source);
+
+    assert_eq!(anchored_line(&old, &new, &runs, 0), 0);
+    assert_eq!(anchored_line(&old, &new, &runs, 4), 8);
+    assert_eq!(anchored_line(&old, &new, &runs, 6), 10);
+    assert_eq!(
+        changed_lines(&new, &runs),
+        [
+            false, false, false, false, true, true, true, true, false, false, false
+        ]
+    );
+}
+
+#[test]
+fn reload_falls_back_to_headings_without_source_lines() {
+    let heading = |slug: &str, line| MappedHeading {
+        level: 2,
+        text: slug.to_string(),
+        slug: slug.to_string(),
+        line,
+    };
+    let old = DocumentMap {
+        headings: vec![heading("usage", 10)],
+        ..DocumentMap::default()
+    };
+    let new = DocumentMap {
+        headings: vec![heading("usage", 14)],
+        ..DocumentMap::default()
+    };
+
+    assert_eq!(anchored_line(&old, &new, &[], 12), 16);
+}
+
+#[test]
+fn change_markers_replace_the_left_margin_after_styles() {
+    let output = " plain\n\x1b[1m bold\x1b[0m\nHeading\n";
+
+    let marked = mark_lines(output, &[false, true, true], "▎");
+
+    let lines: Vec<&str> = marked.lines().collect();
+    assert_eq!(lines[0], " plain");
+    assert_eq!(lines[1], "▎\x1b[1mbold\x1b[0m");
+    assert_eq!(lines[2], "Heading");
+}
+
+#[test]
+fn search_matches_keep_blended_backgrounds_unless_the_theme_sets_both() {
+    assert_eq!(PagerColors::default().search_colors(), None);
+
+    let theme = crate::theme::Theme {
+        pager_search_current_background: crate::theme::Color::AnsiValue(240),
+        pager_search_match_background: crate::theme::Color::AnsiValue(237),
+        ..crate::theme::Theme::default()
+    };
+    let colors = PagerColors::from_theme(&theme).search_colors();
+
+    assert_eq!(
+        colors,
+        Some(minus::SearchColors {
+            current: minus::PromptColor::AnsiValue(240),
+            other: minus::PromptColor::AnsiValue(237),
+        })
+    );
+}
+
+#[test]
+fn change_marker_takes_the_warning_color_unless_colors_are_off() {
+    let theme = crate::theme::Theme {
+        warning: crate::theme::Color::DarkYellow,
+        ..crate::theme::Theme::default()
+    };
+    let colors = PagerColors::from_theme(&theme);
+
+    assert_eq!(colors.change_marker(), "\x1b[38;5;3m▎\x1b[0m");
+    assert_eq!(colors.with_plain_change_marker().change_marker(), "▎");
+}
+
+#[test]
+fn history_steps_move_locations_between_stacks() {
+    let location = |line| PagerLocation {
+        file: Some(PathBuf::from("README.md")),
+        line,
+    };
+    let mut history = PagerHistory::default();
+    history.commit(HistoryStep::Visit, location(4));
+
+    assert_eq!(history.peek(HistoryStep::Back), Some(&location(4)));
+    assert_eq!(history.peek(HistoryStep::Forward), None);
+
+    history.commit(HistoryStep::Back, location(0));
+    assert_eq!(history.peek(HistoryStep::Back), None);
+    assert_eq!(history.peek(HistoryStep::Forward), Some(&location(0)));
+
+    history.commit(HistoryStep::Visit, location(8));
+    assert_eq!(history.peek(HistoryStep::Forward), None);
+}
+
+fn contents_headings() -> Vec<MappedHeading> {
+    [
+        (1, "Guide", 0),
+        (2, "Install", 4),
+        (3, "Linux", 9),
+        (2, "Usage", 20),
+    ]
+    .into_iter()
+    .map(|(level, text, line)| MappedHeading {
+        level,
+        text: text.to_string(),
+        slug: text.to_lowercase(),
+        line,
+    })
+    .collect()
+}
+
+#[test]
+fn contents_start_at_the_heading_above_the_viewport() {
+    let contents = TableOfContents::new(contents_headings(), 12, 40);
+
+    assert_eq!(contents.selected().unwrap().text, "Linux");
+}
+
+#[test]
+fn contents_keys_move_the_selection_and_jump() {
+    let mut contents = TableOfContents::new(contents_headings(), 0, 40);
+
+    assert_eq!(contents.push(KeyCode::Down), ContentsInput::Pending);
+    assert_eq!(contents.push(KeyCode::Char('j')), ContentsInput::Pending);
+    assert_eq!(contents.push(KeyCode::Enter), ContentsInput::Jump(9));
+    assert_eq!(contents.push(KeyCode::Char('G')), ContentsInput::Pending);
+    assert_eq!(contents.selected().unwrap().text, "Usage");
+    assert_eq!(contents.push(KeyCode::Esc), ContentsInput::Close);
+}
+
+#[test]
+fn contents_panel_indents_nested_headings_and_follows_the_selection() {
+    let contents = TableOfContents::new(contents_headings(), 20, 6);
+    let lines = build_contents_panel(&contents, &PagerColors::default())
+        .unwrap()
+        .iter()
+        .map(|line| line.render_plain(40).trim_end().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(lines, ["", "    Install", "      Linux", "    Usage", ""]);
+}
+
+fn source_search_document(markdown: &str) -> PagerDocument {
+    let config = crate::config::Config {
+        cols: Some(24),
+        cols_from_cli: true,
+        source_map: true,
+        ..crate::config::Config::default()
+    };
+    let document = crate::markdown::MarkdownProcessor::new(&config)
+        .parse_document(markdown)
+        .unwrap();
+    let (output, map) = crate::renderer::TerminalRenderer::new(&config)
+        .unwrap()
+        .render_document_map(document)
+        .unwrap();
+    PagerDocument::new(output, markdown.to_string()).with_map(map)
+}
+
+fn rendered_line(document: &PagerDocument, line: usize) -> String {
+    crate::utils::strip_ansi(document.output.lines().nth(line).unwrap())
+}
+
+#[test]
+fn source_search_finds_text_split_by_wrapping_and_hidden_urls() {
+    let document = source_search_document(
+        "# Notes\n\nAn extraordinarily-hyphenated word wraps here.\n\nSee [the docs](https://example.com/hidden-path).\n",
+    );
+    let options = SearchOptions::default();
+
+    let wrapped = source_match_lines(
+        &document,
+        &compile_query("extraordinarily-hyphenated word", options).unwrap(),
+    );
+    assert_eq!(wrapped.len(), 1);
+    assert!(rendered_line(&document, wrapped[0]).contains("extraordinarily"));
+
+    let hidden = source_match_lines(&document, &compile_query("hidden-path", options).unwrap());
+    assert_eq!(hidden.len(), 1);
+    assert!(rendered_line(&document, hidden[0]).contains("the docs"));
+    assert!(!crate::utils::strip_terminal_sequences(&document.output).contains("hidden-path"));
+}
+
+#[test]
+fn source_search_lands_on_the_wrapped_row_showing_the_match() {
+    let document = source_search_document("alpha beta gamma delta epsilon zeta eta theta\n");
+    let lines = source_match_lines(
+        &document,
+        &compile_query("theta", SearchOptions::default()).unwrap(),
+    );
+
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0] > 0);
+    assert!(rendered_line(&document, lines[0]).contains("theta"));
+}
+
+#[test]
+fn source_queries_follow_case_and_regex_settings() {
+    let smart = SearchOptions::default();
+    assert!(compile_query("readme", smart).unwrap().is_match("README"));
+    assert!(!compile_query("ReadMe", smart).unwrap().is_match("README"));
+
+    let sensitive = SearchOptions {
+        case: CaseMode::Sensitive,
+        ..smart
+    };
+    assert!(
+        !compile_query("readme", sensitive)
+            .unwrap()
+            .is_match("README")
+    );
+
+    let ignore = SearchOptions {
+        case: CaseMode::Insensitive,
+        ..smart
+    };
+    assert!(compile_query("ReadMe", ignore).unwrap().is_match("README"));
+
+    assert!(!compile_query("v[0-9]", smart).unwrap().is_match("v1"));
+    let regex = SearchOptions {
+        regex: true,
+        ..smart
+    };
+    assert!(compile_query("v[0-9]", regex).unwrap().is_match("v1"));
+    assert!(compile_query("v[0-", regex).is_err());
+}
+
+#[test]
+fn source_search_prompt_edits_the_query_and_toggles_settings() {
+    let mut prompt = SourceSearchPrompt::new(SearchOptions::default());
+
+    assert_eq!(
+        prompt.push(KeyCode::Enter, KeyModifiers::NONE),
+        SearchInput::Close
+    );
+    for character in "abc".chars() {
+        prompt.push(KeyCode::Char(character), KeyModifiers::NONE);
+    }
+    prompt.push(KeyCode::Backspace, KeyModifiers::NONE);
+    prompt.push(KeyCode::Char('r'), KeyModifiers::CONTROL);
+    prompt.push(KeyCode::Char('t'), KeyModifiers::CONTROL);
+
+    assert_eq!(prompt.query, "ab");
+    assert!(prompt.options.regex);
+    assert_eq!(prompt.options.case, CaseMode::Sensitive);
+    let panel = build_search_panel(&prompt, &PagerColors::default())
+        .unwrap()
+        .iter()
+        .map(|line| line.render_plain(80).trim_end().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(panel[1], "  Source: ab█");
+    assert!(panel[2].contains("regex: on") && panel[2].contains("case: sensitive"));
+    assert_eq!(
+        prompt.push(KeyCode::Enter, KeyModifiers::NONE),
+        SearchInput::Submit
+    );
+}
+
+#[test]
+fn source_matches_step_and_wrap_around_the_document() {
+    let lines = [3, 10, 20];
+
+    assert_eq!(step_match(&lines, 10, true, true), Some(1));
+    assert_eq!(step_match(&lines, 10, true, false), Some(2));
+    assert_eq!(step_match(&lines, 20, true, false), Some(0));
+    assert_eq!(step_match(&lines, 3, false, false), Some(2));
+    assert_eq!(step_match(&lines, 15, false, false), Some(1));
+    assert_eq!(step_match(&[], 0, true, true), None);
+}
+
+#[test]
+fn bookmark_keys_set_and_jump() {
+    let key = |character| Event::Key(KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE));
+
+    assert_eq!(bookmark_action(&key('m')), Some(BookmarkAction::Set));
+    assert_eq!(bookmark_action(&key('\'')), Some(BookmarkAction::Jump));
+    assert_eq!(bookmark_action(&key('M')), None);
+    assert_eq!(
+        bookmark_action(&Event::Key(KeyEvent::new(
+            KeyCode::Char('m'),
+            KeyModifiers::ALT
+        ))),
+        None
+    );
+}
+
+#[test]
+fn source_match_keys_are_n_and_p() {
+    let key = |character| Event::Key(KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE));
+
+    assert_eq!(source_match_step(&key('n')), Some(true));
+    assert_eq!(source_match_step(&key('p')), Some(false));
+    assert_eq!(source_match_step(&key('s')), None);
+    assert!(is_source_search_key(&key('s')));
+}
+
+fn tab(line: usize) -> PagerTab {
+    PagerTab::new(
+        PagerDocument::new(String::new(), String::new()),
+        Some(PathBuf::from(format!("/docs/{line}.md"))),
+        line,
+    )
+}
+
+#[test]
+fn tabs_wrap_around_in_both_directions() {
+    let mut tabs = PagerTabs::new(vec![tab(0), tab(1), tab(2)], 2);
+
+    tabs.step(true);
+    assert_eq!(tabs.active().line, 0);
+    tabs.step(false);
+    assert_eq!(tabs.active().line, 2);
+    tabs.push(tab(3));
+    assert_eq!(tabs.active().line, 3);
+}
+
+#[test]
+fn closing_a_tab_selects_its_neighbour_and_keeps_the_last_one() {
+    let mut tabs = PagerTabs::new(vec![tab(0), tab(1), tab(2)], 1);
+
+    assert_eq!(tabs.close_active().unwrap().line, 1);
+    assert_eq!(tabs.active().line, 2);
+    assert_eq!(tabs.close_active().unwrap().line, 2);
+    assert_eq!(tabs.active().line, 0);
+    assert!(tabs.close_active().is_none());
+    assert_eq!(tabs.len(), 1);
+}
+
+#[test]
+fn tab_keys_switch_tabs_without_modifiers() {
+    assert_eq!(
+        tab_step(&Event::Key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE))),
+        Some(true)
+    );
+    assert_eq!(
+        tab_step(&Event::Key(KeyEvent::new(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT
+        ))),
+        Some(false)
+    );
+    assert_eq!(
+        tab_step(&Event::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::CONTROL
+        ))),
+        None
+    );
+    let key = |character| Event::Key(KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE));
+    assert!(is_close_tab_key(&key('x')));
+    assert!(is_new_tab_link_hint_key(&key('O')));
+    assert!(!is_new_tab_link_hint_key(&key('o')));
+}
@@ -0,0 +1,99 @@
+use super::*;
+use minus::PromptAttribute;
+use minus::input::crossterm_event::KeyCode;
+
+const MIN_VISIBLE_ENTRIES: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum ContentsInput {
+    Pending,
+    Jump(usize),
+    Close,
+}
+
+/// Heading picker shown by the `t` key.
+pub(super) struct TableOfContents {
+    headings: Vec<MappedHeading>,
+    selected: usize,
+    visible_entries: usize,
+}
+
+impl TableOfContents {
+    /// Selects the last heading at or above `current_line`.
+    pub(super) fn new(headings: Vec<MappedHeading>, current_line: usize, rows: usize) -> Self {
+        let selected = headings
+            .iter()
+            .rposition(|heading| heading.line <= current_line)
+            .unwrap_or(0);
+        Self {
+            visible_entries: (rows / 2).max(MIN_VISIBLE_ENTRIES).min(headings.len()),
+            headings,
+            selected,
+        }
+    }
+
+    pub(super) fn selected(&self) -> Option<&MappedHeading> {
+        self.headings.get(self.selected)
+    }
+
+    pub(super) fn push(&mut self, key: KeyCode) -> ContentsInput {
+        let last = self.headings.len().saturating_sub(1);
+        let page = self.visible_entries.max(1);
+        self.selected = match key {
+            KeyCode::Up | KeyCode::Char('k') => self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => (self.selected + 1).min(last),
+            KeyCode::PageUp | KeyCode::Char('b') => self.selected.saturating_sub(page),
+            KeyCode::PageDown | KeyCode::Char('f') => (self.selected + page).min(last),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            KeyCode::Enter => {
+                return self.selected().map_or(ContentsInput::Close, |heading| {
+                    ContentsInput::Jump(heading.line)
+                });
+            }
+            KeyCode::Esc | KeyCode::Char('t' | 'q') => return ContentsInput::Close,
+            _ => self.selected,
+        };
+        ContentsInput::Pending
+    }
+
+    /// Returns the entries that fit in the panel, keeping the selection visible.
+    fn window(&self) -> std::ops::Range<usize> {
+        let start = self
+            .selected
+            .saturating_sub(self.visible_entries / 2)
+            .min(self.headings.len() - self.visible_entries);
+        start..start + self.visible_entries
+    }
+}
+
+pub(super) fn build_contents_panel(
+    contents: &TableOfContents,
+    colors: &PagerColors,
+) -> Result<Vec<PromptLine>, PromptError> {
+    let style = colors.panel_style();
+    let min_level = contents
+        .headings
+        .iter()
+        .map(|heading| heading.level)
+        .min()
+        .unwrap_or(1);
+    let mut lines = vec![panel_line(String::new(), style)?];
+    for index in contents.window() {
+        let heading = &contents.headings[index];
+        let indent = "  ".repeat(usize::from(heading.level - min_level));
+        let text: String = heading
+            .text
+            .chars()
+            .filter(|character| !character.is_control())
+            .collect();
+        let line_style = if index == contents.selected {
+            style.attribute(PromptAttribute::Reverse)
+        } else {
+            style
+        };
+        lines.push(panel_line(format!("  {indent}{text}"), line_style)?);
+    }
+    lines.push(panel_line(String::new(), style)?);
+    Ok(lines)
+}
@@ -10,7 +10,7 @@ impl ActiveWatcher {
         path: &Path,
         pager: Pager,
         refresh: RefreshCallback,
-        document: Arc<RwLock<PagerDocument>>,
+        document: SharedDocument,
     ) -> Result<Self> {
         let target = comparable_path(path)?;
         let directory = target
@@ -52,9 +52,7 @@ impl ActiveWatcher {
                 }
 
                 if refresh_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
-                    match refresh().and_then(|refreshed| {
-                        apply_refreshed_document(&pager, &document, refreshed)
-                    }) {
+                    match refresh().and_then(|refreshed| document.reload(&pager, refreshed)) {
                         Ok(()) => {}
                         Err(error) => {
                             if pager
@@ -0,0 +1,197 @@
+use super::links::hint_labels;
+use super::*;
+
+/// Kind of element that yank mode copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum YankKind {
+    Code,
+    Link,
+    Section,
+}
+
+impl YankKind {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Code => "code",
+            Self::Link => "link",
+            Self::Section => "section",
+        }
+    }
+
+    pub(super) const fn copied_message(self) -> &'static str {
+        match self {
+            Self::Code => "Copied code block",
+            Self::Link => "Copied link",
+            Self::Section => "Copied section",
+        }
+    }
+}
+
+/// Element on screen with the raw text that yank mode copies for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct YankTarget {
+    pub(super) kind: YankKind,
+    /// Short description shown in the yank panel.
+    pub(super) title: String,
+    pub(super) text: String,
+}
+
+/// Returns the code blocks, links, and sections whose start or code is shown between two
+/// output lines, inclusive, in the order they appear.
+pub(super) fn visible_targets(
+    document: &PagerDocument,
+    first_line: usize,
+    last_line: usize,
+) -> Vec<YankTarget> {
+    let map = &document.map;
+    let visible = first_line..=last_line;
+    let mut targets = Vec::new();
+    for block in &map.code_blocks {
+        if block.lines.start > last_line || block.lines.end <= first_line {
+            continue;
+        }
+        targets.push((
+            block.lines.start.max(first_line),
+            YankTarget {
+                kind: YankKind::Code,
+                title: code_block_title(block),
+                text: block.code.clone(),
+            },
+        ));
+    }
+    for link in map.links.iter().filter(|link| visible.contains(&link.line)) {
+        let title = if link.text.is_empty() || link.text == link.url {
+            link.url.clone()
+        } else {
+            format!("{}  {}", link.text, link.url)
+        };
+        targets.push((
+            link.line,
+            YankTarget {
+                kind: YankKind::Link,
+                title,
+                text: link.url.clone(),
+            },
+        ));
+    }
+    for (index, heading) in map.headings.iter().enumerate() {
+        if !visible.contains(&heading.line) {
+            continue;
+        }
+        if let Some(text) = section_text(&document.source, map, index) {
+            targets.push((
+                heading.line,
+                YankTarget {
+                    kind: YankKind::Section,
+                    title: heading.text.clone(),
+                    text,
+                },
+            ));
+        }
+    }
+    targets.sort_by_key(|(line, _)| *line);
+    targets.into_iter().map(|(_, target)| target).collect()
+}
+
+/// Language and first line of a code block.
+fn code_block_title(block: &MappedCodeBlock) -> String {
+    let first_line = block
+        .code
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    if block.language.is_empty() {
+        first_line.to_string()
+    } else {
+        format!("{}  {first_line}", block.language)
+    }
+}
+
+/// Markdown source of the section under the heading at `index`, up to the next heading
+/// of the same or a higher level. Needs the source lines of the map.
+pub(super) fn section_text(source: &str, map: &DocumentMap, index: usize) -> Option<String> {
+    let source_line =
+        |heading: &MappedHeading| map.source_lines.get(heading.line).copied().flatten();
+    let heading = map.headings.get(index)?;
+    let start = source_line(heading)?.checked_sub(1)?;
+    let end = map.headings[index + 1..]
+        .iter()
+        .filter(|next| next.level <= heading.level)
+        .find_map(source_line)
+        .map_or(usize::MAX, |line| line.saturating_sub(1));
+    let lines: Vec<&str> = source
+        .lines()
+        .skip(start)
+        .take(end.saturating_sub(start))
+        .collect();
+    let text = lines.join("\n").trim_end().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum YankInput {
+    Pending,
+    Copy(YankTarget),
+    NoMatch,
+}
+
+/// Yank mode: elements on screen are labelled and copied by typing a label.
+pub(super) struct YankHints {
+    hints: Vec<(String, YankTarget)>,
+    typed: String,
+}
+
+impl YankHints {
+    pub(super) fn new(targets: Vec<YankTarget>) -> Self {
+        Self {
+            hints: hint_labels(targets.len())
+                .into_iter()
+                .zip(targets)
+                .collect(),
+            typed: String::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, character: char) -> YankInput {
+        self.typed.push(character.to_ascii_lowercase());
+        if let Some((_, target)) = self.hints.iter().find(|(label, _)| *label == self.typed) {
+            return YankInput::Copy(target.clone());
+        }
+        if self.matching().next().is_some() {
+            YankInput::Pending
+        } else {
+            YankInput::NoMatch
+        }
+    }
+
+    fn matching(&self) -> impl Iterator<Item = &(String, YankTarget)> {
+        self.hints
+            .iter()
+            .filter(|(label, _)| label.starts_with(&self.typed))
+    }
+}
+
+pub(super) fn build_yank_panel(
+    hints: &YankHints,
+    colors: &PagerColors,
+) -> Result<Vec<PromptLine>, PromptError> {
+    let style = colors.panel_style();
+    let label_width = hints
+        .hints
+        .first()
+        .map_or(1, |(label, _)| label.chars().count());
+    let mut lines = vec![panel_line(String::new(), style)?];
+    for (label, target) in hints.matching() {
+        lines.push(panel_line(
+            format!(
+                "  {label:<label_width$}  {:<7}  {}",
+                target.kind.name(),
+                single_line_message(&target.title)
+            ),
+            style,
+        )?);
+    }
+    lines.push(panel_line(String::new(), style)?);
+    Ok(lines)
+}
@@ -0,0 +1,249 @@
+use crate::renderer::DocumentMap;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DATA_DIR_ENV: &str = "MDV_DATA_DIR";
+const STATE_FILE_NAME: &str = "reading.json";
+
+/// Place in a document, kept relative to the closest heading above it so that edits
+/// in earlier sections do not move it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ReadingPosition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    heading: Option<String>,
+    #[serde(default)]
+    offset: usize,
+    line: usize,
+}
+
+impl ReadingPosition {
+    pub(crate) fn capture(map: &DocumentMap, line: usize) -> Self {
+        let heading = map
+            .headings
+            .iter()
+            .rev()
+            .find(|heading| heading.line <= line);
+        Self {
+            heading: heading.map(|heading| heading.slug.clone()),
+            offset: heading.map_or(0, |heading| line - heading.line),
+            line,
+        }
+    }
+
+    /// Rendered line of the position; falls back to the recorded line when the heading is gone.
+    pub(crate) fn resolve(&self, map: &DocumentMap) -> usize {
+        self.heading
+            .as_deref()
+            .and_then(|slug| map.heading_by_slug(slug))
+            .map_or(self.line, |heading| heading.line + self.offset)
+    }
+}
+
+/// Reading state of one file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ReadingRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) position: Option<ReadingPosition>,
+    /// Seconds since the Unix epoch when the pager last left the file.
+    #[serde(default)]
+    last_read: u64,
+    /// Share of the document, in percent, above the bottom of the viewport.
+    #[serde(default)]
+    pub(crate) progress: u8,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) bookmarks: BTreeMap<char, ReadingPosition>,
+}
+
+impl ReadingRecord {
+    pub(crate) fn last_read(&self) -> Option<SystemTime> {
+        (self.last_read > 0).then(|| SystemTime::UNIX_EPOCH + Duration::from_secs(self.last_read))
+    }
+
+    pub(crate) fn mark_read(&mut self, position: ReadingPosition, progress: u8, now: SystemTime) {
+        self.position = Some(position);
+        self.progress = progress.min(100);
+        self.last_read = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+    }
+}
+
+/// Reading positions and bookmarks of every file opened in the pager.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ReadingStore {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(default)]
+    files: BTreeMap<PathBuf, ReadingRecord>,
+    /// Record of a document without a file, such as standard input; it is never saved.
+    #[serde(skip)]
+    unsaved: ReadingRecord,
+}
+
+impl ReadingStore {
+    /// Loads the store from the user data directory, or `MDV_DATA_DIR` when it is set.
+    pub(crate) fn load() -> Self {
+        default_state_path().map_or_else(Self::default, Self::load_from)
+    }
+
+    pub(crate) fn load_from(path: PathBuf) -> Self {
+        let mut store = read_store(&path).unwrap_or_else(|error| {
+            log::warn!("Ignoring reading state: {error:#}");
+            Self::default()
+        });
+        store.path = Some(path);
+        store
+    }
+
+    pub(crate) fn record(&self, file: Option<&Path>) -> Option<&ReadingRecord> {
+        match file {
+            Some(file) => self.files.get(file),
+            None => Some(&self.unsaved),
+        }
+    }
+
+    /// Applies `change` to the record of `file` and saves the store. Records written by
+    /// other mdv processes since the store was loaded are kept.
+    pub(crate) fn update(
+        &mut self,
+        file: Option<&Path>,
+        change: impl FnOnce(&mut ReadingRecord),
+    ) -> Result<()> {
+        let Some(file) = file else {
+            change(&mut self.unsaved);
+            return Ok(());
+        };
+        if let Some(path) = &self.path
+            && let Ok(stored) = read_store(path)
+        {
+            self.files = stored.files;
+        }
+        change(self.files.entry(file.to_path_buf()).or_default());
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let temporary = path.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", temporary.display()))?;
+        std::fs::rename(&temporary, path)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn read_store(path: &Path) -> Result<ReadingStore> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(ReadingStore::default()),
+        Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn default_state_path() -> Option<PathBuf> {
+    std::env::var_os(DATA_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_dir().map(|dir| dir.join("mdv")))
+        .map(|dir| dir.join(STATE_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::MappedHeading;
+
+    fn heading(slug: &str, line: usize) -> MappedHeading {
+        MappedHeading {
+            level: 2,
+            text: slug.to_string(),
+            slug: slug.to_string(),
+            line,
+        }
+    }
+
+    fn map(headings: Vec<MappedHeading>) -> DocumentMap {
+        DocumentMap {
+            headings,
+            ..DocumentMap::default()
+        }
+    }
+
+    #[test]
+    fn positions_follow_their_heading_when_earlier_sections_change() {
+        let original = map(vec![heading("intro", 0), heading("usage", 40)]);
+        let position = ReadingPosition::capture(&original, 46);
+
+        let edited = map(vec![heading("intro", 0), heading("usage", 55)]);
+        assert_eq!(position.resolve(&edited), 61);
+
+        let renamed = map(vec![heading("intro", 0), heading("examples", 55)]);
+        assert_eq!(position.resolve(&renamed), 46);
+        assert_eq!(ReadingPosition::capture(&original, 0).resolve(&edited), 0);
+    }
+
+    #[test]
+    fn store_keeps_records_written_by_other_processes() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("state").join(STATE_FILE_NAME);
+        let document = map(vec![heading("usage", 10)]);
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        let mut first = ReadingStore::load_from(path.clone());
+        let mut second = ReadingStore::load_from(path.clone());
+        first
+            .update(Some(Path::new("/docs/a.md")), |record| {
+                record.mark_read(ReadingPosition::capture(&document, 12), 40, now);
+            })
+            .unwrap();
+        second
+            .update(Some(Path::new("/docs/b.md")), |record| {
+                record
+                    .bookmarks
+                    .insert('x', ReadingPosition::capture(&document, 3));
+            })
+            .unwrap();
+
+        let loaded = ReadingStore::load_from(path);
+        let a = loaded.record(Some(Path::new("/docs/a.md"))).unwrap();
+        assert_eq!(a.progress, 40);
+        assert_eq!(a.last_read(), Some(now));
+        assert_eq!(a.position.as_ref().unwrap().resolve(&document), 12);
+        let b = loaded.record(Some(Path::new("/docs/b.md"))).unwrap();
+        assert_eq!(b.bookmarks[&'x'].resolve(&document), 3);
+        assert_eq!(b.last_read(), None);
+    }
+
+    #[test]
+    fn documents_without_a_file_are_not_saved() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(STATE_FILE_NAME);
+        let mut store = ReadingStore::load_from(path.clone());
+
+        store.update(None, |record| record.progress = 50).unwrap();
+
+        assert_eq!(store.record(None).unwrap().progress, 50);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn unreadable_state_starts_empty() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join(STATE_FILE_NAME);
+        std::fs::write(&path, "not json").unwrap();
+
+        let store = ReadingStore::load_from(path);
+
+        assert_eq!(store.record(Some(Path::new("/docs/a.md"))), None);
+    }
+}
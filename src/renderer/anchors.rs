@@ -0,0 +1,282 @@
+use super::line_numbers::strip_internal_anchors;
+use crate::markdown::{HeadingSlugs, SourceLineMarker, heading_text, source_line_from_event};
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Tag, TagEnd};
+use std::cmp::Reverse;
+use std::ops::{Range, RangeInclusive};
+
+const EVENT_PREFIX: &str = "\u{001d}MDV_ANCHOR:";
+const EVENT_SUFFIX: char = '\u{001e}';
+
+/// Headings, links, and code blocks of a rendered document, keyed by rendered output line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DocumentMap {
+    pub(crate) headings: Vec<MappedHeading>,
+    pub(crate) links: Vec<MappedLink>,
+    /// Code blocks located through source lines; empty without a source map.
+    pub(crate) code_blocks: Vec<MappedCodeBlock>,
+    /// First source line on each rendered line; empty unless the config asks for a source map.
+    pub(crate) source_lines: Vec<Option<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MappedHeading {
+    pub(crate) level: u8,
+    pub(crate) text: String,
+    pub(crate) slug: String,
+    pub(crate) line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MappedLink {
+    pub(crate) url: String,
+    pub(crate) text: String,
+    pub(crate) line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MappedCodeBlock {
+    pub(crate) language: String,
+    /// Code as parsed, without fences, frame, or line numbers.
+    pub(crate) code: String,
+    /// Rendered lines that show the code.
+    pub(crate) lines: Range<usize>,
+}
+
+impl DocumentMap {
+    /// Accounts for lines printed before the rendered document.
+    pub(crate) fn offset_lines(&mut self, lines: usize) {
+        for heading in &mut self.headings {
+            heading.line += lines;
+        }
+        for link in &mut self.links {
+            link.line += lines;
+        }
+        for block in &mut self.code_blocks {
+            block.lines = block.lines.start + lines..block.lines.end + lines;
+        }
+        if !self.source_lines.is_empty() {
+            self.source_lines
+                .splice(0..0, std::iter::repeat_n(None, lines));
+        }
+    }
+
+    /// Rendered lines that show source `line`: from the first line starting it, or the
+    /// closest earlier source line, up to the next line that starts another one.
+    pub(crate) fn rendered_lines_for_source(&self, line: usize) -> Option<Range<usize>> {
+        let (_, start) = self
+            .source_lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, source)| {
+                source
+                    .filter(|source| *source <= line)
+                    .map(|source| (source, index))
+            })
+            .min_by_key(|&(source, index)| (Reverse(source), index))?;
+        let end = self.source_lines[start + 1..]
+            .iter()
+            .position(Option::is_some)
+            .map_or(self.source_lines.len(), |offset| start + 1 + offset);
+        Some(start..end)
+    }
+
+    pub(crate) fn heading_by_slug(&self, slug: &str) -> Option<&MappedHeading> {
+        self.headings
+            .iter()
+            .find(|heading| heading.slug == slug)
+            .or_else(|| {
+                let slug = slug.to_lowercase();
+                self.headings.iter().find(|heading| heading.slug == slug)
+            })
+    }
+}
+
+pub(super) enum AnchorTarget {
+    Heading {
+        level: u8,
+        text: String,
+        slug: String,
+    },
+    Link {
+        url: String,
+        text: String,
+    },
+}
+
+/// Inserts an anchor event after every heading and link start tag.
+///
+/// The renderer turns these events into zero-width markers that survive wrapping
+/// and layout, so the final output can be scanned for the line of each target.
+pub(super) fn insert_anchor_events(
+    events: Vec<Event<'static>>,
+) -> (Vec<Event<'static>>, Vec<AnchorTarget>) {
+    let mut slugs = HeadingSlugs::default();
+    let mut targets = Vec::new();
+    let mut anchored = Vec::with_capacity(events.len());
+    for (index, event) in events.iter().enumerate() {
+        anchored.push(event.clone());
+        let target = match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                let text = heading_text(&events[index + 1..]);
+                (!text.is_empty()).then(|| AnchorTarget::Heading {
+                    level: *level as u8,
+                    slug: slugs.next(&text, id.as_deref()),
+                    text,
+                })
+            }
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) if !matches!(link_type, LinkType::Email) => Some(AnchorTarget::Link {
+                url: dest_url.to_string(),
+                text: link_text(&events[index + 1..]),
+            }),
+            _ => None,
+        };
+        if let Some(target) = target {
+            anchored.push(event_for(targets.len()));
+            targets.push(target);
+        }
+    }
+    (anchored, targets)
+}
+
+pub(crate) fn from_event(event: &Event<'_>) -> Option<usize> {
+    let Event::InlineHtml(marker) = event else {
+        return None;
+    };
+
+    marker
+        .strip_prefix(EVENT_PREFIX)?
+        .strip_suffix(EVENT_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+/// Strips anchor markers from `output` and records the line of each target.
+pub(super) fn locate_anchors(output: &str, targets: Vec<AnchorTarget>) -> (String, DocumentMap) {
+    let mut lines = vec![None; targets.len()];
+    let mut cleaned = String::with_capacity(output.len());
+    for (line_index, line) in output.split_inclusive('\n').enumerate() {
+        let (line, anchors) = strip_internal_anchors(line);
+        for anchor in anchors {
+            if let Some(slot) = lines.get_mut(anchor) {
+                slot.get_or_insert(line_index);
+            }
+        }
+        cleaned.push_str(&line);
+    }
+
+    let mut map = DocumentMap::default();
+    for (target, line) in targets.into_iter().zip(lines) {
+        let Some(line) = line else {
+            continue;
+        };
+        match target {
+            AnchorTarget::Heading { level, text, slug } => map.headings.push(MappedHeading {
+                level,
+                text,
+                slug,
+                line,
+            }),
+            AnchorTarget::Link { url, text } => map.links.push(MappedLink { url, text, line }),
+        }
+    }
+    (cleaned, map)
+}
+
+/// Code block of the parsed events with the source lines its code came from.
+pub(super) struct CodeBlockSource {
+    language: String,
+    code: String,
+    lines: RangeInclusive<usize>,
+}
+
+/// Collects the code blocks whose code carries source-line markers.
+pub(super) fn code_block_sources(events: &[Event<'_>]) -> Vec<CodeBlockSource> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, String, Option<RangeInclusive<usize>>)> = None;
+    for event in events {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or(""),
+                    CodeBlockKind::Indented => "",
+                };
+                current = Some((language.to_string(), String::new(), None));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, code, Some(lines))) = current.take() {
+                    blocks.push(CodeBlockSource {
+                        language,
+                        code,
+                        lines,
+                    });
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, code, _)) = current.as_mut() {
+                    code.push_str(text);
+                }
+            }
+            event => {
+                if let Some((_, _, lines)) = current.as_mut()
+                    && let Some(SourceLineMarker::Content(line)) = source_line_from_event(event)
+                {
+                    *lines = Some(
+                        lines
+                            .as_ref()
+                            .map_or(line..=line, |lines| *lines.start()..=line.max(*lines.end())),
+                    );
+                }
+            }
+        }
+    }
+    blocks
+}
+
+/// Finds the rendered lines of each code block from the source line of every rendered
+/// line.
+pub(super) fn locate_code_blocks(
+    blocks: Vec<CodeBlockSource>,
+    source_lines: &[Option<usize>],
+) -> Vec<MappedCodeBlock> {
+    blocks
+        .into_iter()
+        .filter_map(|block| {
+            let mut rendered = source_lines
+                .iter()
+                .enumerate()
+                .filter(|(_, source)| source.is_some_and(|source| block.lines.contains(&source)))
+                .map(|(line, _)| line);
+            let start = rendered.next()?;
+            let end = rendered.next_back().unwrap_or(start) + 1;
+            Some(MappedCodeBlock {
+                language: block.language,
+                code: block.code.trim_end_matches('\n').to_string(),
+                lines: start..end,
+            })
+        })
+        .collect()
+}
+
+fn event_for(anchor: usize) -> Event<'static> {
+    Event::InlineHtml(format!("{EVENT_PREFIX}{anchor}{EVENT_SUFFIX}").into())
+}
+
+fn link_text(events: &[Event<'_>]) -> String {
+    let mut text = String::new();
+    let mut depth = 0usize;
+    for event in events {
+        match event {
+            Event::Start(Tag::Link { .. }) => depth += 1,
+            Event::End(TagEnd::Link) if depth == 0 => break,
+            Event::End(TagEnd::Link) => depth -= 1,
+            Event::Text(fragment) | Event::Code(fragment) => text.push_str(fragment),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
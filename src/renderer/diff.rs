@@ -0,0 +1,104 @@
+use super::anchors::DocumentMap;
+use super::terminal::TerminalRenderer;
+use crate::diff::{DiffKind, DocumentDiff};
+use crate::markdown::ParsedDocument;
+use crate::theme::{ThemeElement, create_style};
+use crate::utils::{display_width, strip_terminal_sequences};
+use anyhow::Result;
+
+impl TerminalRenderer {
+    /// Renders the merged source of `diff` and paints every line of a changed block with
+    /// its diff background, or marks it with `+`, `-`, or `~` when colors are off. The
+    /// returned map points at lines of the new version only.
+    pub(crate) fn render_diff(
+        &self,
+        document: ParsedDocument,
+        diff: &DocumentDiff,
+    ) -> Result<(String, DocumentMap)> {
+        let (rendered, mut map) = self.render_document_map(document)?;
+        let lines: Vec<&str> = rendered.split_inclusive('\n').collect();
+        let kinds = line_kinds(&lines, &map, diff);
+
+        let width = self.config.get_terminal_width();
+        let mut output = String::with_capacity(rendered.len());
+        for (line, kind) in lines.iter().zip(kinds) {
+            match kind {
+                Some(kind) => output.push_str(&self.paint_line(line, kind, width)),
+                None => output.push_str(line),
+            }
+        }
+
+        for source in &mut map.source_lines {
+            *source = source.and_then(|line| diff.new_lines.get(line - 1).copied().flatten());
+        }
+        Ok((output, map))
+    }
+
+    fn paint_line(&self, line: &str, kind: DiffKind, width: usize) -> String {
+        let (text, newline) = match line.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (line, ""),
+        };
+        if self.config.no_colors {
+            let sign = match kind {
+                DiffKind::Added => '+',
+                DiffKind::Deleted => '-',
+                DiffKind::Modified => '~',
+            };
+            return format!("{sign}{}{newline}", text.strip_prefix(' ').unwrap_or(text));
+        }
+
+        let element = match kind {
+            DiffKind::Added => ThemeElement::DiffAdded,
+            DiffKind::Deleted => ThemeElement::DiffDeleted,
+            DiffKind::Modified => ThemeElement::DiffModified,
+        };
+        let background = create_style(&self.theme, element).sequence();
+        let used = display_width(&strip_terminal_sequences(text));
+        let mut painted = background.clone();
+        painted.push_str(
+            &text
+                .replace("\x1b[0m", &format!("\x1b[0m{background}"))
+                .replace("\x1b[49m", &background),
+        );
+        painted.push_str(&" ".repeat(width.saturating_sub(used)));
+        painted.push_str("\x1b[0m");
+        painted.push_str(newline);
+        painted
+    }
+}
+
+/// Change of every rendered line, taken from the source line it starts after. Blank lines
+/// are painted only between lines of the same change, so separate blocks stay apart.
+fn line_kinds(lines: &[&str], map: &DocumentMap, diff: &DocumentDiff) -> Vec<Option<DiffKind>> {
+    let mut kind = None;
+    let mut kinds: Vec<Option<DiffKind>> = lines
+        .iter()
+        .enumerate()
+        .map(|(index, _)| {
+            if let Some(Some(source)) = map.source_lines.get(index) {
+                kind = diff.kinds.get(source - 1).copied().flatten();
+            }
+            kind
+        })
+        .collect();
+
+    let blank: Vec<bool> = lines
+        .iter()
+        .map(|line| strip_terminal_sequences(line).trim().is_empty())
+        .collect();
+    let mut previous = None;
+    for index in 0..kinds.len() {
+        if !blank[index] {
+            previous = kinds[index];
+            continue;
+        }
+        let next = (index..kinds.len())
+            .find(|&next| !blank[next])
+            .and_then(|next| kinds[next]);
+        if previous != next {
+            kinds[index] = None;
+        }
+    }
+    kinds
+}
@@ -6,6 +6,7 @@ use super::{
 use crate::block_spacing::BlockElement;
 use crate::inline_style::InlineStyleKind;
 use crate::math::is_math_language_hint;
+use crate::mermaid::is_mermaid_language_hint;
 use crate::terminal::AnsiStyle;
 use crate::utils::{display_width, strip_ansi};
 use regex::regex;
@@ -71,6 +72,7 @@ mod hint;
 mod inline;
 mod labels;
 mod line_numbers;
+mod mermaid;
 mod plaintext;
 mod pretty;
 mod rendering;
@@ -29,6 +29,13 @@ impl<'a> EventRenderer<'a> {
             self.code_block_language = None;
             return self.handle_math_code_block(&raw_code, language_hint.as_deref());
         }
+        if let Some(hint) = language_hint.as_deref()
+            && is_mermaid_language_hint(hint)
+            && self.render_mermaid_code_block(&raw_code, hint)?
+        {
+            self.code_block_language = None;
+            return Ok(());
+        }
         let treat_as_plaintext =
             self.should_render_code_block_as_plaintext(language_hint.as_deref());
         let (
@@ -90,28 +97,14 @@ impl<'a> EventRenderer<'a> {
 
         let code_starts_with_blank = raw_code.starts_with('\n');
 
-        let language_label =
-            if self.config.code_block_style.show_name || self.config.code_block_style.show_icon {
-                let (base_label, hint_key) = match language_hint.as_deref() {
-                    Some(raw) => {
-                        let syntax = self.resolve_syntax(Some(raw), &raw_code);
-                        let resolved = Self::resolve_language_label(raw, syntax);
-                        let custom_label = self
-                            .find_custom_code_block(raw)
-                            .and_then(|b| b.label.clone());
-                        (custom_label.unwrap_or(resolved), raw)
-                    }
-                    None => {
-                        let custom_label = self
-                            .find_custom_code_block("text")
-                            .and_then(|b| b.label.clone());
-                        (custom_label.unwrap_or_else(|| "Text".to_string()), "text")
-                    }
-                };
-                self.format_code_block_label(hint_key, &base_label)
-            } else {
-                None
-            };
+        let language_label = if self.config.code_block_style.show_name
+            || self.config.code_block_style.show_icon
+        {
+            let base_label = self.code_block_name(language_hint.as_deref(), &raw_code);
+            self.format_code_block_label(language_hint.as_deref().unwrap_or("text"), &base_label)
+        } else {
+            None
+        };
 
         self.code_block_language = None;
 
@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> EventRenderer<'a> {
-    pub(super) fn resolve_syntax<'s>(
+    pub(in crate::renderer::event) fn resolve_syntax<'s>(
         &'s self,
         language_hint: Option<&str>,
         code: &str,
@@ -51,6 +51,27 @@ impl<'a> EventRenderer<'a> {
         syntax_name.to_string()
     }
 
+    /// Returns the display name of a code block, preferring a custom label.
+    pub(in crate::renderer::event) fn code_block_name(
+        &self,
+        language_hint: Option<&str>,
+        code: &str,
+    ) -> String {
+        let Some(raw) = language_hint else {
+            return self
+                .find_custom_code_block("text")
+                .and_then(|block| block.label.clone())
+                .unwrap_or_else(|| "Text".to_string());
+        };
+        let custom_label = self
+            .find_custom_code_block(raw)
+            .and_then(|block| block.label.clone());
+        custom_label.unwrap_or_else(|| {
+            let syntax = self.resolve_syntax(Some(raw), code);
+            Self::resolve_language_label(raw, syntax)
+        })
+    }
+
     pub(super) fn find_custom_code_block(
         &self,
         hint: &str,
@@ -0,0 +1,77 @@
+use super::*;
+use crate::mermaid::render_mermaid;
+
+impl<'a> EventRenderer<'a> {
+    /// Draws a `mermaid` block as a diagram inside the usual code-block frame.
+    ///
+    /// Returns `false` when the diagram is not supported or does not fit, leaving the
+    /// block to be rendered as code.
+    pub(super) fn render_mermaid_code_block(
+        &mut self,
+        raw_code: &str,
+        language_hint: &str,
+    ) -> Result<bool> {
+        if self.table_state.is_some() {
+            return Ok(false);
+        }
+
+        let frame_width = match self.config.code_block_style.style {
+            CodeBlockStyle::Basic => BASIC_CODE_BLOCK_INDENT,
+            CodeBlockStyle::Simple => 2,
+            CodeBlockStyle::Pretty => 4,
+        };
+        let terminal_width = self.config.get_content_width();
+        let available =
+            terminal_width.saturating_sub(self.compute_code_block_context_width() + frame_width);
+        let Some(diagram) = render_mermaid(raw_code, available) else {
+            return Ok(false);
+        };
+        let diagram = diagram.trim_end_matches('\n');
+        if let Some(options) = self.config.code_line_numbers {
+            let number_width = diagram
+                .lines()
+                .count()
+                .to_string()
+                .len()
+                .max(self.config.code_line_number_width);
+            let gutter =
+                crate::renderer::line_numbers::gutter_width_for_number_width(number_width, options);
+            let widest = diagram.lines().map(display_width).max().unwrap_or(0);
+            if widest + gutter > available {
+                return Ok(false);
+            }
+        }
+
+        let style = create_style(self.theme, ThemeElement::Code);
+        let highlighted = diagram
+            .lines()
+            .map(|line| style.apply(line, self.config.no_colors))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let label = self.format_code_block_label(language_hint, "Mermaid");
+
+        let code_block_prefix = self.current_code_block_prefix();
+        let spacing = self.config.block_spacing.spacing(BlockElement::CodeBlock);
+        self.ensure_contextual_blank_lines_with_prefix(spacing.top, &code_block_prefix);
+
+        // Diagram rows must never wrap; they were already sized to fit.
+        let render_input = CodeBlockRenderInput::new(
+            &highlighted,
+            label.as_deref(),
+            false,
+            false,
+            WrapMode::None,
+            terminal_width,
+            diagram,
+        );
+        match self.config.code_block_style.style {
+            CodeBlockStyle::Basic => self.render_code_block_basic(render_input)?,
+            CodeBlockStyle::Simple => self.render_code_block_simple(render_input)?,
+            CodeBlockStyle::Pretty => self.render_code_block_pretty(render_input)?,
+        }
+
+        self.ensure_contextual_blank_lines_with_prefix(spacing.bottom, &code_block_prefix);
+        self.commit_pending_heading_placeholder_if_content();
+        Ok(true)
+    }
+}
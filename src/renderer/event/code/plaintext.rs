@@ -36,8 +36,11 @@ impl<'a> EventRenderer<'a> {
     pub(super) fn render_plaintext_code_block(&self, code: &str) -> Result<PlaintextRenderResult> {
         let mut nested_config = self.config.clone();
         nested_config.from_text = None;
+        nested_config.section = None;
+        nested_config.images = crate::cli::ImageMode::Off;
         nested_config.margin = crate::cli::HorizontalMargins::default();
         nested_config.line_numbers = None;
+        nested_config.source_map = false;
         nested_config.code_line_numbers = None;
         nested_config.code_line_number_width = 0;
         nested_config.line_number_gutter_width = 0;
@@ -93,4 +93,10 @@ pub(crate) struct EventRenderer<'a> {
     pub(crate) pending_callout_label_buffer: String,
     pub(crate) suppress_next_soft_break: bool,
     pub(crate) suppress_next_paragraph_break: bool,
+    /// Images drawn with a graphics protocol, indexed by image marker.
+    pub(crate) image_graphics: Vec<crate::renderer::graphics::Graphic>,
+    /// Nesting depth inside a drawn image, whose alt text is not printed.
+    pub(crate) image_alt_depth: usize,
+    /// Output offsets around the line break added after the last drawn image.
+    pub(crate) image_line_break: Option<(usize, usize)>,
 }
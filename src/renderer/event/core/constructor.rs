@@ -69,6 +69,9 @@ impl<'a> EventRenderer<'a> {
             pending_callout_label_buffer: String::new(),
             suppress_next_soft_break: false,
             suppress_next_paragraph_break: false,
+            image_graphics: Vec::new(),
+            image_alt_depth: 0,
+            image_line_break: None,
         }
     }
 }
@@ -31,6 +31,7 @@ impl<'a> EventRenderer<'a> {
     }
 
     pub(super) fn handle_item_end(&mut self) -> Result<()> {
+        self.discard_image_line_break();
         let mut start_index = self.output.len();
         let mut has_content = false;
         let mut was_ordered = false;
@@ -2,6 +2,7 @@ use super::*;
 
 impl<'a> EventRenderer<'a> {
     pub(super) fn handle_paragraph_end(&mut self) -> Result<()> {
+        self.discard_image_line_break();
         self.close_inline_backticks();
         self.finalize_pending_callout_label_override();
         let paragraph_start = self.current_paragraph_start.take();
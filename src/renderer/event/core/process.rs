@@ -14,6 +14,25 @@ impl<'a> EventRenderer<'a> {
             }
             return Ok(());
         }
+        if let Some(anchor) = crate::renderer::anchors::from_event(&event) {
+            self.push_internal_marker(&crate::renderer::line_numbers::encode_internal_anchor(
+                anchor,
+            ));
+            return Ok(());
+        }
+        if self.image_alt_depth > 0 {
+            match event {
+                Event::Start(Tag::Image { .. }) => self.image_alt_depth += 1,
+                Event::End(TagEnd::Image) => {
+                    self.image_alt_depth -= 1;
+                    if self.image_alt_depth == 0 {
+                        self.finish_inline_image();
+                    }
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
 
         if !matches!(event, Event::Text(_)) {
             self.reset_footnote_text_scan();
@@ -81,16 +100,20 @@ impl<'a> EventRenderer<'a> {
 
     pub(super) fn push_source_line_marker(&mut self, source_line: usize) {
         let marker = crate::renderer::line_numbers::encode_internal_marker(source_line);
+        self.push_internal_marker(&marker);
+    }
+
+    fn push_internal_marker(&mut self, marker: &str) {
         if self.in_code_block {
-            self.code_block_content.push_str(&marker);
+            self.code_block_content.push_str(marker);
         } else if self.in_link {
-            self.current_link_text.push_str(&marker);
+            self.current_link_text.push_str(marker);
         } else if let Some(table) = self.table_state.as_mut() {
-            table.current_cell.push_str(&marker);
+            table.current_cell.push_str(marker);
         } else if let Some(buffer) = self.pending_html_block_buffer.as_mut() {
-            buffer.content.push_str(&marker);
+            buffer.content.push_str(marker);
         } else {
-            self.output.push_str(&marker);
+            self.output.push_str(marker);
         }
     }
 
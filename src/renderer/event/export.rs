@@ -0,0 +1,177 @@
+use super::core::CalloutFold;
+use super::{EventRenderer, HighlightLines, MdvError, Result};
+use crate::math::{MathMode, is_math_language_hint, render_math};
+use crate::mermaid::{is_mermaid_language_hint, render_mermaid};
+use crate::renderer::standalone::css_color;
+use crate::theme::Color;
+use crate::utils::escape_html_text;
+use std::fmt::Write as _;
+use syntect::highlighting::{FontStyle, Style};
+use syntect::util::LinesWithEndings;
+
+/// Callout marker recognized at the start of a blockquote, for the export backends.
+pub(crate) struct CalloutHeading {
+    pub(crate) label: String,
+    pub(crate) title: String,
+    pub(crate) color: Color,
+    pub(crate) fold: Option<CalloutFold>,
+    /// Text after the marker that belongs to the first paragraph of the body.
+    pub(crate) body: String,
+}
+
+/// Callout box recognized at the start of a blockquote in the standalone HTML page.
+pub(crate) struct CalloutHtml {
+    pub(crate) open: String,
+    pub(crate) close: &'static str,
+    /// Text after the marker that belongs to the first paragraph of the body.
+    pub(crate) body: String,
+}
+
+impl<'a> EventRenderer<'a> {
+    /// Renders a code block for the standalone HTML page. Math and Mermaid blocks keep
+    /// their terminal drawings; other code is highlighted with the resolved code theme.
+    pub(crate) fn code_block_html(
+        &self,
+        code: &str,
+        language_hint: Option<&str>,
+    ) -> Result<String> {
+        if let Some(hint) = language_hint
+            && is_math_language_hint(hint)
+        {
+            let math = render_math(code.trim(), MathMode::Display);
+            return Ok(format!(
+                "<pre class=\"math math-display\">{}</pre>\n",
+                escape_html_text(&math)
+            ));
+        }
+        if let Some(hint) = language_hint
+            && is_mermaid_language_hint(hint)
+            && let Some(diagram) = render_mermaid(code, usize::MAX)
+        {
+            return Ok(format!(
+                "<pre class=\"diagram\">{}</pre>\n",
+                escape_html_text(&diagram)
+            ));
+        }
+
+        let mut html = String::from("<figure class=\"code-block\">");
+        if self.config.code_block_style.show_name {
+            let name = self.code_block_name(language_hint, code);
+            let _ = write!(html, "<figcaption>{}</figcaption>", escape_html_text(&name));
+        }
+        html.push_str("<pre><code>");
+        let syntax = self.resolve_syntax(language_hint, code);
+        let default_foreground = self.code_theme.syntect.settings.foreground;
+        let mut highlighter = HighlightLines::new(syntax, &self.code_theme.syntect);
+        for line in LinesWithEndings::from(code) {
+            let ranges = highlighter
+                .highlight_line(line, self.syntax_set)
+                .map_err(|e| MdvError::SyntaxError(e.to_string()))?;
+            for (style, text) in ranges {
+                let inherits_color = style.foreground.a == 0
+                    || default_foreground.is_some_and(|color| color == style.foreground);
+                push_highlighted_span(&mut html, style, text, inherits_color);
+            }
+        }
+        html.push_str("</code></pre></figure>\n");
+        Ok(html)
+    }
+
+    /// Recognizes a callout marker at the start of a blockquote's first paragraph.
+    pub(crate) fn callout_heading(&self, text: &str) -> Option<CalloutHeading> {
+        let marker = Self::parse_callout_marker(text)?;
+        Some(CalloutHeading {
+            title: self.callout_display_label(&marker.label, marker.label_override.as_deref()),
+            color: self.callout_color(marker.kind, &marker.label),
+            label: marker.label,
+            fold: marker.fold,
+            body: callout_body(text).to_string(),
+        })
+    }
+
+    /// Returns the pretty checkbox icon of a task state, when `--pretty-checkbox` is on.
+    pub(crate) fn task_marker_icon(&self, state: char) -> Option<String> {
+        let shape = self.config.pretty_checkbox?;
+        self.checkbox_icon(shape, state).0
+    }
+
+    /// Splits a custom task marker such as `[-]` from the start of a list item's text.
+    pub(crate) fn custom_task_marker<'b>(&self, text: &'b str) -> Option<(char, &'b str)> {
+        let (marker, remainder) = self.split_custom_task_marker_prefix(text)?;
+        Some((marker.chars().nth(1)?, remainder))
+    }
+
+    /// Opens a callout box for the standalone HTML page.
+    pub(crate) fn callout_html(&self, text: &str) -> Option<CalloutHtml> {
+        let heading = self.callout_heading(text)?;
+        let title = escape_html_text(&heading.title);
+        let style = css_color(&heading.color)
+            .map(|color| format!(" style=\"--callout: {color}\""))
+            .unwrap_or_default();
+        let class = format!("callout callout-{}", escape_html_text(&heading.label));
+
+        let (open, close) = match heading.fold {
+            None => (
+                format!("<div class=\"{class}\"{style}>\n<p class=\"callout-title\">{title}</p>\n"),
+                "</div>\n",
+            ),
+            Some(fold) => {
+                let open = if fold == CalloutFold::Expanded {
+                    " open"
+                } else {
+                    ""
+                };
+                (
+                    format!(
+                        "<details class=\"{class}\"{style}{open}>\n<summary class=\"callout-title\">{title}</summary>\n"
+                    ),
+                    "</details>\n",
+                )
+            }
+        };
+        Some(CalloutHtml {
+            open,
+            close,
+            body: heading.body,
+        })
+    }
+}
+
+/// Returns the text glued to a marker such as `[!note]text`; a title after a space
+/// belongs to the marker instead.
+fn callout_body(text: &str) -> &str {
+    let marker = text.trim_start();
+    let rest = marker
+        .find(']')
+        .map_or("", |closing| &marker[closing + 1..]);
+    let rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+    if rest.starts_with(char::is_whitespace) {
+        ""
+    } else {
+        rest
+    }
+}
+
+fn push_highlighted_span(html: &mut String, style: Style, text: &str, inherits_color: bool) {
+    let mut css = String::new();
+    if !inherits_color {
+        let color = style.foreground;
+        let _ = write!(css, "color:#{:02x}{:02x}{:02x};", color.r, color.g, color.b);
+    }
+    if style.font_style.contains(FontStyle::BOLD) {
+        css.push_str("font-weight:bold;");
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        css.push_str("font-style:italic;");
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        css.push_str("text-decoration:underline;");
+    }
+
+    let text = escape_html_text(text);
+    if css.is_empty() {
+        html.push_str(&text);
+    } else {
+        let _ = write!(html, "<span style=\"{css}\">{text}</span>");
+    }
+}
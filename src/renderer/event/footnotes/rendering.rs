@@ -174,6 +174,8 @@ impl<'a> EventRenderer<'a> {
 
         let mut nested_config = self.config.clone();
         nested_config.footnote_style = FootnoteStyle::Endnotes;
+        // Footnote bodies are rewrapped as text, which would break image rows.
+        nested_config.images = crate::cli::ImageMode::Off;
 
         let mut nested_renderer =
             EventRenderer::new(&nested_config, self.theme, self.syntax_set, self.code_theme);
@@ -12,6 +12,7 @@ fn is_quote_prefix_char(ch: char) -> bool {
 
 fn strip_layout_metadata(line: &str) -> String {
     let clean = strip_ansi(line);
+    let clean = crate::renderer::line_numbers::strip_internal_anchors(&clean).0;
     crate::renderer::line_numbers::strip_internal_markers(&clean).0
 }
 
@@ -16,7 +16,17 @@ impl<'a> EventRenderer<'a> {
         kind: CalloutKind,
         label: &str,
     ) -> AnsiStyle {
-        let color = if kind == CalloutKind::Properties {
+        AnsiStyle::new()
+            .fg(self.callout_color(kind, label).into())
+            .bold()
+    }
+
+    pub(in crate::renderer::event) fn callout_color(
+        &self,
+        kind: CalloutKind,
+        label: &str,
+    ) -> crate::theme::Color {
+        if kind == CalloutKind::Properties {
             self.theme.front_matter_title_color().clone()
         } else if let Some(custom) = self.config.custom_callouts.get(label) {
             custom
@@ -28,9 +38,7 @@ impl<'a> EventRenderer<'a> {
                 .get(&kind)
                 .cloned()
                 .unwrap_or_else(|| self.theme.text.clone())
-        };
-
-        AnsiStyle::new().fg(color.into()).bold()
+        }
     }
 
     pub(in crate::renderer::event) fn unknown_callout_color(&self) -> crate::theme::Color {
@@ -1,5 +1,7 @@
 use super::{CowStr, EventRenderer, Result, ThemeElement, create_style};
-use crate::utils::strip_ansi;
+use crate::renderer::graphics::{CellGeometry, ImageProtocol, local_image_path, prepare_image};
+use crate::renderer::line_numbers::encode_internal_image;
+use crate::utils::{display_width, strip_ansi};
 
 pub(super) fn media_marker(dest_url: &str) -> &'static str {
     if let Some(marker) = media_marker_from_data_uri(dest_url) {
@@ -38,15 +40,11 @@ fn needs_media_marker_leading_separator(buffer: &str) -> bool {
         .rsplit_once('\n')
         .map(|(_, line)| line)
         .unwrap_or(buffer);
-    let clean_line = strip_ansi(current_line);
-
-    let has_visible_text = clean_line
-        .chars()
-        .any(|ch| !ch.is_whitespace() && ch != '│' && ch != '┃');
-    if !has_visible_text {
+    if !has_visible_text(current_line) {
         return false;
     }
 
+    let clean_line = strip_ansi(current_line);
     if clean_line
         .chars()
         .next_back()
@@ -63,6 +61,13 @@ fn needs_media_marker_leading_separator(buffer: &str) -> bool {
     !matches!(last_visible, '(' | '[' | '{')
 }
 
+/// Whether a line has content other than blockquote bars and indentation.
+fn has_visible_text(line: &str) -> bool {
+    strip_ansi(line)
+        .chars()
+        .any(|ch| !ch.is_whitespace() && ch != '│' && ch != '┃')
+}
+
 fn media_marker_from_data_uri(dest_url: &str) -> Option<&'static str> {
     let value = dest_url.trim();
     if !value
@@ -262,6 +267,10 @@ impl<'a> EventRenderer<'a> {
         }
 
         self.note_paragraph_content();
+        if self.render_inline_image(dest_url.as_ref()) {
+            self.commit_pending_heading_placeholder_if_content();
+            return Ok(());
+        }
 
         // Ensure correct indentation/prefix when an image starts a visual line.
         // Paragraph start may have added spaces, but when inside lists/quotes
@@ -290,4 +299,77 @@ impl<'a> EventRenderer<'a> {
         // Image handling is completed in start
         Ok(())
     }
+
+    /// Draws a local image on its own rows for `--images`.
+    ///
+    /// Returns `false` when the image keeps its text marker: images are disabled, inside
+    /// links or headings, remote, or cannot be decoded.
+    fn render_inline_image(&mut self, dest_url: &str) -> bool {
+        if self.in_link || self.current_heading_start.is_some() || self.config.no_colors {
+            return false;
+        }
+        let Some(protocol) = ImageProtocol::from_mode(self.config.images) else {
+            return false;
+        };
+        let Some(path) = local_image_path(dest_url, self.config.document_dir.as_deref()) else {
+            return false;
+        };
+
+        let prefix_width = display_width(&strip_ansi(&self.current_line_prefix()));
+        let max_columns = self
+            .effective_text_width()
+            .saturating_sub(prefix_width)
+            .max(1);
+        let image = match prepare_image(&path, protocol, max_columns, CellGeometry::detect()) {
+            Ok(image) => image,
+            Err(error) => {
+                log::debug!("Keeping the marker for image '{dest_url}': {error:#}");
+                return false;
+            }
+        };
+
+        // Images start at the content column: right after a list marker, or on a new line
+        // when the current one already has text.
+        let line_start_idx = self.output.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let current_line = &self.output[line_start_idx..];
+        if !has_visible_text(current_line) {
+            self.output.truncate(line_start_idx);
+            self.push_indent_for_line_start();
+        } else if display_width(&strip_ansi(current_line)) != prefix_width {
+            self.push_newline_with_context();
+        }
+        let marker = image
+            .graphic
+            .is_some()
+            .then(|| encode_internal_image(self.image_graphics.len()));
+        for (index, row) in image.rows.iter().enumerate() {
+            if index > 0 {
+                self.push_newline_with_context();
+            }
+            self.output.push_str(row);
+            if let Some(marker) = &marker {
+                self.output.push_str(marker);
+            }
+        }
+        self.image_graphics.extend(image.graphic);
+        self.image_alt_depth = 1;
+        true
+    }
+
+    /// Starts a new line after a drawn image, taking the place of a following soft break.
+    pub(super) fn finish_inline_image(&mut self) {
+        let before = self.output.len();
+        self.push_newline_with_context();
+        self.image_line_break = Some((before, self.output.len()));
+        self.suppress_next_soft_break = true;
+    }
+
+    /// Drops the line break after an image that ends its block, which adds its own.
+    pub(super) fn discard_image_line_break(&mut self) {
+        if let Some((before, after)) = self.image_line_break.take()
+            && after == self.output.len()
+        {
+            self.output.truncate(before);
+        }
+    }
 }
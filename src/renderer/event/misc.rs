@@ -1,5 +1,6 @@
 use super::{CowStr, EventRenderer, PRETTY_ACCENT_COLOR, Result, ThemeElement, create_style};
 use crate::block_spacing::BlockElement;
+use crate::cli::CheckboxShape;
 use crate::terminal::AnsiStyle;
 use crate::utils::{display_width, strip_ansi};
 
@@ -199,7 +200,27 @@ impl<'a> EventRenderer<'a> {
             .pretty_checkbox
             .expect("pretty checkbox rendering is enabled");
 
-        let (icon, custom_color) = match self.config.checkbox_overrides.get(&state) {
+        let (icon, custom_color) = self.checkbox_icon(shape, state);
+
+        let style = match custom_color {
+            Some(color) => create_style(self.theme, ThemeElement::ListMarker).fg(color.into()),
+            None => create_style(self.theme, ThemeElement::ListMarker),
+        };
+
+        match icon {
+            Some(glyph) => style.apply(&glyph, self.config.no_colors),
+            None => style.apply(&format!("[{state}]"), self.config.no_colors),
+        }
+    }
+
+    /// Resolves the pretty checkbox icon and color of a state, `--custom-checkbox`
+    /// overrides first.
+    pub(in crate::renderer::event) fn checkbox_icon(
+        &self,
+        shape: CheckboxShape,
+        state: char,
+    ) -> (Option<String>, Option<crate::theme::Color>) {
+        match self.config.checkbox_overrides.get(&state) {
             Some(ov) => {
                 let icon = ov.icon.clone().or_else(|| {
                     crate::checkbox::default_icon(shape, state)
@@ -212,16 +233,6 @@ impl<'a> EventRenderer<'a> {
                 crate::checkbox::default_icon(shape, state).map(|ch| ch.to_string()),
                 None,
             ),
-        };
-
-        let style = match custom_color {
-            Some(color) => create_style(self.theme, ThemeElement::ListMarker).fg(color.into()),
-            None => create_style(self.theme, ThemeElement::ListMarker),
-        };
-
-        match icon {
-            Some(glyph) => style.apply(&glyph, self.config.no_colors),
-            None => style.apply(&format!("[{state}]"), self.config.no_colors),
         }
     }
 
@@ -1,6 +1,7 @@
 mod code;
 mod core;
 mod definition_lists;
+mod export;
 mod footnotes;
 mod formatting;
 mod headings;
@@ -16,7 +17,9 @@ mod text;
 
 use crossterm::style::Color as CrosstermColor;
 
-pub(crate) use core::{CapturedReferenceBlock, DeferredLinkReferenceBlock, EventRenderer};
+pub(crate) use core::{
+    CalloutFold, CapturedReferenceBlock, DeferredLinkReferenceBlock, EventRenderer,
+};
 pub(super) use core::{HtmlBlockBuffer, TableInlineUrlSegment, TableInlineUrlTarget, TableState};
 use definition_lists::DefinitionListState;
 pub(super) use footnotes::FootnoteDefinition;
@@ -15,11 +15,11 @@ enum CalloutBufferEval {
     NotCallout,
 }
 
-struct CalloutMarker {
-    kind: CalloutKind,
-    label: String,
-    label_override: Option<String>,
-    fold: Option<CalloutFold>,
+pub(in crate::renderer::event) struct CalloutMarker {
+    pub(in crate::renderer::event) kind: CalloutKind,
+    pub(in crate::renderer::event) label: String,
+    pub(in crate::renderer::event) label_override: Option<String>,
+    pub(in crate::renderer::event) fold: Option<CalloutFold>,
     trailing: Option<String>,
     allow_label_override: bool,
     suppress_paragraph_break: bool,
@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> EventRenderer<'a> {
-    pub(super) fn split_custom_task_marker_prefix<'b>(
+    pub(in crate::renderer::event) fn split_custom_task_marker_prefix<'b>(
         &self,
         text: &'b str,
     ) -> Option<(&'b str, &'b str)> {
@@ -17,7 +17,7 @@ impl<'a> EventRenderer<'a> {
         matches!(self.split_custom_task_marker_prefix(text), Some((_, "")))
     }
 
-    pub(super) fn parse_callout_marker(text: &str) -> Option<CalloutMarker> {
+    pub(in crate::renderer::event) fn parse_callout_marker(text: &str) -> Option<CalloutMarker> {
         let trimmed = text.trim_start();
         if !trimmed.starts_with("[!") {
             return None;
@@ -1,15 +1,45 @@
+use super::anchors::{
+    DocumentMap, code_block_sources, insert_anchor_events, locate_anchors, locate_code_blocks,
+};
 use super::terminal::TerminalRenderer;
 use crate::cli::FrontMatterMode;
 use crate::config::Config;
 use crate::markdown::{FrontMatter, MarkdownProcessor, ParsedDocument};
-use crate::utils::escape_html_text;
+use crate::utils::{escape_html_text, strip_terminal_sequences};
 use anyhow::Result;
 use pulldown_cmark::{CodeBlockKind, CowStr, Event, Tag, TagEnd};
 use serde_yaml::{Mapping, Value};
 
 impl TerminalRenderer {
-    pub(crate) fn render_document(&self, document: ParsedDocument) -> Result<String> {
-        self.render(document_events(document, &self.config, false)?)
+    /// Renders the document and locates its headings, links, and code blocks in the output.
+    pub(crate) fn render_document_map(
+        &self,
+        document: ParsedDocument,
+    ) -> Result<(String, DocumentMap)> {
+        let events = document_events(document, &self.config, false)?;
+        let (events, targets) = insert_anchor_events(events);
+        let code_blocks = if self.config.source_map {
+            code_block_sources(&events)
+        } else {
+            Vec::new()
+        };
+        let (output, source_lines) = self.render_source_mapped(events)?;
+        let (output, mut map) = locate_anchors(&output, targets);
+        if self.config.source_map {
+            map.code_blocks = locate_code_blocks(code_blocks, &source_lines);
+            map.source_lines = source_lines;
+        }
+        Ok((output, map))
+    }
+
+    /// Renders the document as plain text. Links are expected in a reference style with
+    /// colors disabled; any escape sequence a code path still emits is removed here.
+    pub(crate) fn to_plain_text(&self, document: ParsedDocument) -> Result<String> {
+        let (output, _) = self.render_document_map(document)?;
+        Ok(strip_terminal_sequences(&output)
+            .lines()
+            .map(|line| format!("{}\n", line.trim_end()))
+            .collect())
     }
 
     pub(crate) fn to_html_document(&self, document: ParsedDocument) -> Result<String> {
@@ -17,7 +47,7 @@ impl TerminalRenderer {
     }
 }
 
-fn document_events(
+pub(super) fn document_events(
     mut document: ParsedDocument,
     config: &Config,
     html: bool,
@@ -139,7 +169,9 @@ fn metadata_events(markdown: &str, config: &Config) -> Result<Vec<Event<'static>
     let mut metadata_config = config.clone();
     metadata_config.reverse = false;
     metadata_config.from_text = None;
+    metadata_config.section = None;
     metadata_config.line_numbers = None;
+    metadata_config.source_map = false;
     MarkdownProcessor::new(&metadata_config).parse(markdown)
 }
 
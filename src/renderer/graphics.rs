@@ -0,0 +1,273 @@
+use super::line_numbers::strip_internal_images;
+use crate::cli::ImageMode;
+use crate::utils::{display_width, percent_decode, strip_ansi, url_scheme};
+use anyhow::{Context, Result};
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+mod blocks;
+mod iterm;
+mod kitty;
+mod sixel;
+
+/// Cell size assumed when the terminal does not report its pixel dimensions.
+const DEFAULT_CELL_PIXELS: (u32, u32) = (10, 20);
+/// Row limit used when the terminal height is unknown.
+const DEFAULT_MAX_ROWS: usize = 40;
+/// Each half-block cell shows one pixel column and two pixel rows.
+const BLOCK_CELL_PIXELS: (u32, u32) = (1, 2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImageProtocol {
+    Kitty,
+    Iterm,
+    Sixel,
+    Blocks,
+}
+
+impl ImageProtocol {
+    /// Resolves `--images`, returning `None` when images keep their text markers.
+    pub(crate) fn from_mode(mode: ImageMode) -> Option<Self> {
+        match mode {
+            ImageMode::Off => None,
+            ImageMode::Auto => Some(Self::detect(|name| std::env::var(name).ok())),
+            ImageMode::Kitty => Some(Self::Kitty),
+            ImageMode::Iterm => Some(Self::Iterm),
+            ImageMode::Sixel => Some(Self::Sixel),
+            ImageMode::Blocks => Some(Self::Blocks),
+        }
+    }
+
+    /// Picks a protocol from the variables terminal emulators export.
+    ///
+    /// Multiplexers do not forward graphics reliably, so they always get half-blocks.
+    fn detect(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+            return Self::Blocks;
+        }
+
+        if var("KITTY_WINDOW_ID").is_some()
+            || matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+            || term_program.eq_ignore_ascii_case("ghostty")
+        {
+            Self::Kitty
+        } else if matches!(term_program.as_str(), "iTerm.app" | "WezTerm")
+            || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            Self::Iterm
+        } else if term.contains("sixel")
+            || ["foot", "mlterm", "contour"]
+                .iter()
+                .any(|prefix| term.starts_with(prefix))
+        {
+            Self::Sixel
+        } else {
+            Self::Blocks
+        }
+    }
+}
+
+/// Pixel size of a terminal cell and the tallest image, in rows, that fits on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CellGeometry {
+    pub(crate) cell_width: u32,
+    pub(crate) cell_height: u32,
+    pub(crate) max_rows: usize,
+}
+
+impl Default for CellGeometry {
+    fn default() -> Self {
+        Self {
+            cell_width: DEFAULT_CELL_PIXELS.0,
+            cell_height: DEFAULT_CELL_PIXELS.1,
+            max_rows: DEFAULT_MAX_ROWS,
+        }
+    }
+}
+
+impl CellGeometry {
+    pub(crate) fn detect() -> Self {
+        let mut geometry = Self::default();
+        let Ok(size) = crossterm::terminal::window_size() else {
+            return geometry;
+        };
+        if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 {
+            geometry.cell_width = u32::from(size.width / size.columns).max(1);
+            geometry.cell_height = u32::from(size.height / size.rows).max(1);
+        }
+        if size.rows > 2 {
+            geometry.max_rows = usize::from(size.rows - 2);
+        }
+        geometry
+    }
+}
+
+/// Image prepared for the renderer.
+#[derive(Debug)]
+pub(crate) struct InlineImage {
+    /// Text printed on each terminal row the image occupies.
+    pub(crate) rows: Vec<String>,
+    /// Picture drawn over the rows by a graphics protocol; the rows are then empty.
+    pub(crate) graphic: Option<Graphic>,
+}
+
+/// Escape sequence that draws an image over `rows` reserved terminal rows.
+#[derive(Debug, Clone)]
+pub(crate) struct Graphic {
+    pub(crate) rows: usize,
+    sequence: String,
+}
+
+/// Resolves an image destination to a local file, relative to the document directory.
+///
+/// Remote and `data:` URLs return `None`.
+pub(crate) fn local_image_path(dest_url: &str, document_dir: Option<&Path>) -> Option<PathBuf> {
+    let location = dest_url.trim();
+    let location = location.split_once('?').map_or(location, |(path, _)| path);
+    if location.is_empty() || location.starts_with('#') {
+        return None;
+    }
+
+    let path = match url_scheme(location) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file") => {
+            let path = &location[scheme.len() + 1..];
+            PathBuf::from(percent_decode(path.strip_prefix("//").unwrap_or(path)))
+        }
+        Some(_) => return None,
+        None => PathBuf::from(percent_decode(location)),
+    };
+    Some(match document_dir {
+        Some(directory) if path.is_relative() => directory.join(path),
+        _ => path,
+    })
+}
+
+/// Decodes a PNG, JPEG or GIF file and encodes it for `protocol`.
+///
+/// The image is scaled down to `max_columns` cells and the screen height, never up.
+pub(crate) fn prepare_image(
+    path: &Path,
+    protocol: ImageProtocol,
+    max_columns: usize,
+    geometry: CellGeometry,
+) -> Result<InlineImage> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let format = image::guess_format(&bytes)?;
+    let image = image::load_from_memory_with_format(&bytes, format)
+        .with_context(|| format!("Failed to decode {}", path.display()))?;
+
+    if protocol == ImageProtocol::Blocks {
+        let (columns, rows) = fit_cells(&image, max_columns, BLOCK_CELL_PIXELS, geometry.max_rows);
+        return Ok(InlineImage {
+            rows: blocks::encode(&image, columns, rows),
+            graphic: None,
+        });
+    }
+
+    let cell = (geometry.cell_width, geometry.cell_height);
+    let (columns, rows) = fit_cells(&image, max_columns, cell, geometry.max_rows);
+    let sequence = match protocol {
+        ImageProtocol::Kitty => kitty::encode(&png_bytes(bytes, format, &image)?, columns, rows),
+        ImageProtocol::Iterm => iterm::encode(&bytes, columns, rows),
+        ImageProtocol::Sixel => {
+            let width = u32::try_from(columns)?.saturating_mul(cell.0);
+            let height = u32::try_from(rows)?.saturating_mul(cell.1);
+            let scaled = image.resize(width, height, image::imageops::FilterType::Triangle);
+            sixel::encode(&scaled.to_rgba8())
+        }
+        ImageProtocol::Blocks => unreachable!("half-blocks are handled above"),
+    };
+
+    Ok(InlineImage {
+        rows: vec![String::new(); rows],
+        graphic: Some(Graphic { rows, sequence }),
+    })
+}
+
+/// Replaces the image markers on reserved rows with the sequences that draw the images.
+///
+/// Every reserved row carries its image's marker. Once the last row has been printed the
+/// screen has scrolled, so the picture is drawn upward from the end of that row, at the
+/// column of the marker; `left_columns` counts the margin and gutter added later. An image
+/// whose rows did not all survive layout is left out rather than drawn over other text.
+pub(super) fn place_graphics(output: String, graphics: &[Graphic], left_columns: usize) -> String {
+    if graphics.is_empty() {
+        return output;
+    }
+
+    let mut remaining_rows: Vec<usize> = graphics.iter().map(|graphic| graphic.rows).collect();
+    let mut placed = String::with_capacity(output.len());
+    for line in output.split_inclusive('\n') {
+        let (content, newline) = line
+            .strip_suffix('\n')
+            .map_or((line, ""), |content| (content, "\n"));
+        let (content, images) = strip_internal_images(content);
+        placed.push_str(&content);
+        for (offset, index) in images {
+            let Some(remaining) = remaining_rows.get_mut(index) else {
+                continue;
+            };
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                let column = left_columns + display_width(&strip_ansi(&content[..offset]));
+                placed.push_str(&draw_upward(&graphics[index], column));
+            }
+        }
+        placed.push_str(newline);
+    }
+    placed
+}
+
+fn draw_upward(graphic: &Graphic, column: usize) -> String {
+    let mut sequence = String::from("\x1b7\r");
+    if column > 0 {
+        sequence.push_str(&format!("\x1b[{column}C"));
+    }
+    if graphic.rows > 1 {
+        sequence.push_str(&format!("\x1b[{}A", graphic.rows - 1));
+    }
+    sequence.push_str(&graphic.sequence);
+    sequence.push_str("\x1b8");
+    sequence
+}
+
+/// Returns the columns and rows an image occupies at its native size, shrunk to fit.
+fn fit_cells(
+    image: &DynamicImage,
+    max_columns: usize,
+    (cell_width, cell_height): (u32, u32),
+    max_rows: usize,
+) -> (usize, usize) {
+    let (width, height) = (image.width().max(1), image.height().max(1));
+    let rows_for = |columns: usize| {
+        let rows = (f64::from(height) * columns as f64 * f64::from(cell_width))
+            / (f64::from(width) * f64::from(cell_height));
+        (rows.ceil() as usize).max(1)
+    };
+
+    let native_columns = width.div_ceil(cell_width) as usize;
+    let mut columns = native_columns.min(max_columns).max(1);
+    let mut rows = rows_for(columns);
+    let max_rows = max_rows.max(1);
+    if rows > max_rows {
+        columns = (columns * max_rows / rows).max(1);
+        rows = rows_for(columns).min(max_rows);
+    }
+    (columns, rows)
+}
+
+fn png_bytes(bytes: Vec<u8>, format: ImageFormat, image: &DynamicImage) -> Result<Vec<u8>> {
+    if format == ImageFormat::Png {
+        return Ok(bytes);
+    }
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests;
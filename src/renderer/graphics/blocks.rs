@@ -0,0 +1,59 @@
+use image::DynamicImage;
+use image::imageops::FilterType;
+use std::fmt::Write as _;
+
+const UPPER_HALF: char = '▀';
+const LOWER_HALF: char = '▄';
+/// Pixels at least this opaque are drawn; the rest show the terminal background.
+const ALPHA_THRESHOLD: u8 = 128;
+
+type Rgb = [u8; 3];
+
+/// Draws the image with `▀`/`▄` cells whose foreground and background carry two pixels.
+pub(super) fn encode(image: &DynamicImage, columns: usize, rows: usize) -> Vec<String> {
+    let pixels = image
+        .resize_exact(columns as u32, rows as u32 * 2, FilterType::Triangle)
+        .to_rgba8();
+    let visible = |x: usize, y: usize| {
+        let pixel = pixels.get_pixel(x as u32, y as u32);
+        (pixel[3] >= ALPHA_THRESHOLD).then_some([pixel[0], pixel[1], pixel[2]])
+    };
+
+    (0..rows)
+        .map(|row| {
+            let mut line = String::new();
+            let mut current: Option<(Option<Rgb>, Option<Rgb>)> = None;
+            for column in 0..columns {
+                let (top, bottom) = (visible(column, row * 2), visible(column, row * 2 + 1));
+                let (glyph, colors) = match (top, bottom) {
+                    (Some(top), Some(bottom)) => (UPPER_HALF, (Some(top), Some(bottom))),
+                    (Some(top), None) => (UPPER_HALF, (Some(top), None)),
+                    (None, Some(bottom)) => (LOWER_HALF, (Some(bottom), None)),
+                    (None, None) => (' ', (None, None)),
+                };
+                if current != Some(colors) {
+                    push_colors(&mut line, colors);
+                    current = Some(colors);
+                }
+                line.push(glyph);
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+fn push_colors(line: &mut String, (foreground, background): (Option<Rgb>, Option<Rgb>)) {
+    match foreground {
+        Some([r, g, b]) => {
+            let _ = write!(line, "\x1b[38;2;{r};{g};{b}m");
+        }
+        None => line.push_str("\x1b[39m"),
+    }
+    match background {
+        Some([r, g, b]) => {
+            let _ = write!(line, "\x1b[48;2;{r};{g};{b}m");
+        }
+        None => line.push_str("\x1b[49m"),
+    }
+}
@@ -0,0 +1,11 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Sends the original file; the terminal decodes it and scales it to `columns`×`rows` cells.
+pub(super) fn encode(bytes: &[u8], columns: usize, rows: usize) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=1:{}\x07",
+        bytes.len(),
+        STANDARD.encode(bytes)
+    )
+}
@@ -0,0 +1,26 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Largest base64 payload the protocol accepts in one escape sequence.
+const CHUNK_SIZE: usize = 4096;
+
+/// Transmits and displays a PNG scaled to `columns`×`rows` cells without moving the cursor.
+pub(super) fn encode(png: &[u8], columns: usize, rows: usize) -> String {
+    let data = STANDARD.encode(png);
+    let chunks = data.len().div_ceil(CHUNK_SIZE).max(1);
+    let mut sequence = String::with_capacity(data.len() + chunks * 16 + 48);
+    for chunk in 0..chunks {
+        let more = u8::from(chunk + 1 < chunks);
+        if chunk == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={columns},r={rows},m={more};"
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={more};"));
+        }
+        let start = chunk * CHUNK_SIZE;
+        sequence.push_str(&data[start..data.len().min(start + CHUNK_SIZE)]);
+        sequence.push_str("\x1b\\");
+    }
+    sequence
+}
@@ -0,0 +1,83 @@
+use image::RgbaImage;
+use std::fmt::Write as _;
+
+/// Pixels at least this opaque are drawn; the rest stay transparent.
+const ALPHA_THRESHOLD: u8 = 128;
+/// Levels per channel of the color cube used as the palette.
+const LEVELS: usize = 6;
+
+/// Encodes the image as a sixel sequence using a 6×6×6 color cube.
+pub(super) fn encode(image: &RgbaImage) -> String {
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let colors: Vec<Option<u8>> = image.pixels().map(|pixel| cube_index(pixel.0)).collect();
+
+    // P2=1 keeps transparent pixels unchanged instead of painting the background color.
+    let mut sequence = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    let mut used = [false; LEVELS * LEVELS * LEVELS];
+    for color in colors.iter().flatten() {
+        used[usize::from(*color)] = true;
+    }
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let percent = |level: usize| level * 100 / (LEVELS - 1);
+        let _ = write!(
+            sequence,
+            "#{index};2;{};{};{}",
+            percent(index / (LEVELS * LEVELS)),
+            percent(index / LEVELS % LEVELS),
+            percent(index % LEVELS)
+        );
+    }
+
+    for band in (0..height).step_by(6) {
+        let band_height = 6.min(height - band);
+        let mut band_colors: Vec<u8> = (band..band + band_height)
+            .flat_map(|y| colors[y * width..(y + 1) * width].iter().flatten().copied())
+            .collect();
+        band_colors.sort_unstable();
+        band_colors.dedup();
+
+        for (position, color) in band_colors.iter().enumerate() {
+            if position > 0 {
+                sequence.push('$');
+            }
+            let _ = write!(sequence, "#{color}");
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = (0..band_height)
+                    .filter(|row| colors[(band + row) * width + x] == Some(*color))
+                    .fold(0u8, |bits, row| bits | (1 << row));
+                let sixel = char::from(63 + bits);
+                run = match run {
+                    Some((previous, count)) if previous == sixel => Some((sixel, count + 1)),
+                    Some((previous, count)) => {
+                        push_run(&mut sequence, previous, count);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((sixel, count)) = run {
+                push_run(&mut sequence, sixel, count);
+            }
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+fn cube_index([r, g, b, a]: [u8; 4]) -> Option<u8> {
+    if a < ALPHA_THRESHOLD {
+        return None;
+    }
+    let level = |value: u8| (usize::from(value) * (LEVELS - 1) + 127) / 255;
+    u8::try_from(level(r) * LEVELS * LEVELS + level(g) * LEVELS + level(b)).ok()
+}
+
+fn push_run(sequence: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        let _ = write!(sequence, "!{count}{sixel}");
+    } else {
+        sequence.extend(std::iter::repeat_n(sixel, count));
+    }
+}
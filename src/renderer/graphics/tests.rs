@@ -0,0 +1,129 @@
+use super::*;
+use image::{Rgba, RgbaImage};
+use std::collections::HashMap;
+
+fn environment(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let variables: HashMap<String, String> = pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| variables.get(name).cloned()
+}
+
+fn write_png(directory: &Path, name: &str, width: u32, height: u32) -> PathBuf {
+    let path = directory.join(name);
+    RgbaImage::from_fn(width, height, |x, _| {
+        if x < width / 2 {
+            Rgba([255, 0, 0, 255])
+        } else {
+            Rgba([0, 0, 255, 0])
+        }
+    })
+    .save(&path)
+    .unwrap();
+    path
+}
+
+#[test]
+fn detects_protocols_from_terminal_variables() {
+    let detect = |pairs: &[(&str, &str)]| ImageProtocol::detect(environment(pairs));
+
+    assert_eq!(detect(&[("KITTY_WINDOW_ID", "1")]), ImageProtocol::Kitty);
+    assert_eq!(detect(&[("TERM", "xterm-ghostty")]), ImageProtocol::Kitty);
+    assert_eq!(
+        detect(&[("TERM_PROGRAM", "iTerm.app")]),
+        ImageProtocol::Iterm
+    );
+    assert_eq!(detect(&[("LC_TERMINAL", "iTerm2")]), ImageProtocol::Iterm);
+    assert_eq!(detect(&[("TERM", "foot")]), ImageProtocol::Sixel);
+    assert_eq!(detect(&[("TERM", "xterm-256color")]), ImageProtocol::Blocks);
+    assert_eq!(
+        detect(&[
+            ("KITTY_WINDOW_ID", "1"),
+            ("TMUX", "/tmp/tmux-1000/default,1,0")
+        ]),
+        ImageProtocol::Blocks
+    );
+}
+
+#[test]
+fn resolves_local_image_paths_against_the_document() {
+    let directory = Path::new("docs");
+
+    assert_eq!(
+        local_image_path("images/arch%20diagram.png?raw=1", Some(directory)),
+        Some(PathBuf::from("docs/images/arch diagram.png"))
+    );
+    assert_eq!(
+        local_image_path("file:///tmp/diagram.png", Some(directory)),
+        Some(PathBuf::from("/tmp/diagram.png"))
+    );
+    assert_eq!(local_image_path("https://example.com/a.png", None), None);
+    assert_eq!(local_image_path("data:image/png;base64,AAAA", None), None);
+}
+
+#[test]
+fn images_shrink_to_the_available_width_and_height() {
+    let wide = DynamicImage::new_rgba8(400, 100);
+    let tall = DynamicImage::new_rgba8(100, 1000);
+
+    assert_eq!(fit_cells(&wide, 80, (10, 20), 40), (40, 5));
+    assert_eq!(fit_cells(&wide, 20, (10, 20), 40), (20, 3));
+    assert_eq!(fit_cells(&tall, 80, (10, 20), 10), (2, 10));
+    assert_eq!(fit_cells(&wide, 30, BLOCK_CELL_PIXELS, 40), (30, 4));
+}
+
+#[test]
+fn half_blocks_keep_transparent_pixels_empty() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = write_png(directory.path(), "half.png", 4, 2);
+
+    let image = prepare_image(&path, ImageProtocol::Blocks, 80, CellGeometry::default()).unwrap();
+
+    assert!(image.graphic.is_none());
+    assert_eq!(image.rows.len(), 1);
+    assert_eq!(strip_ansi(&image.rows[0]), "▀▀  ");
+    assert!(image.rows[0].starts_with("\x1b[38;2;255;0;0m\x1b[48;2;255;0;0m"));
+}
+
+#[test]
+fn kitty_transmits_png_in_chunks() {
+    let sequence = kitty::encode(&[0; 4000], 12, 3);
+    let chunks: Vec<&str> = sequence.split("\x1b\\").filter(|s| !s.is_empty()).collect();
+
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks[0].starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=12,r=3,m=1;"));
+    assert!(chunks[1].starts_with("\x1b_Gm=0;"));
+}
+
+#[test]
+fn sixel_encodes_palette_and_transparent_pixels() {
+    let mut image = RgbaImage::new(5, 7);
+    for x in 0..5 {
+        image.put_pixel(x, 0, Rgba([255, 0, 0, 255]));
+    }
+
+    let sequence = sixel::encode(&image);
+
+    assert!(sequence.starts_with("\x1bP0;1;0q\"1;1;5;7#180;2;100;0;0"));
+    assert!(sequence.contains("#180!5@-"));
+    assert!(sequence.ends_with("-\x1b\\"));
+}
+
+#[test]
+fn graphics_are_drawn_after_their_last_reserved_row() {
+    let marker = crate::renderer::line_numbers::encode_internal_image(0);
+    let graphic = Graphic {
+        rows: 2,
+        sequence: "<img>".to_string(),
+    };
+    let output = format!("│ {marker}\n│ {marker}\ntext\n");
+
+    let placed = place_graphics(output, std::slice::from_ref(&graphic), 3);
+
+    assert_eq!(placed, "│ \n│ \x1b7\r\x1b[5C\x1b[1A<img>\x1b8\ntext\n");
+    assert_eq!(
+        place_graphics(format!("│ {marker}\ntext\n"), &[graphic], 0),
+        "│ \ntext\n"
+    );
+}
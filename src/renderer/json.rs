@@ -0,0 +1,478 @@
+use super::event::EventRenderer;
+use super::terminal::TerminalRenderer;
+use crate::markdown::{
+    BLANK_LINE_MARKER, HeadingSlugs, ParsedDocument, SourceLineMarker, heading_text,
+    source_line_from_event,
+};
+use crate::renderer::event::CalloutFold;
+use anyhow::Result;
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Tag};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet};
+
+/// Version of the JSON layout; bumped only when existing fields change meaning.
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+struct JsonDocument {
+    version: u32,
+    front_matter: Option<JsonFrontMatter>,
+    children: Vec<Node>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFrontMatter {
+    raw: String,
+    properties: Value,
+}
+
+/// Block or inline element. Containers carry `children`, leaves carry `value`.
+#[derive(Debug, Serialize)]
+struct Node {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(flatten)]
+    attributes: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lines: Option<Lines>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<Node>>,
+}
+
+/// First and last source lines, 1-based and inclusive.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct Lines {
+    start: usize,
+    end: usize,
+}
+
+impl Lines {
+    fn merge(lines: Option<Self>, other: Option<Self>) -> Option<Self> {
+        match (lines, other) {
+            (Some(lines), Some(other)) => Some(Self {
+                start: lines.start.min(other.start),
+                end: lines.end.max(other.end),
+            }),
+            (lines, other) => lines.or(other),
+        }
+    }
+}
+
+impl Node {
+    fn leaf(kind: &'static str, value: Option<String>) -> Self {
+        Self {
+            kind,
+            attributes: Map::new(),
+            lines: None,
+            value,
+            children: None,
+        }
+    }
+
+    fn container(kind: &'static str, attributes: Map<String, Value>) -> Self {
+        Self {
+            kind,
+            attributes,
+            lines: None,
+            value: None,
+            children: Some(Vec::new()),
+        }
+    }
+}
+
+impl TerminalRenderer {
+    /// Serializes the preprocessed event stream as a block and inline tree. The events
+    /// must carry source-line markers for the nodes to receive `lines`.
+    pub(crate) fn to_json(&self, document: ParsedDocument) -> Result<String> {
+        let renderer = EventRenderer::new(
+            &self.config,
+            &self.theme,
+            &self.syntax_set,
+            &self.code_theme,
+        );
+        let front_matter = document
+            .front_matter
+            .map(|front_matter| -> Result<JsonFrontMatter> {
+                Ok(JsonFrontMatter {
+                    properties: serde_json::to_value(&front_matter.properties)?,
+                    raw: front_matter.raw,
+                })
+            })
+            .transpose()?;
+
+        let mut builder = TreeBuilder {
+            renderer: &renderer,
+            events: &document.events,
+            index: 0,
+            slugs: HeadingSlugs::default(),
+            footnotes: Footnotes::new(&document.events),
+        };
+        let children = builder.children();
+        let mut json = serde_json::to_string_pretty(&JsonDocument {
+            version: SCHEMA_VERSION,
+            front_matter,
+            children,
+        })?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// Footnote numbers in order of first reference, as the terminal output shows them.
+struct Footnotes {
+    numbers: HashMap<String, usize>,
+    defined: HashSet<String>,
+}
+
+impl Footnotes {
+    fn new(events: &[Event<'_>]) -> Self {
+        let mut numbers = HashMap::new();
+        let mut defined = HashSet::new();
+        for event in events {
+            match event {
+                Event::FootnoteReference(label) => {
+                    let next = numbers.len() + 1;
+                    numbers.entry(label.to_string()).or_insert(next);
+                }
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    defined.insert(label.to_string());
+                }
+                _ => {}
+            }
+        }
+        Self { numbers, defined }
+    }
+
+    fn attributes(&self, label: &str) -> Map<String, Value> {
+        attributes([
+            ("label", json!(label)),
+            ("number", json!(self.numbers.get(label))),
+            ("defined", json!(self.defined.contains(label))),
+        ])
+    }
+}
+
+struct TreeBuilder<'a> {
+    renderer: &'a EventRenderer<'a>,
+    events: &'a [Event<'static>],
+    index: usize,
+    slugs: HeadingSlugs,
+    footnotes: Footnotes,
+}
+
+impl TreeBuilder<'_> {
+    /// Builds nodes up to the end tag that closes the current container.
+    fn children(&mut self) -> Vec<Node> {
+        let mut nodes: Vec<Node> = Vec::new();
+        let mut line = None;
+        while let Some(event) = self.events.get(self.index) {
+            self.index += 1;
+            if let Some(marker) = source_line_from_event(event) {
+                if let SourceLineMarker::Content(number) = marker {
+                    line = Some(Lines {
+                        start: number,
+                        end: number,
+                    });
+                }
+                continue;
+            }
+
+            let mut node = match event {
+                Event::End(_) => break,
+                Event::Start(tag) => self.container(tag, line.take()),
+                event => match self.leaf(event) {
+                    Some(mut node) => {
+                        node.lines = line.take();
+                        node
+                    }
+                    None => continue,
+                },
+            };
+            match node.kind {
+                "blockquote" => self.convert_callout(&mut node),
+                "item" => self.convert_custom_task_marker(&mut node),
+                _ => {}
+            }
+            match nodes.last_mut() {
+                Some(previous) if previous.kind == "text" && node.kind == "text" => {
+                    if let (Some(text), Some(more)) = (&mut previous.value, node.value) {
+                        text.push_str(&more);
+                    }
+                    previous.lines = Lines::merge(previous.lines, node.lines);
+                }
+                _ => nodes.push(node),
+            }
+        }
+        nodes
+    }
+
+    fn container(&mut self, tag: &Tag<'_>, line: Option<Lines>) -> Node {
+        let (kind, attributes) = match tag {
+            Tag::CodeBlock(kind) => {
+                let info = match kind {
+                    CodeBlockKind::Fenced(info) => Some(info.trim().to_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                let language = info
+                    .as_deref()
+                    .and_then(|info| info.split_whitespace().next())
+                    .map(str::to_string);
+                return self.literal(
+                    "code_block",
+                    attributes([("info", json!(info)), ("language", json!(language))]),
+                    line,
+                );
+            }
+            Tag::HtmlBlock => return self.literal("html_block", Map::new(), line),
+            Tag::Paragraph => ("paragraph", Map::new()),
+            Tag::Heading { level, id, .. } => {
+                let text = heading_text(&self.events[self.index..]);
+                let slug = (!text.is_empty()).then(|| self.slugs.next(&text, id.as_deref()));
+                (
+                    "heading",
+                    attributes([("level", json!(*level as u8)), ("id", json!(slug))]),
+                )
+            }
+            Tag::BlockQuote(kind) => (
+                "blockquote",
+                attributes([(
+                    "kind",
+                    json!(kind.map(|kind| format!("{kind:?}").to_lowercase())),
+                )]),
+            ),
+            Tag::List(start) => (
+                "list",
+                attributes([("ordered", json!(start.is_some())), ("start", json!(start))]),
+            ),
+            Tag::Item => ("item", Map::new()),
+            Tag::FootnoteDefinition(label) => {
+                ("footnote_definition", self.footnotes.attributes(label))
+            }
+            Tag::DefinitionList => ("definition_list", Map::new()),
+            Tag::DefinitionListTitle => ("definition_term", Map::new()),
+            Tag::DefinitionListDefinition => ("definition_details", Map::new()),
+            Tag::Table(alignments) => (
+                "table",
+                attributes([(
+                    "alignments",
+                    alignments
+                        .iter()
+                        .map(|alignment| alignment_name(*alignment))
+                        .collect(),
+                )]),
+            ),
+            Tag::TableHead => ("table_head", Map::new()),
+            Tag::TableRow => ("table_row", Map::new()),
+            Tag::TableCell => ("table_cell", Map::new()),
+            Tag::Emphasis => ("emphasis", Map::new()),
+            Tag::Strong => ("strong", Map::new()),
+            Tag::Strikethrough => ("strikethrough", Map::new()),
+            Tag::Superscript => ("superscript", Map::new()),
+            Tag::Subscript => ("subscript", Map::new()),
+            Tag::Link {
+                dest_url, title, ..
+            } => ("link", target_attributes(dest_url, title)),
+            Tag::Image {
+                dest_url, title, ..
+            } => ("image", target_attributes(dest_url, title)),
+            Tag::MetadataBlock(_) => ("metadata_block", Map::new()),
+        };
+
+        let mut node = Node::container(kind, attributes);
+        let children = self.children();
+        node.lines = children
+            .iter()
+            .fold(line, |lines, child| Lines::merge(lines, child.lines));
+        node.children = Some(children);
+        node
+    }
+
+    /// Collects the text of a code or HTML block into one value.
+    fn literal(
+        &mut self,
+        kind: &'static str,
+        attributes: Map<String, Value>,
+        mut lines: Option<Lines>,
+    ) -> Node {
+        let mut value = String::new();
+        while let Some(event) = self.events.get(self.index) {
+            self.index += 1;
+            match source_line_from_event(event) {
+                Some(SourceLineMarker::Content(number)) => {
+                    let line = Lines {
+                        start: number,
+                        end: number,
+                    };
+                    lines = Lines::merge(lines, Some(line));
+                }
+                Some(SourceLineMarker::Blank(_)) => {}
+                None => match event {
+                    Event::End(_) => break,
+                    Event::Text(text) | Event::Html(text) => value.push_str(text),
+                    _ => {}
+                },
+            }
+        }
+        Node {
+            lines,
+            value: Some(value),
+            children: None,
+            ..Node::container(kind, attributes)
+        }
+    }
+
+    fn leaf(&self, event: &Event<'_>) -> Option<Node> {
+        let node = match event {
+            Event::Text(text) => Node::leaf("text", Some(text.to_string())),
+            Event::Code(code) => Node::leaf("code", Some(code.to_string())),
+            Event::InlineMath(math) => Node {
+                attributes: attributes([("display", json!(false))]),
+                ..Node::leaf("math", Some(math.to_string()))
+            },
+            Event::DisplayMath(math) => Node {
+                attributes: attributes([("display", json!(true))]),
+                ..Node::leaf("math", Some(math.to_string()))
+            },
+            Event::Html(html) if html.trim() == BLANK_LINE_MARKER => return None,
+            Event::Html(html) => Node::leaf("html", Some(html.to_string())),
+            Event::InlineHtml(html) => Node::leaf("inline_html", Some(html.to_string())),
+            Event::FootnoteReference(label) => Node {
+                attributes: self.footnotes.attributes(label),
+                ..Node::leaf("footnote_reference", None)
+            },
+            Event::SoftBreak => Node::leaf("soft_break", None),
+            Event::HardBreak => Node::leaf("hard_break", None),
+            Event::Rule => Node::leaf("rule", None),
+            Event::TaskListMarker(checked) => self.task_marker(if *checked { 'x' } else { ' ' }),
+            Event::Start(_) | Event::End(_) => return None,
+        };
+        Some(node)
+    }
+
+    fn task_marker(&self, state: char) -> Node {
+        Node {
+            attributes: attributes([
+                ("checked", json!(matches!(state, 'x' | 'X'))),
+                ("state", json!(state)),
+                ("icon", json!(self.renderer.task_marker_icon(state))),
+            ]),
+            ..Node::leaf("task_marker", None)
+        }
+    }
+
+    /// Turns a `[c]` prefix at the start of a list item into a `task_marker` node, as
+    /// the terminal output does for states other than `[ ]` and `[x]`.
+    fn convert_custom_task_marker(&self, node: &mut Node) {
+        let Some(children) = node.children.as_mut() else {
+            return;
+        };
+        let inline = match children.first_mut() {
+            Some(paragraph) if paragraph.kind == "paragraph" => match paragraph.children.as_mut() {
+                Some(inline) => inline,
+                None => return,
+            },
+            _ => children,
+        };
+        let Some(text) = inline.first_mut().filter(|child| child.kind == "text") else {
+            return;
+        };
+        let Some((state, remainder)) = text
+            .value
+            .as_deref()
+            .and_then(|value| self.renderer.custom_task_marker(value))
+        else {
+            return;
+        };
+
+        let mut marker = self.task_marker(state);
+        marker.lines = text.lines;
+        let remainder = remainder.trim_start().to_string();
+        if remainder.is_empty() {
+            inline.remove(0);
+        } else {
+            text.value = Some(remainder);
+        }
+        inline.insert(0, marker);
+    }
+
+    /// Turns a blockquote that opens with a callout marker into a `callout` node and
+    /// removes the marker from its first paragraph.
+    fn convert_callout(&self, node: &mut Node) {
+        let Some(children) = node.children.as_mut() else {
+            return;
+        };
+        let Some(paragraph) = children
+            .first_mut()
+            .filter(|child| child.kind == "paragraph")
+        else {
+            return;
+        };
+        let Some(inline) = paragraph.children.as_mut() else {
+            return;
+        };
+        let Some(marker) = inline
+            .first()
+            .filter(|child| child.kind == "text")
+            .and_then(|child| child.value.as_deref())
+        else {
+            return;
+        };
+        let Some(heading) = self.renderer.callout_heading(marker) else {
+            return;
+        };
+
+        if heading.body.is_empty() {
+            inline.remove(0);
+            if inline
+                .first()
+                .is_some_and(|child| matches!(child.kind, "soft_break" | "hard_break"))
+            {
+                inline.remove(0);
+            }
+        } else {
+            inline[0].value = Some(heading.body);
+        }
+        if inline.is_empty() {
+            children.remove(0);
+        }
+
+        node.kind = "callout";
+        node.attributes = attributes([
+            ("callout", json!(heading.label)),
+            ("title", json!(heading.title)),
+            (
+                "fold",
+                json!(heading.fold.map(|fold| match fold {
+                    CalloutFold::Expanded => "expanded",
+                    CalloutFold::Collapsed => "collapsed",
+                })),
+            ),
+        ]);
+    }
+}
+
+fn attributes<const N: usize>(pairs: [(&str, Value); N]) -> Map<String, Value> {
+    pairs
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+fn target_attributes(url: &str, title: &str) -> Map<String, Value> {
+    attributes([
+        ("url", json!(url)),
+        ("title", json!((!title.is_empty()).then_some(title))),
+    ])
+}
+
+fn alignment_name(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::None => "none",
+        Alignment::Left => "left",
+        Alignment::Center => "center",
+        Alignment::Right => "right",
+    }
+}
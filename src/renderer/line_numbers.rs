@@ -5,6 +5,8 @@ use pulldown_cmark::Event;
 
 // unicode-width counts C0 controls in strings, so metadata uses zero-width default-ignorable code points.
 const INTERNAL_MARKER_START: char = '\u{2063}';
+const INTERNAL_ANCHOR_START: char = '\u{2062}';
+const INTERNAL_IMAGE_START: char = '\u{2061}';
 const INTERNAL_MARKER_END: char = '\u{2064}';
 const INTERNAL_MARKER_ZERO: char = '\u{200c}';
 const INTERNAL_MARKER_ONE: char = '\u{200d}';
@@ -46,11 +48,23 @@ pub(super) fn rendered_line_count(output: &str) -> usize {
 }
 
 pub(super) fn encode_internal_marker(line: usize) -> String {
-    let highest_bit = usize::BITS - line.max(1).leading_zeros();
+    encode_marker(INTERNAL_MARKER_START, line)
+}
+
+pub(super) fn encode_internal_anchor(anchor: usize) -> String {
+    encode_marker(INTERNAL_ANCHOR_START, anchor)
+}
+
+pub(super) fn encode_internal_image(image: usize) -> String {
+    encode_marker(INTERNAL_IMAGE_START, image)
+}
+
+fn encode_marker(start: char, value: usize) -> String {
+    let highest_bit = usize::BITS - value.max(1).leading_zeros();
     let mut marker = String::with_capacity(highest_bit as usize + 2);
-    marker.push(INTERNAL_MARKER_START);
+    marker.push(start);
     for shift in (0..highest_bit).rev() {
-        let bit = (line >> shift) & 1;
+        let bit = (value >> shift) & 1;
         marker.push(if bit == 0 {
             INTERNAL_MARKER_ZERO
         } else {
@@ -122,19 +136,57 @@ pub(super) fn format_gutter(
 }
 
 pub(super) fn strip_internal_markers(line: &str) -> (String, Option<usize>) {
-    let mut cleaned = String::with_capacity(line.len());
     let mut source_line = None;
+    let cleaned = strip_markers(line, INTERNAL_MARKER_START, |_, line| {
+        source_line.get_or_insert(line);
+    });
+    (cleaned, source_line)
+}
+
+/// Removes source-line markers from every line, returning the first source line of each.
+pub(super) fn strip_source_lines(output: &str) -> (String, Vec<Option<usize>>) {
+    let mut cleaned = String::with_capacity(output.len());
+    let mut source_lines = Vec::new();
+    for line in output.split_inclusive('\n') {
+        let (line, source_line) = strip_internal_markers(line);
+        cleaned.push_str(&line);
+        source_lines.push(source_line);
+    }
+    (cleaned, source_lines)
+}
+
+/// Removes anchor markers while leaving source-line markers in place.
+pub(super) fn strip_internal_anchors(line: &str) -> (String, Vec<usize>) {
+    let mut anchors = Vec::new();
+    let cleaned = strip_markers(line, INTERNAL_ANCHOR_START, |_, anchor| {
+        anchors.push(anchor)
+    });
+    (cleaned, anchors)
+}
+
+/// Removes image markers, returning each image index with its byte offset in the cleaned line.
+pub(super) fn strip_internal_images(line: &str) -> (String, Vec<(usize, usize)>) {
+    let mut images = Vec::new();
+    let cleaned = strip_markers(line, INTERNAL_IMAGE_START, |cleaned, image| {
+        images.push((cleaned.len(), image));
+    });
+    (cleaned, images)
+}
+
+/// Removes markers that begin with `start`, passing each value and the cleaned text before it.
+fn strip_markers(line: &str, start: char, mut on_value: impl FnMut(&str, usize)) -> String {
+    let mut cleaned = String::with_capacity(line.len());
     let mut cursor = 0usize;
 
     while cursor < line.len() {
-        let Some(relative_start) = line[cursor..].find(INTERNAL_MARKER_START) else {
+        let Some(relative_start) = line[cursor..].find(start) else {
             cleaned.push_str(&line[cursor..]);
             break;
         };
         let marker_start = cursor + relative_start;
         cleaned.push_str(&line[cursor..marker_start]);
 
-        let payload_start = marker_start + INTERNAL_MARKER_START.len_utf8();
+        let payload_start = marker_start + start.len_utf8();
         let Some(relative_end) = line[payload_start..].find(INTERNAL_MARKER_END) else {
             cleaned.push_str(&line[marker_start..]);
             break;
@@ -161,20 +213,23 @@ pub(super) fn strip_internal_markers(line: &str) -> (String, Option<usize>) {
         }
 
         if valid && has_payload {
-            source_line.get_or_insert(value);
+            on_value(&cleaned, value);
             cursor = marker_end + INTERNAL_MARKER_END.len_utf8();
         } else {
-            cleaned.push(INTERNAL_MARKER_START);
+            cleaned.push(start);
             cursor = payload_start;
         }
     }
 
-    (cleaned, source_line)
+    cleaned
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{apply_line_numbers, encode_internal_marker, strip_internal_markers};
+    use super::{
+        apply_line_numbers, encode_internal_anchor, encode_internal_image, encode_internal_marker,
+        strip_internal_anchors, strip_internal_images, strip_internal_markers, strip_source_lines,
+    };
     use crate::cli::{LineNumberOptions, LineNumberTarget};
     use crate::terminal::AnsiStyle;
     use crate::utils::display_width;
@@ -189,6 +244,58 @@ mod tests {
         assert_eq!(source_line, Some(42));
     }
 
+    #[test]
+    fn anchors_are_stripped_independently_of_source_markers() {
+        let source = encode_internal_marker(7);
+        let line = format!(
+            "{}a{source}b{}",
+            encode_internal_anchor(0),
+            encode_internal_anchor(3)
+        );
+        let (cleaned, anchors) = strip_internal_anchors(&line);
+
+        assert_eq!(cleaned, format!("a{source}b"));
+        assert_eq!(anchors, [0, 3]);
+        assert_eq!(
+            strip_internal_markers(&cleaned),
+            ("ab".to_string(), Some(7))
+        );
+        assert_eq!(display_width(&encode_internal_anchor(3)), 0);
+    }
+
+    #[test]
+    fn image_markers_report_their_offsets() {
+        let source = encode_internal_marker(2);
+        let line = format!(
+            "│ {}{source}x{}",
+            encode_internal_image(5),
+            encode_internal_image(0)
+        );
+        let (cleaned, images) = strip_internal_images(&line);
+
+        assert_eq!(cleaned, format!("│ {source}x"));
+        assert_eq!(images, [(4, 5), (4 + source.len() + 1, 0)]);
+        assert_eq!(display_width(&encode_internal_image(5)), 0);
+    }
+
+    #[test]
+    fn source_lines_are_reported_per_rendered_line() {
+        let output = format!(
+            "{}first{}\nwrapped\n\n{}last",
+            encode_internal_marker(1),
+            encode_internal_marker(2),
+            encode_internal_marker(4)
+        );
+
+        assert_eq!(
+            strip_source_lines(&output),
+            (
+                "first\nwrapped\n\nlast".to_string(),
+                vec![Some(1), None, None, Some(4)]
+            )
+        );
+    }
+
     #[test]
     fn internal_marker_occupies_no_display_columns() {
         assert_eq!(display_width(&encode_internal_marker(42)), 0);
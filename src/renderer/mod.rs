@@ -1,10 +1,18 @@
+mod anchors;
+mod diff;
 mod event;
 mod front_matter;
+mod graphics;
+mod json;
 mod line_numbers;
+mod screenshot;
+mod split;
+mod standalone;
 mod syntax_set;
 mod syntax_theme;
 pub(super) mod terminal;
 
+pub(crate) use anchors::{DocumentMap, MappedCodeBlock, MappedHeading, MappedLink};
 pub use terminal::TerminalRenderer;
 
 #[cfg(test)]
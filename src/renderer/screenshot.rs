@@ -0,0 +1,76 @@
+use super::standalone::page_colors;
+use super::terminal::TerminalRenderer;
+use crate::cli::ExportFormat;
+
+mod ansi;
+mod html;
+mod svg;
+
+use ansi::{CellStyle, Line, Rgb};
+
+/// Colors the exported screen uses where the ANSI output leaves the terminal defaults.
+#[derive(Debug, Clone, Copy)]
+struct Screen {
+    background: Rgb,
+    foreground: Rgb,
+    /// Columns the output was rendered for.
+    columns: usize,
+}
+
+impl TerminalRenderer {
+    /// Converts the final ANSI output into a picture of the terminal: an HTML page or an
+    /// SVG image whose spans sit on the same cells as in a terminal `columns` wide.
+    pub(crate) fn export_screen(&self, output: &str, format: ExportFormat) -> String {
+        let (background, foreground) = page_colors(&self.theme);
+        let screen = Screen {
+            background,
+            foreground,
+            columns: self.config.get_terminal_width(),
+        };
+        let lines = ansi::parse(output);
+        match format {
+            ExportFormat::AnsiHtml => html::write(&lines, screen),
+            ExportFormat::Svg => svg::write(&lines, screen),
+        }
+    }
+}
+
+/// Width of the screen in cells: the render width, or a longer unwrapped line.
+fn screen_columns(lines: &[Line], screen: Screen) -> usize {
+    lines
+        .iter()
+        .map(Line::width)
+        .max()
+        .unwrap_or(0)
+        .max(screen.columns)
+}
+
+/// Resolves a run's text and background colors the way a terminal draws them: reverse
+/// video swaps the two, and faint text is mixed halfway into its background.
+fn run_colors(style: &CellStyle, screen: Screen) -> (Rgb, Option<Rgb>) {
+    let mut foreground = style.foreground.unwrap_or(screen.foreground);
+    let mut background = style.background;
+    if style.inverse {
+        background = Some(foreground);
+        foreground = style.background.unwrap_or(screen.background);
+    }
+    if style.dim {
+        let (r, g, b) = foreground;
+        let (br, bg, bb) = background.unwrap_or(screen.background);
+        let mix = |front: u8, back: u8| ((u16::from(front) + u16::from(back)) / 2) as u8;
+        foreground = (mix(r, br), mix(g, bg), mix(b, bb));
+    }
+    (foreground, background)
+}
+
+fn text_decoration(style: &CellStyle) -> Option<&'static str> {
+    match (style.underline, style.strikethrough) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests;
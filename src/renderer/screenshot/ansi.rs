@@ -0,0 +1,191 @@
+use crate::terminal::ansi256_to_rgb;
+use std::iter::Peekable;
+use std::str::Chars;
+use unicode_width::UnicodeWidthChar;
+
+pub(super) type Rgb = (u8, u8, u8);
+
+/// Tab stops of a terminal without custom stops.
+const TAB_WIDTH: usize = 8;
+
+/// Attributes of one terminal cell. `None` colors are the terminal defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct CellStyle {
+    pub(super) foreground: Option<Rgb>,
+    pub(super) background: Option<Rgb>,
+    pub(super) bold: bool,
+    pub(super) dim: bool,
+    pub(super) italic: bool,
+    pub(super) underline: bool,
+    pub(super) strikethrough: bool,
+    pub(super) inverse: bool,
+    /// Target of the OSC 8 hyperlink the cell belongs to.
+    pub(super) link: Option<String>,
+}
+
+/// Cells with one style that follow each other on a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Run {
+    pub(super) column: usize,
+    pub(super) width: usize,
+    pub(super) text: String,
+    pub(super) style: CellStyle,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct Line {
+    pub(super) runs: Vec<Run>,
+}
+
+impl Line {
+    pub(super) fn width(&self) -> usize {
+        self.runs.last().map_or(0, |run| run.column + run.width)
+    }
+
+    fn push(&mut self, ch: char, width: usize, style: &CellStyle) {
+        let column = self.width();
+        match self.runs.last_mut() {
+            // Combining marks join the cell before them whatever its style.
+            Some(run) if width == 0 || run.style == *style => {
+                run.text.push(ch);
+                run.width += width;
+            }
+            _ if width == 0 => {}
+            _ => self.runs.push(Run {
+                column,
+                width,
+                text: ch.to_string(),
+                style: style.clone(),
+            }),
+        }
+    }
+}
+
+/// Splits ANSI output into lines of styled runs. SGR attributes and OSC 8 links are
+/// kept; other escape sequences, such as image protocols, are dropped.
+pub(super) fn parse(output: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut line = Line::default();
+    let mut style = CellStyle::default();
+    let mut chars = output.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => match chars.next() {
+                Some('[') => {
+                    let (parameters, command) = control_sequence(&mut chars);
+                    if command == Some('m') {
+                        apply_sgr(&mut style, &parameters);
+                    }
+                }
+                Some(']') => {
+                    let command = string_sequence(&mut chars);
+                    if let Some(link) = command.strip_prefix("8;") {
+                        let target = link.split_once(';').map_or("", |(_, target)| target);
+                        style.link = (!target.is_empty()).then(|| target.to_string());
+                    }
+                }
+                Some('P' | '_' | '^' | 'X') => {
+                    string_sequence(&mut chars);
+                }
+                _ => {}
+            },
+            '\n' => lines.push(std::mem::take(&mut line)),
+            '\t' => {
+                let spaces = TAB_WIDTH - line.width() % TAB_WIDTH;
+                for _ in 0..spaces {
+                    line.push(' ', 1, &style);
+                }
+            }
+            ch if ch.is_control() => {}
+            ch => line.push(ch, ch.width().unwrap_or(0), &style),
+        }
+    }
+    if !line.runs.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Reads a CSI sequence up to its final byte.
+fn control_sequence(chars: &mut Peekable<Chars<'_>>) -> (String, Option<char>) {
+    let mut parameters = String::new();
+    for ch in chars.by_ref() {
+        if ('\x40'..='\x7e').contains(&ch) {
+            return (parameters, Some(ch));
+        }
+        parameters.push(ch);
+    }
+    (parameters, None)
+}
+
+/// Reads an OSC, DCS, or APC payload up to BEL or the string terminator.
+fn string_sequence(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut payload = String::new();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x07' => break,
+            '\x1b' => {
+                if chars.peek() == Some(&'\\') {
+                    chars.next();
+                }
+                break;
+            }
+            ch => payload.push(ch),
+        }
+    }
+    payload
+}
+
+fn apply_sgr(style: &mut CellStyle, parameters: &str) {
+    let mut codes = parameters
+        .split(';')
+        .map(|code| code.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        match code {
+            // A reset ends the attributes but not the hyperlink around them.
+            0 => {
+                *style = CellStyle {
+                    link: style.link.take(),
+                    ..CellStyle::default()
+                }
+            }
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            7 => style.inverse = true,
+            9 => style.strikethrough = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            27 => style.inverse = false,
+            29 => style.strikethrough = false,
+            30..=37 => style.foreground = Some(ansi256_to_rgb((code - 30) as u8)),
+            90..=97 => style.foreground = Some(ansi256_to_rgb((code - 90 + 8) as u8)),
+            40..=47 => style.background = Some(ansi256_to_rgb((code - 40) as u8)),
+            100..=107 => style.background = Some(ansi256_to_rgb((code - 100 + 8) as u8)),
+            38 => style.foreground = extended_color(&mut codes),
+            48 => style.background = extended_color(&mut codes),
+            39 => style.foreground = None,
+            49 => style.background = None,
+            _ => {}
+        }
+    }
+}
+
+/// Reads the `5;index` or `2;r;g;b` tail of an extended color code.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Rgb> {
+    let channel = |value: u16| value.min(255) as u8;
+    match codes.next()? {
+        5 => codes.next().map(|index| ansi256_to_rgb(channel(index))),
+        2 => Some((
+            channel(codes.next()?),
+            channel(codes.next()?),
+            channel(codes.next()?),
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,76 @@
+use super::ansi::{Line, Run};
+use super::{Screen, run_colors, screen_columns, text_decoration};
+use crate::renderer::standalone::rgb_hex;
+use crate::utils::escape_html_text;
+use std::fmt::Write as _;
+
+/// Writes the screen as a page with one `<pre>` block. Every run is an inline block as
+/// wide as its cells, so wide glyphs drawn by fallback fonts cannot shift the columns
+/// after them.
+pub(super) fn write(lines: &[Line], screen: Screen) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        html,
+        "<meta name=\"generator\" content=\"mdv {}\">",
+        env!("CARGO_PKG_VERSION")
+    );
+    html.push_str("<title>mdv</title>\n<style>\n");
+    let _ = writeln!(
+        html,
+        "body {{ margin: 0; background: {}; }}",
+        rgb_hex(screen.background)
+    );
+    let _ = writeln!(
+        html,
+        "pre.terminal {{ box-sizing: content-box; width: {}ch; margin: 0; padding: 1em 2ch; color: {}; background: {}; font: 14px/1.25 ui-monospace, SFMono-Regular, Menlo, Consolas, \"DejaVu Sans Mono\", monospace; }}",
+        screen_columns(lines, screen),
+        rgb_hex(screen.foreground),
+        rgb_hex(screen.background)
+    );
+    html.push_str("pre.terminal span, pre.terminal a { display: inline-block; vertical-align: top; overflow: visible; }\n");
+    html.push_str("pre.terminal a { color: inherit; text-decoration: none; }\n");
+    html.push_str("</style>\n</head>\n<body>\n<pre class=\"terminal\">");
+    for line in lines {
+        for run in &line.runs {
+            push_run(&mut html, run, screen);
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+fn push_run(html: &mut String, run: &Run, screen: Screen) {
+    let style = &run.style;
+    let (foreground, background) = run_colors(style, screen);
+    let mut css = format!("width:{}ch;", run.width);
+    if foreground != screen.foreground {
+        let _ = write!(css, "color:{};", rgb_hex(foreground));
+    }
+    if let Some(background) = background {
+        let _ = write!(css, "background:{};", rgb_hex(background));
+    }
+    if style.bold {
+        css.push_str("font-weight:bold;");
+    }
+    if style.italic {
+        css.push_str("font-style:italic;");
+    }
+    if let Some(decoration) = text_decoration(style) {
+        let _ = write!(css, "text-decoration:{decoration};");
+    }
+
+    let text = escape_html_text(&run.text);
+    match &style.link {
+        Some(link) => {
+            let _ = write!(
+                html,
+                "<a href=\"{}\" style=\"{css}\">{text}</a>",
+                escape_html_text(link)
+            );
+        }
+        None => {
+            let _ = write!(html, "<span style=\"{css}\">{text}</span>");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::ansi::{Line, Run};
+use super::{Screen, run_colors, screen_columns, text_decoration};
+use crate::renderer::standalone::rgb_hex;
+use crate::utils::escape_html_text;
+use std::fmt::Write as _;
+
+const FONT_SIZE: usize = 15;
+/// Advance of one cell; monospace fonts are close to 0.6 of their size.
+const CELL_WIDTH: usize = 9;
+const ROW_HEIGHT: usize = 19;
+/// Distance from the top of a row to the text baseline.
+const BASELINE: usize = 14;
+const PADDING: usize = 12;
+
+/// Writes the screen as an SVG image. Runs are placed on their cells, and
+/// `textLength` stretches each one to exactly its cell count.
+pub(super) fn write(lines: &[Line], screen: Screen) -> String {
+    let width = screen_columns(lines, screen) * CELL_WIDTH + 2 * PADDING;
+    let height = lines.len() * ROW_HEIGHT + 2 * PADDING;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"ui-monospace, SFMono-Regular, Menlo, Consolas, 'DejaVu Sans Mono', monospace\" font-size=\"{FONT_SIZE}\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+        rgb_hex(screen.background)
+    );
+
+    for (row, line) in lines.iter().enumerate() {
+        let top = PADDING + row * ROW_HEIGHT;
+        for run in &line.runs {
+            if let (_, Some(background)) = run_colors(&run.style, screen) {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{top}\" width=\"{}\" height=\"{ROW_HEIGHT}\" fill=\"{}\"/>",
+                    PADDING + run.column * CELL_WIDTH,
+                    run.width * CELL_WIDTH,
+                    rgb_hex(background)
+                );
+            }
+        }
+
+        let visible: Vec<&Run> = line.runs.iter().filter(|run| is_visible(run)).collect();
+        if visible.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            svg,
+            "<text y=\"{}\" xml:space=\"preserve\">",
+            top + BASELINE
+        );
+        for run in visible {
+            push_run(&mut svg, run, screen);
+        }
+        svg.push_str("</text>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Blank runs only matter when they draw a line through or under their cells.
+fn is_visible(run: &Run) -> bool {
+    run.style.underline || run.style.strikethrough || !run.text.trim().is_empty()
+}
+
+fn push_run(svg: &mut String, run: &Run, screen: Screen) {
+    let style = &run.style;
+    let (foreground, _) = run_colors(style, screen);
+    let mut attributes = format!(
+        "x=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\"",
+        PADDING + run.column * CELL_WIDTH,
+        run.width * CELL_WIDTH,
+        rgb_hex(foreground)
+    );
+    if style.bold {
+        attributes.push_str(" font-weight=\"bold\"");
+    }
+    if style.italic {
+        attributes.push_str(" font-style=\"italic\"");
+    }
+    if let Some(decoration) = text_decoration(style) {
+        let _ = write!(attributes, " text-decoration=\"{decoration}\"");
+    }
+
+    let tspan = format!(
+        "<tspan {attributes}>{}</tspan>",
+        escape_html_text(&run.text)
+    );
+    match &style.link {
+        Some(link) => {
+            let _ = write!(svg, "<a href=\"{}\">{tspan}</a>", escape_html_text(link));
+        }
+        None => svg.push_str(&tspan),
+    }
+}
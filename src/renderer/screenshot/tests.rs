@@ -0,0 +1,99 @@
+use super::ansi::{CellStyle, parse};
+use super::*;
+
+const SCREEN: Screen = Screen {
+    background: (0, 0, 0),
+    foreground: (200, 200, 200),
+    columns: 20,
+};
+
+#[test]
+fn parse_keeps_sgr_attributes_per_run() {
+    let lines = parse("\x1b[1;38;2;255;0;0mError\x1b[0m: \x1b[3;9;48;5;21mold\x1b[0m\n");
+
+    let runs = &lines[0].runs;
+    assert_eq!(runs.len(), 3);
+    assert_eq!(runs[0].text, "Error");
+    assert_eq!(runs[0].style.foreground, Some((255, 0, 0)));
+    assert!(runs[0].style.bold);
+    assert_eq!(runs[1].style, CellStyle::default());
+    assert_eq!(runs[2].column, 7);
+    assert!(runs[2].style.italic && runs[2].style.strikethrough);
+    assert_eq!(runs[2].style.background, Some((0, 0, 255)));
+}
+
+#[test]
+fn parse_maps_named_colors_and_defaults() {
+    let lines = parse("\x1b[31mred\x1b[39m \x1b[94mblue\x1b[0m");
+
+    let runs = &lines[0].runs;
+    assert_eq!(runs[0].style.foreground, Some((128, 0, 0)));
+    assert_eq!(runs[1].style.foreground, None);
+    assert_eq!(runs[2].style.foreground, Some((0, 0, 255)));
+}
+
+#[test]
+fn parse_tracks_hyperlinks_across_sgr_resets() {
+    let lines = parse("\x1b]8;;https://example.com\x1b\\\x1b[4mdocs\x1b[0m!\x1b]8;;\x1b\\ end");
+
+    let runs = &lines[0].runs;
+    assert_eq!(runs[0].style.link.as_deref(), Some("https://example.com"));
+    assert!(runs[0].style.underline);
+    assert_eq!(runs[1].text, "!");
+    assert_eq!(runs[1].style.link.as_deref(), Some("https://example.com"));
+    assert_eq!(runs[2].text, " end");
+    assert_eq!(runs[2].style.link, None);
+}
+
+#[test]
+fn parse_counts_cells_of_wide_characters_and_skips_image_protocols() {
+    let lines = parse("中文\x1b_Ga=T;AAAA\x1b\\x\x1bPq#0~\x1b\\\n\nend");
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].runs[0].text, "中文x");
+    assert_eq!(lines[0].width(), 5);
+    assert!(lines[1].runs.is_empty());
+    assert_eq!(lines[2].width(), 3);
+}
+
+#[test]
+fn run_colors_apply_reverse_video_and_faint_text() {
+    let inverse = CellStyle {
+        inverse: true,
+        ..CellStyle::default()
+    };
+    assert_eq!(
+        run_colors(&inverse, SCREEN),
+        ((0, 0, 0), Some((200, 200, 200)))
+    );
+
+    let dim = CellStyle {
+        dim: true,
+        ..CellStyle::default()
+    };
+    assert_eq!(run_colors(&dim, SCREEN), ((100, 100, 100), None));
+}
+
+#[test]
+fn html_export_positions_runs_on_cells() {
+    let html = html::write(
+        &parse("\x1b[38;2;1;2;3mab\x1b[0m\x1b]8;;https://a.test\x1b\\c\x1b]8;;\x1b\\\n"),
+        SCREEN,
+    );
+
+    assert!(html.contains("width: 20ch;"));
+    assert!(html.contains("<span style=\"width:2ch;color:#010203;\">ab</span>"));
+    assert!(html.contains("<a href=\"https://a.test\" style=\"width:1ch;\">c</a>"));
+}
+
+#[test]
+fn svg_export_draws_backgrounds_and_skips_blank_runs() {
+    let svg = svg::write(&parse("  \x1b[44;4mhi\x1b[0m  \n\n"), SCREEN);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"204\""));
+    assert!(svg.contains("<rect x=\"30\" y=\"12\" width=\"18\" height=\"19\" fill=\"#000080\"/>"));
+    assert!(svg.contains(
+        "<tspan x=\"30\" textLength=\"18\" lengthAdjust=\"spacingAndGlyphs\" fill=\"#c8c8c8\" text-decoration=\"underline\">hi</tspan>"
+    ));
+    assert_eq!(svg.matches("<text ").count(), 1);
+}
@@ -0,0 +1,204 @@
+use super::anchors::DocumentMap;
+use super::syntax_theme::as_terminal_escaped;
+use super::terminal::TerminalRenderer;
+use crate::markdown::ParsedDocument;
+use crate::theme::{ThemeElement, create_style};
+use anyhow::Result;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::Style;
+use unicode_width::UnicodeWidthChar;
+
+const SEPARATOR: &str = " │ ";
+const SEPARATOR_WIDTH: usize = 3;
+
+impl TerminalRenderer {
+    /// Renders the document beside its highlighted Markdown source. Rows are aligned by
+    /// the source-line markers, so both panes scroll together as one text.
+    pub(crate) fn render_split_view(
+        &self,
+        document: ParsedDocument,
+        source: &str,
+    ) -> Result<(String, DocumentMap)> {
+        let width = self.config.get_terminal_width();
+        let source_width = width.saturating_sub(SEPARATOR_WIDTH) / 2;
+        let mut config = self.config.clone();
+        config.cols = Some(width.saturating_sub(SEPARATOR_WIDTH + source_width));
+        config.cols_from_cli = true;
+        config.source_map = true;
+        let pane = Self {
+            config,
+            theme: self.theme.clone(),
+            syntax_set: self.syntax_set.clone(),
+            code_theme: self.code_theme.clone(),
+        };
+        let (rendered, map) = pane.render_document_map(document)?;
+        let source = self.source_pane(source, source_width)?;
+        let separator = create_style(&self.theme, ThemeElement::LineNumberSeparator)
+            .apply(SEPARATOR, self.config.no_colors);
+        Ok(compose_split(
+            &rendered,
+            map,
+            &source,
+            &" ".repeat(source_width),
+            &separator,
+        ))
+    }
+
+    /// Highlights `source` as Markdown, with the rows of each line wrapped and padded to
+    /// `width`.
+    fn source_pane(&self, source: &str, width: usize) -> Result<Vec<Vec<String>>> {
+        let syntax = self
+            .syntax_set
+            .find_syntax_by_extension("md")
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+        let mut highlighter = HighlightLines::new(syntax, &self.code_theme.syntect);
+        let tab = " ".repeat(self.config.tab_length.max(1));
+        source
+            .lines()
+            .map(|line| {
+                let line = format!("{}\n", line.replace('\t', &tab));
+                let ranges = highlighter.highlight_line(&line, &self.syntax_set)?;
+                Ok(wrap_ranges(&ranges, width)
+                    .into_iter()
+                    .map(|(ranges, used)| {
+                        let mut cell = if self.config.no_colors {
+                            ranges.iter().map(|(_, text)| *text).collect()
+                        } else {
+                            let mut cell = as_terminal_escaped(&ranges, self.code_theme.palette());
+                            cell.push_str("\x1b[0m");
+                            cell
+                        };
+                        cell.push_str(&" ".repeat(width.saturating_sub(used)));
+                        cell
+                    })
+                    .collect())
+            })
+            .collect()
+    }
+}
+
+/// Splits highlighted ranges into rows of at most `width` columns, dropping the line
+/// break and control characters. Returns the ranges of each row and their width.
+fn wrap_ranges<'a>(
+    ranges: &[(Style, &'a str)],
+    width: usize,
+) -> Vec<(Vec<(Style, &'a str)>, usize)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut used = 0;
+    for &(style, text) in ranges {
+        let mut start = 0;
+        for (index, character) in text.char_indices() {
+            let character_width = if character.is_control() {
+                None
+            } else {
+                character.width()
+            };
+            let Some(character_width) = character_width else {
+                if start < index {
+                    row.push((style, &text[start..index]));
+                }
+                start = index + character.len_utf8();
+                continue;
+            };
+            // A character wider than the pane still takes a row of its own.
+            if used > 0 && used + character_width > width {
+                if start < index {
+                    row.push((style, &text[start..index]));
+                }
+                rows.push((std::mem::take(&mut row), used));
+                (start, used) = (index, 0);
+            }
+            used += character_width;
+        }
+        if start < text.len() {
+            row.push((style, &text[start..]));
+        }
+    }
+    rows.push((row, used));
+    rows
+}
+
+/// Places each block of rendered lines beside the rows of the source lines it came from.
+/// A block starts at every rendered line that begins a later source line, and takes as
+/// many rows as the longer of its two sides.
+fn compose_split(
+    rendered: &str,
+    map: DocumentMap,
+    source: &[Vec<String>],
+    empty_cell: &str,
+    separator: &str,
+) -> (String, DocumentMap) {
+    let rendered: Vec<&str> = rendered
+        .split_inclusive('\n')
+        .map(|line| line.strip_suffix('\n').unwrap_or(line))
+        .collect();
+    let mut blocks = Vec::new();
+    let (mut rendered_start, mut source_start) = (0, 0);
+    for (line, source_line) in map.source_lines.iter().enumerate() {
+        let Some(source_line) = source_line.map(|line| line.saturating_sub(1)) else {
+            continue;
+        };
+        if source_line > source_start {
+            blocks.push((rendered_start..line, source_start..source_line));
+            (rendered_start, source_start) = (line, source_line);
+        }
+    }
+    blocks.push((
+        rendered_start..rendered.len(),
+        source_start..source.len().max(source_start),
+    ));
+
+    let mut output = String::new();
+    let mut rows_of_rendered = Vec::with_capacity(rendered.len());
+    let mut source_lines = Vec::new();
+    for (rendered_lines, source_range) in blocks {
+        // Only the first row of a wrapped source line starts it.
+        let source_rows: Vec<(Option<usize>, &str)> = source_range
+            .filter_map(|line| source.get(line).map(|rows| (line, rows)))
+            .flat_map(|(line, rows)| {
+                rows.iter()
+                    .enumerate()
+                    .map(move |(row, cell)| ((row == 0).then_some(line + 1), cell.as_str()))
+            })
+            .collect();
+        let rows = rendered_lines.len().max(source_rows.len());
+        for offset in 0..rows {
+            let source_row = source_rows.get(offset);
+            let rendered_line = rendered_lines.start + offset;
+            if offset < rendered_lines.len() {
+                rows_of_rendered.push(source_lines.len());
+            }
+            source_lines.push(source_row.and_then(|(line, _)| *line));
+            output.push_str(source_row.map_or(empty_cell, |(_, cell)| cell));
+            output.push_str(separator);
+            if offset < rendered_lines.len() {
+                output.push_str(rendered[rendered_line]);
+            }
+            output.push('\n');
+        }
+    }
+
+    let row = |line: usize| rows_of_rendered.get(line).copied().unwrap_or(line);
+    let mut headings = map.headings;
+    for heading in &mut headings {
+        heading.line = row(heading.line);
+    }
+    let mut links = map.links;
+    for link in &mut links {
+        link.line = row(link.line);
+    }
+    let mut code_blocks = map.code_blocks;
+    for block in &mut code_blocks {
+        block.lines = row(block.lines.start)..row(block.lines.end - 1) + 1;
+    }
+    (
+        output,
+        DocumentMap {
+            headings,
+            links,
+            code_blocks,
+            source_lines,
+        },
+    )
+}
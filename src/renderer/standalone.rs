@@ -0,0 +1,305 @@
+use super::event::EventRenderer;
+use super::front_matter::document_events;
+use super::syntax_theme::CodeHighlightTheme;
+use super::terminal::{TerminalRenderer, html_events};
+use crate::markdown::{Heading, ParsedDocument, heading_text, headings};
+use crate::math::{MathMode, render_math};
+use crate::terminal::calculate_luminosity;
+use crate::theme::{Color, Theme, color_to_rgb};
+use crate::utils::escape_html_text;
+use anyhow::Result;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Tag, TagEnd};
+use std::fmt::Write as _;
+use syntect::highlighting::Color as SyntectColor;
+
+const STYLESHEET: &str = include_str!("../../assets/html/standalone.css");
+
+impl TerminalRenderer {
+    /// Renders a self-contained HTML page: CSS from the active theme, code highlighted
+    /// with the resolved code theme, callout boxes, and a table of contents.
+    pub(crate) fn to_standalone_html(&self, document: ParsedDocument) -> Result<String> {
+        let mut events: Vec<Event<'static>> =
+            html_events(document_events(document, &self.config, true)?).collect();
+        let outline = headings(&events);
+        assign_heading_ids(&mut events, &outline);
+
+        let renderer = EventRenderer::new(
+            &self.config,
+            &self.theme,
+            &self.syntax_set,
+            &self.code_theme,
+        );
+        let mut body = String::new();
+        pulldown_cmark::html::push_html(&mut body, page_events(&renderer, events)?.into_iter());
+
+        let title = outline
+            .first()
+            .map_or("Document", |heading| heading.text.as_str());
+        let mut page = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n",
+        );
+        let _ = writeln!(
+            page,
+            "<meta name=\"generator\" content=\"mdv {}\">",
+            env!("CARGO_PKG_VERSION")
+        );
+        let _ = writeln!(page, "<title>{}</title>", escape_html_text(title));
+        page.push_str("<style>\n");
+        page.push_str(&theme_variables(&self.theme, &self.code_theme));
+        page.push_str(STYLESHEET);
+        page.push_str("</style>\n</head>\n<body>\n<div class=\"page\">\n");
+        page.push_str(&table_of_contents(&outline));
+        page.push_str("<main>\n");
+        page.push_str(&body);
+        page.push_str("</main>\n</div>\n</body>\n</html>\n");
+        Ok(page)
+    }
+}
+
+pub(super) fn css_color(color: &Color) -> Option<String> {
+    color_to_rgb(color).map(rgb_hex)
+}
+
+pub(super) fn rgb_hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Returns the page background and text colors. Themes without a background are drawn
+/// on the terminal's own, usually dark, one; `reset` text follows that background.
+pub(super) fn page_colors(theme: &Theme) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let background = theme.background.as_ref().and_then(color_to_rgb);
+    let dark = background.is_none_or(|(r, g, b)| calculate_luminosity(r, g, b) < 0.5);
+    let background = background.unwrap_or((0x1c, 0x1c, 0x1c));
+    let text = color_to_rgb(&theme.text).unwrap_or(if dark {
+        (0xd0, 0xd0, 0xd0)
+    } else {
+        (0x1c, 0x1c, 0x1c)
+    });
+    (background, text)
+}
+
+/// Declares the theme colors as CSS custom properties. `reset` colors are left out so
+/// the stylesheet falls back to the inherited text color.
+fn theme_variables(theme: &Theme, code_theme: &CodeHighlightTheme) -> String {
+    let (background, text) = page_colors(theme);
+    let mut css = String::from(":root {\n");
+    let mut declare = |name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            let _ = writeln!(css, "  --{name}: {value};");
+        }
+    };
+
+    declare("bg", Some(rgb_hex(background)));
+    declare("text", Some(rgb_hex(text)));
+    declare("text-light", css_color(&theme.text_light));
+    for (name, color) in [
+        ("h1", &theme.h1),
+        ("h2", &theme.h2),
+        ("h3", &theme.h3),
+        ("h4", &theme.h4),
+        ("h5", &theme.h5),
+        ("h6", &theme.h6),
+        ("code", &theme.code),
+        ("quote", &theme.quote),
+        ("link", &theme.link),
+        ("emphasis", &theme.emphasis),
+        ("strong", &theme.strong),
+        ("strikethrough", &theme.strikethrough),
+        ("border", &theme.border),
+        ("list-marker", &theme.list_marker),
+        ("table-header", &theme.table_header),
+        ("table-border", &theme.table_border),
+        ("front-matter-title", theme.front_matter_title_color()),
+        ("front-matter-key", theme.front_matter_key_color()),
+        ("front-matter-value", theme.front_matter_value_color()),
+        ("front-matter-border", theme.front_matter_border_color()),
+    ] {
+        declare(name, css_color(color));
+    }
+    declare(
+        "strong-emphasis",
+        theme.strong_emphasis.as_ref().and_then(css_color),
+    );
+    declare(
+        "code-bg",
+        theme.code_background.as_ref().and_then(css_color),
+    );
+    declare(
+        "code-block-bg",
+        code_theme
+            .syntect
+            .settings
+            .background
+            .and_then(syntect_css_color),
+    );
+    declare(
+        "code-block-text",
+        code_theme
+            .syntect
+            .settings
+            .foreground
+            .and_then(syntect_css_color),
+    );
+    css.push_str("}\n");
+    css
+}
+
+/// Converts an opaque syntect color; the transparent `reset` sentinel has none.
+fn syntect_css_color(color: SyntectColor) -> Option<String> {
+    (color.a != 0).then(|| format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b))
+}
+
+/// Gives every heading listed in the outline its anchor slug as an `id`.
+fn assign_heading_ids(events: &mut [Event<'static>], outline: &[Heading]) {
+    let positions: Vec<usize> = (0..events.len())
+        .filter(|&index| {
+            matches!(events[index], Event::Start(Tag::Heading { .. }))
+                && !heading_text(&events[index + 1..]).is_empty()
+        })
+        .collect();
+    for (position, heading) in positions.into_iter().zip(outline) {
+        if let Event::Start(Tag::Heading { id, .. }) = &mut events[position] {
+            *id = Some(CowStr::from(heading.slug.clone()));
+        }
+    }
+}
+
+/// Replaces code blocks, callouts, and math with the page's own markup.
+fn page_events(
+    renderer: &EventRenderer<'_>,
+    events: Vec<Event<'static>>,
+) -> Result<Vec<Event<'static>>> {
+    let mut output = Vec::with_capacity(events.len());
+    // Closing tag of each open blockquote that became a callout box.
+    let mut blockquotes: Vec<Option<&'static str>> = Vec::new();
+    let mut index = 0;
+    while index < events.len() {
+        let event = events[index].clone();
+        index += 1;
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let mut code = String::new();
+                while let Some(event) = events.get(index) {
+                    index += 1;
+                    match event {
+                        Event::End(TagEnd::CodeBlock) => break,
+                        Event::Text(text) => code.push_str(text),
+                        _ => {}
+                    }
+                }
+                let hint = match &kind {
+                    CodeBlockKind::Fenced(info) if !info.trim().is_empty() => Some(info.trim()),
+                    _ => None,
+                };
+                output.push(Event::Html(renderer.code_block_html(&code, hint)?.into()));
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                let following = events.get(index + 1..).unwrap_or_default();
+                let texts = following
+                    .iter()
+                    .take_while(|event| matches!(event, Event::Text(_)))
+                    .count();
+                let marker: String = following[..texts]
+                    .iter()
+                    .filter_map(|event| match event {
+                        Event::Text(text) => Some(text.as_ref()),
+                        _ => None,
+                    })
+                    .collect();
+                let callout = matches!(events.get(index), Some(Event::Start(Tag::Paragraph)))
+                    .then(|| renderer.callout_html(&marker))
+                    .flatten();
+                let Some(callout) = callout else {
+                    blockquotes.push(None);
+                    output.push(event);
+                    continue;
+                };
+
+                output.push(Event::Html(callout.open.into()));
+                blockquotes.push(Some(callout.close));
+                index += 1 + texts;
+                if !callout.body.is_empty() {
+                    output.push(Event::Start(Tag::Paragraph));
+                    output.push(Event::Text(callout.body.into()));
+                    continue;
+                }
+                // The marker line was the whole first line; drop its break, and the
+                // paragraph too when nothing else was in it.
+                if matches!(events.get(index), Some(Event::SoftBreak | Event::HardBreak)) {
+                    index += 1;
+                }
+                if matches!(events.get(index), Some(Event::End(TagEnd::Paragraph))) {
+                    index += 1;
+                } else {
+                    output.push(Event::Start(Tag::Paragraph));
+                }
+            }
+            Event::End(TagEnd::BlockQuote(_)) => match blockquotes.pop().flatten() {
+                Some(close) => output.push(Event::Html(close.into())),
+                None => output.push(event),
+            },
+            Event::InlineMath(math) => output.push(Event::InlineHtml(
+                format!(
+                    "<span class=\"math math-inline\">{}</span>",
+                    escape_html_text(&render_math(&math, MathMode::Inline))
+                )
+                .into(),
+            )),
+            Event::DisplayMath(math) => output.push(Event::InlineHtml(
+                format!(
+                    "<span class=\"math math-display\">{}</span>",
+                    escape_html_text(&render_math(&math, MathMode::Display))
+                )
+                .into(),
+            )),
+            other => output.push(other),
+        }
+    }
+    Ok(output)
+}
+
+/// Builds nested lists of heading links; each heading nests under the closest
+/// preceding heading of a lower level, as in `mdv toc`.
+fn table_of_contents(outline: &[Heading]) -> String {
+    if outline.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::from("<nav class=\"toc\">\n<p class=\"toc-title\">Contents</p>\n");
+    // Level of the last heading in each open list.
+    let mut lists: Vec<u8> = Vec::new();
+    for heading in outline {
+        match lists.last_mut() {
+            Some(last) if heading.level <= *last => {
+                html.push_str("</li>\n");
+                while lists.len() > 1 && heading.level <= lists[lists.len() - 2] {
+                    lists.pop();
+                    html.push_str("</ul>\n</li>\n");
+                }
+                if let Some(last) = lists.last_mut() {
+                    *last = heading.level;
+                }
+            }
+            Some(_) => {
+                html.push_str("\n<ul>\n");
+                lists.push(heading.level);
+            }
+            None => {
+                html.push_str("<ul>\n");
+                lists.push(heading.level);
+            }
+        }
+        let _ = write!(
+            html,
+            "<li><a href=\"#{}\">{}</a>",
+            escape_html_text(&heading.slug),
+            escape_html_text(&heading.text)
+        );
+    }
+    html.push_str("</li>\n");
+    for _ in 1..lists.len() {
+        html.push_str("</ul>\n</li>\n");
+    }
+    html.push_str("</ul>\n</nav>\n");
+    html
+}
@@ -16,9 +16,9 @@ use syntect::parsing::SyntaxSet;
 /// Terminal renderer for markdown content
 pub struct TerminalRenderer {
     pub(super) config: Config,
-    theme: Theme,
-    syntax_set: Arc<SyntaxSet>,
-    code_theme: CodeHighlightTheme,
+    pub(super) theme: Theme,
+    pub(super) syntax_set: Arc<SyntaxSet>,
+    pub(super) code_theme: CodeHighlightTheme,
 }
 
 impl TerminalRenderer {
@@ -54,8 +54,19 @@ impl TerminalRenderer {
     }
 
     pub fn render(&self, events: Vec<Event<'static>>) -> Result<String> {
-        let output = match self.config.line_numbers {
-            None => self.render_events(&self.config, events)?,
+        self.render_source_mapped(events).map(|(output, _)| output)
+    }
+
+    /// Renders `events` and reports the first source line on each rendered line. Lines
+    /// only carry a source line when the parser emitted source-line markers.
+    pub(super) fn render_source_mapped(
+        &self,
+        events: Vec<Event<'static>>,
+    ) -> Result<(String, Vec<Option<usize>>)> {
+        let (output, source_lines) = match self.config.line_numbers {
+            None => {
+                super::line_numbers::strip_source_lines(&self.render_events(&self.config, events)?)
+            }
             Some(options) => match options.target {
                 LineNumberTarget::Rendered => {
                     self.render_with_rendered_line_numbers(events, options)?
@@ -66,11 +77,14 @@ impl TerminalRenderer {
             },
         };
 
-        Ok(apply_left_margin(&output, self.config.margin.left))
+        Ok((
+            apply_left_margin(&output, self.config.margin.left),
+            source_lines,
+        ))
     }
 
-    pub(crate) const fn pager_status_bar_transparent(&self) -> bool {
-        self.theme.pager_status_bar_transparent
+    pub(crate) const fn theme(&self) -> &Theme {
+        &self.theme
     }
 
     fn render_events(&self, config: &Config, events: Vec<Event<'static>>) -> Result<String> {
@@ -102,6 +116,9 @@ impl TerminalRenderer {
         let mut renderer =
             EventRenderer::new(config, &self.theme, &self.syntax_set, &self.code_theme);
         let output = renderer.render_events(events)?;
+        let left_columns = config.margin.left + config.line_number_gutter_width;
+        let output =
+            super::graphics::place_graphics(output, &renderer.image_graphics, left_columns);
         Ok((output, renderer.max_code_line_number_width))
     }
 
@@ -109,9 +126,10 @@ impl TerminalRenderer {
         &self,
         events: Vec<Event<'static>>,
         options: LineNumberOptions,
-    ) -> Result<String> {
+    ) -> Result<(String, Vec<Option<usize>>)> {
         let Some(max_line) = super::line_numbers::max_source_line(&events) else {
-            return self.render_events(&self.config, events);
+            let output = self.render_events(&self.config, events)?;
+            return Ok(super::line_numbers::strip_source_lines(&output));
         };
 
         let mut render_config = self.config.clone();
@@ -125,7 +143,7 @@ impl TerminalRenderer {
         &self,
         events: Vec<Event<'static>>,
         options: LineNumberOptions,
-    ) -> Result<String> {
+    ) -> Result<(String, Vec<Option<usize>>)> {
         let mut number_width = 1;
 
         loop {
@@ -136,7 +154,7 @@ impl TerminalRenderer {
             let rendered_lines = super::line_numbers::rendered_line_count(&output);
 
             if rendered_lines == 0 {
-                return Ok(output);
+                return Ok((output, Vec::new()));
             }
 
             let required_width = rendered_lines.to_string().len();
@@ -153,43 +171,49 @@ impl TerminalRenderer {
         output: &str,
         max_line: usize,
         options: LineNumberOptions,
-    ) -> String {
+    ) -> (String, Vec<Option<usize>>) {
         let number_style = create_style(&self.theme, ThemeElement::LineNumber);
         let separator_style = create_style(&self.theme, ThemeElement::LineNumberSeparator);
-        super::line_numbers::apply_line_numbers(
+        let (_, source_lines) = super::line_numbers::strip_source_lines(output);
+        let numbered = super::line_numbers::apply_line_numbers(
             output,
             max_line,
             &number_style,
             &separator_style,
             options,
             self.config.no_colors,
-        )
+        );
+        (numbered, source_lines)
     }
 
     pub fn to_html(&self, events: Vec<Event<'static>>) -> Result<String> {
-        let events = events.into_iter().filter_map(|event| {
-            if crate::markdown::source_line_from_event(&event).is_some() {
-                return None;
-            }
-
-            Some(match event {
-                Event::Html(html) if html.as_ref().trim() == crate::markdown::BLANK_LINE_MARKER => {
-                    Event::HardBreak
-                }
-                Event::InlineHtml(html)
-                    if html.as_ref().trim() == crate::markdown::BLANK_LINE_MARKER =>
-                {
-                    Event::HardBreak
-                }
-                other => other,
-            })
-        });
         let mut html_output = String::new();
-        pulldown_cmark::html::push_html(&mut html_output, events);
+        pulldown_cmark::html::push_html(&mut html_output, html_events(events));
         Ok(html_output)
     }
 }
 
+/// Drops source-line markers and turns explicit blank-line markers into line breaks.
+pub(super) fn html_events(events: Vec<Event<'static>>) -> impl Iterator<Item = Event<'static>> {
+    events.into_iter().filter_map(|event| {
+        if crate::markdown::source_line_from_event(&event).is_some() {
+            return None;
+        }
+
+        Some(match event {
+            Event::Html(html) if html.as_ref().trim() == crate::markdown::BLANK_LINE_MARKER => {
+                Event::HardBreak
+            }
+            Event::InlineHtml(html)
+                if html.as_ref().trim() == crate::markdown::BLANK_LINE_MARKER =>
+            {
+                Event::HardBreak
+            }
+            other => other,
+        })
+    })
+}
+
 fn apply_left_margin(output: &str, margin: usize) -> String {
     if margin == 0 || output.is_empty() {
         return output.to_string();
@@ -228,9 +252,10 @@ fn resolve_theme(config: &Config, theme_manager: &ThemeManager) -> Result<Theme>
     Ok(theme)
 }
 
-pub(crate) fn pager_status_bar_transparent(config: &Config) -> Result<bool> {
+/// Theme of `config` with its custom overrides applied.
+pub(crate) fn resolved_theme(config: &Config) -> Result<Theme> {
     let theme_manager = build_theme_manager(config);
-    Ok(resolve_theme(config, &theme_manager)?.pager_status_bar_transparent)
+    resolve_theme(config, &theme_manager)
 }
 
 fn resolve_code_theme(
@@ -294,7 +319,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn renderer_exposes_pager_status_bar_transparency() {
+    fn renderer_exposes_the_resolved_theme() {
         let config = Config {
             custom_theme: Some("pager_status_bar_transparent=true".to_string()),
             ..Config::default()
@@ -302,6 +327,6 @@ mod tests {
 
         let renderer = TerminalRenderer::new(&config).unwrap();
 
-        assert!(renderer.pager_status_bar_transparent());
+        assert!(renderer.theme().pager_status_bar_transparent);
     }
 }
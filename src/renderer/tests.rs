@@ -27,3 +27,200 @@ fn test_basic_rendering() {
     let output = result.unwrap();
     assert!(!output.is_empty());
 }
+
+#[test]
+fn document_map_locates_headings_and_links_without_changing_output() {
+    let config = Config::default();
+    let renderer = TerminalRenderer::new(&config).unwrap();
+    let markdown = "# Guide\n\nRead the [setup notes](setup.md).\n\n## Usage\n\n## Usage\n";
+    let processor = crate::markdown::MarkdownProcessor::new(&config);
+    let plain = renderer
+        .render(processor.parse_document(markdown).unwrap().events)
+        .unwrap();
+    let document = processor.parse_document(markdown).unwrap();
+
+    let (output, map) = renderer.render_document_map(document).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    let slugs: Vec<&str> = map
+        .headings
+        .iter()
+        .map(|heading| heading.slug.as_str())
+        .collect();
+
+    assert_eq!(slugs, ["guide", "usage", "usage-1"]);
+    assert!(lines[map.headings[1].line].contains("Usage"));
+    assert_eq!(map.links.len(), 1);
+    assert_eq!(map.links[0].url, "setup.md");
+    assert_eq!(map.links[0].text, "setup notes");
+    assert!(lines[map.links[0].line].contains("setup notes"));
+    assert!(!output.contains('\u{2062}'));
+    assert_eq!(output, plain);
+}
+
+#[test]
+fn source_map_records_source_lines_without_changing_output() {
+    let markdown = "---\ntitle: Guide\n---\n# Guide\n\nFirst paragraph.\n\n- item\n";
+    let config = Config::default();
+    let mapped_config = Config {
+        source_map: true,
+        ..Config::default()
+    };
+    let plain = TerminalRenderer::new(&config)
+        .unwrap()
+        .render_document_map(
+            crate::markdown::MarkdownProcessor::new(&config)
+                .parse_document(markdown)
+                .unwrap(),
+        )
+        .unwrap();
+    let (output, mut map) = TerminalRenderer::new(&mapped_config)
+        .unwrap()
+        .render_document_map(
+            crate::markdown::MarkdownProcessor::new(&mapped_config)
+                .parse_document(markdown)
+                .unwrap(),
+        )
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(output, plain.0);
+    assert!(plain.1.source_lines.is_empty());
+    assert_eq!(map.source_lines.len(), lines.len());
+    let paragraph = map.rendered_lines_for_source(6).unwrap();
+    assert!(lines[paragraph.start].contains("First paragraph."));
+    assert!(map.rendered_lines_for_source(7).unwrap().start >= paragraph.end);
+    assert!(lines[map.rendered_lines_for_source(8).unwrap().start].contains("item"));
+    assert_eq!(map.rendered_lines_for_source(2), None);
+
+    map.offset_lines(2);
+    assert_eq!(
+        map.rendered_lines_for_source(6).unwrap().start,
+        paragraph.start + 2
+    );
+}
+
+#[test]
+fn source_map_locates_code_blocks_with_their_code() {
+    let markdown =
+        "# Guide\n\n```rust\nfn main() {}\nlet x = 1;\n```\n\n- item\n\n      indented\n";
+    let config = Config {
+        source_map: true,
+        ..Config::default()
+    };
+    let (output, map) = TerminalRenderer::new(&config)
+        .unwrap()
+        .render_document_map(
+            crate::markdown::MarkdownProcessor::new(&config)
+                .parse_document(markdown)
+                .unwrap(),
+        )
+        .unwrap();
+    let lines: Vec<String> = output.lines().map(crate::utils::strip_ansi).collect();
+
+    assert_eq!(map.code_blocks.len(), 1);
+    let block = &map.code_blocks[0];
+    assert_eq!(block.language, "rust");
+    assert_eq!(block.code, "fn main() {}\nlet x = 1;");
+    assert_eq!(block.lines.len(), 2);
+    assert!(lines[block.lines.start].contains("fn main"));
+    assert!(lines[block.lines.end - 1].contains("let x"));
+}
+
+#[test]
+fn split_view_places_source_lines_beside_their_rendered_blocks() {
+    let markdown = "# Guide\n\nFirst paragraph.\n\n## Usage\n\nRun it.\n";
+    let config = Config {
+        cols: Some(80),
+        cols_from_cli: true,
+        no_colors: true,
+        ..Config::default()
+    };
+    let (output, map) = TerminalRenderer::new(&config)
+        .unwrap()
+        .render_split_view(
+            crate::markdown::MarkdownProcessor::new(&config)
+                .parse_document(markdown)
+                .unwrap(),
+            markdown,
+        )
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(map.source_lines.len(), lines.len());
+    let paragraph = map.rendered_lines_for_source(3).unwrap();
+    assert!(lines[paragraph.start].starts_with("First paragraph."));
+    assert!(lines[paragraph.start].ends_with("First paragraph."));
+    let usage = &map.headings[1];
+    assert!(lines[usage.line].starts_with("## Usage"));
+    assert!(lines[usage.line].contains(" │ "));
+    assert!(lines[usage.line].ends_with("Usage"));
+    assert!(lines.iter().all(|line| line.find(" │ ") == Some(38)));
+}
+
+#[test]
+fn split_view_wraps_long_source_lines_beside_their_block() {
+    let markdown =
+        "A paragraph whose single source line is much longer than the source pane.\n\nNext.\n";
+    let config = Config {
+        cols: Some(43),
+        cols_from_cli: true,
+        no_colors: true,
+        ..Config::default()
+    };
+    let (output, map) = TerminalRenderer::new(&config)
+        .unwrap()
+        .render_split_view(
+            crate::markdown::MarkdownProcessor::new(&config)
+                .parse_document(markdown)
+                .unwrap(),
+            markdown,
+        )
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    let source: Vec<&str> = lines.iter().map(|line| &line[..20]).collect();
+
+    assert_eq!(
+        source[..4],
+        [
+            "A paragraph whose si",
+            "ngle source line is ",
+            "much longer than the",
+            " source pane.       ",
+        ]
+    );
+    assert!(lines.iter().all(|line| line.find(" │ ") == Some(20)));
+    assert_eq!(map.source_lines[1], None);
+    let next = map.rendered_lines_for_source(3).unwrap();
+    assert!(lines[next.start].starts_with("Next."));
+    assert!(lines[next.start].ends_with("Next."));
+}
+
+#[test]
+fn diff_view_marks_changed_blocks_and_maps_new_lines() {
+    let old = "# Guide\n\n- one\n- two\n\nOld closing.\n\nEnd.\n";
+    let new = "# Guide\n\n- one\n- three\n\nNew closing.\n\nEnd.\n\nAppendix.\n";
+    let config = Config {
+        cols: Some(60),
+        cols_from_cli: true,
+        no_colors: true,
+        source_map: true,
+        ..Config::default()
+    };
+    let processor = crate::markdown::MarkdownProcessor::new(&config);
+    let diff = crate::diff::DocumentDiff::new(&processor, old, new);
+    let (output, map) = TerminalRenderer::new(&config)
+        .unwrap()
+        .render_diff(processor.parse_document(&diff.source).unwrap(), &diff)
+        .unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(lines.iter().any(|line| line.starts_with("-- two")));
+    assert!(lines.iter().any(|line| line.starts_with("~- three")));
+    assert!(lines.iter().any(|line| line.starts_with("-Old closing.")));
+    assert!(lines.iter().any(|line| line.starts_with("~New closing.")));
+    assert!(lines.iter().any(|line| line.starts_with("+Appendix.")));
+    assert!(lines.iter().any(|line| line.starts_with(" - one")));
+    let closing = map.rendered_lines_for_source(6).unwrap();
+    assert!(lines[closing.start].contains("New closing."));
+    assert!(map.source_lines.iter().flatten().all(|&line| line <= 10));
+}
@@ -51,6 +51,15 @@ impl AnsiStyle {
             return text.to_string();
         }
 
+        let mut result = self.sequence();
+        result.push_str(text);
+        result.push_str("\x1b[0m");
+
+        result
+    }
+
+    /// Escape sequences that switch to this style, without text or a reset.
+    pub(crate) fn sequence(&self) -> String {
         let mut result = String::new();
 
         if let Some(fg) = self.fg_color {
@@ -73,10 +82,6 @@ impl AnsiStyle {
         if self.strikethrough {
             result.push_str("\x1b[9m");
         }
-
-        result.push_str(text);
-        result.push_str("\x1b[0m");
-
         result
     }
 }
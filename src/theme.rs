@@ -24,7 +24,7 @@ pub use manager::ThemeManager;
 pub use overrides::{apply_custom_code_theme, apply_custom_theme};
 pub use types::{SyntaxTheme, Theme};
 
-pub(crate) use color_parse::parse_color_value;
+pub(crate) use color_parse::{color_to_rgb, parse_color_value};
 
 #[cfg(test)]
 use builtin::BUILTIN_THEME_FILES;
@@ -155,7 +155,7 @@ pub(super) fn calculate_theme_luminosity(theme: &Theme) -> f64 {
 }
 
 /// Convert Color to RGB tuple if possible
-fn color_to_rgb(color: &Color) -> Option<(u8, u8, u8)> {
+pub(crate) fn color_to_rgb(color: &Color) -> Option<(u8, u8, u8)> {
     match color {
         Color::AnsiValue(n) => Some(ansi256_to_rgb(*n)),
         Color::Rgb { r, g, b } => Some((*r, *g, *b)),
@@ -44,9 +44,17 @@ pub fn create_style(theme: &Theme, element: ThemeElement) -> AnsiStyle {
         ThemeElement::TableBorder => &theme.table_border,
         ThemeElement::Error => &theme.error,
         ThemeElement::Warning => &theme.warning,
+        ThemeElement::DiffAdded => &theme.diff_added_background,
+        ThemeElement::DiffDeleted => &theme.diff_deleted_background,
+        ThemeElement::DiffModified => &theme.diff_modified_background,
     };
 
-    let mut style = AnsiStyle::new().fg(color.clone().into());
+    let mut style = match element {
+        ThemeElement::DiffAdded | ThemeElement::DiffDeleted | ThemeElement::DiffModified => {
+            AnsiStyle::new().bg(color.clone().into())
+        }
+        _ => AnsiStyle::new().fg(color.clone().into()),
+    };
 
     match element {
         ThemeElement::Strong | ThemeElement::H1 => style = style.bold(),
@@ -26,4 +26,7 @@ pub enum ThemeElement {
     TableBorder,
     Error,
     Warning,
+    DiffAdded,
+    DiffDeleted,
+    DiffModified,
 }
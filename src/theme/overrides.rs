@@ -116,6 +116,31 @@ fn apply_theme_override(theme: &mut Theme, key: &str, value: &str) -> Result<()>
         "table_border" | "tableborder" => theme.table_border = parse_color_spec(value)?,
         "error" => theme.error = parse_color_spec(value)?,
         "warning" => theme.warning = parse_color_spec(value)?,
+        "diff_added_background" | "diff_added_bg" => {
+            theme.diff_added_background = parse_color_spec(value)?
+        }
+        "diff_deleted_background" | "diff_deleted_bg" => {
+            theme.diff_deleted_background = parse_color_spec(value)?
+        }
+        "diff_modified_background" | "diff_modified_bg" => {
+            theme.diff_modified_background = parse_color_spec(value)?
+        }
+        "pager_footer_foreground" => theme.pager_footer_foreground = parse_color_spec(value)?,
+        "pager_footer_background" | "pager_footer_bg" => {
+            theme.pager_footer_background = parse_color_spec(value)?
+        }
+        "pager_footer_accent" => theme.pager_footer_accent = parse_color_spec(value)?,
+        "pager_progress" => theme.pager_progress = parse_color_spec(value)?,
+        "pager_help_foreground" => theme.pager_help_foreground = parse_color_spec(value)?,
+        "pager_help_background" | "pager_help_bg" => {
+            theme.pager_help_background = parse_color_spec(value)?
+        }
+        "pager_search_current_background" | "pager_search_current_bg" => {
+            theme.pager_search_current_background = parse_color_spec(value)?
+        }
+        "pager_search_match_background" | "pager_search_match_bg" => {
+            theme.pager_search_match_background = parse_color_spec(value)?
+        }
         other => bail!("Unknown key for custom theme: '{}'.", other),
     }
 
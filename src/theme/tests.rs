@@ -117,6 +117,27 @@ fn custom_theme_can_enable_transparent_pager_status_bar() {
     );
 }
 
+#[test]
+fn custom_theme_overrides_pager_colors() {
+    let mut theme = Theme::default();
+
+    apply_custom_theme(
+        &mut theme,
+        "pager_footer_background=#fdf6e3;pager_search_match_bg=ansi(229)",
+    )
+    .expect("pager color overrides should be accepted");
+
+    assert_eq!(
+        theme.pager_footer_background,
+        Color::Rgb {
+            r: 253,
+            g: 246,
+            b: 227
+        }
+    );
+    assert_eq!(theme.pager_search_match_background, Color::AnsiValue(229));
+}
+
 #[test]
 fn test_apply_custom_code_theme_overrides() {
     let mut theme = Theme::default();
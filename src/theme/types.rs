@@ -71,6 +71,32 @@ pub struct Theme {
     pub error: Color,
     pub warning: Color,
 
+    // Diff backgrounds
+    #[serde(default = "default_diff_added_background")]
+    pub diff_added_background: Color,
+    #[serde(default = "default_diff_deleted_background")]
+    pub diff_deleted_background: Color,
+    #[serde(default = "default_diff_modified_background")]
+    pub diff_modified_background: Color,
+
+    // Pager status bar, panels, and search matches
+    #[serde(default = "default_pager_footer_foreground")]
+    pub pager_footer_foreground: Color,
+    #[serde(default = "default_pager_footer_background")]
+    pub pager_footer_background: Color,
+    #[serde(default = "default_pager_footer_accent")]
+    pub pager_footer_accent: Color,
+    #[serde(default = "default_pager_progress")]
+    pub pager_progress: Color,
+    #[serde(default = "default_pager_help_foreground")]
+    pub pager_help_foreground: Color,
+    #[serde(default = "default_pager_help_background")]
+    pub pager_help_background: Color,
+    #[serde(default = "default_pager_search_current_background")]
+    pub pager_search_current_background: Color,
+    #[serde(default = "default_pager_search_match_background")]
+    pub pager_search_match_background: Color,
+
     // Code syntax highlighting colors
     pub syntax: SyntaxTheme,
 }
@@ -147,3 +173,71 @@ impl Theme {
 fn default_line_number_color() -> Color {
     Color::Grey
 }
+
+fn default_diff_added_background() -> Color {
+    Color::AnsiValue(22)
+}
+
+fn default_diff_deleted_background() -> Color {
+    Color::AnsiValue(52)
+}
+
+fn default_diff_modified_background() -> Color {
+    Color::AnsiValue(58)
+}
+
+fn default_pager_footer_foreground() -> Color {
+    Color::Rgb {
+        r: 125,
+        g: 125,
+        b: 125,
+    }
+}
+
+fn default_pager_footer_background() -> Color {
+    Color::Rgb {
+        r: 36,
+        g: 36,
+        b: 36,
+    }
+}
+
+fn default_pager_footer_accent() -> Color {
+    Color::Rgb {
+        r: 50,
+        g: 50,
+        b: 50,
+    }
+}
+
+fn default_pager_progress() -> Color {
+    Color::Rgb {
+        r: 90,
+        g: 90,
+        b: 90,
+    }
+}
+
+fn default_pager_help_foreground() -> Color {
+    Color::Rgb {
+        r: 125,
+        g: 125,
+        b: 125,
+    }
+}
+
+fn default_pager_help_background() -> Color {
+    Color::Rgb {
+        r: 27,
+        g: 27,
+        b: 27,
+    }
+}
+
+fn default_pager_search_current_background() -> Color {
+    Color::Reset
+}
+
+fn default_pager_search_match_background() -> Color {
+    Color::Reset
+}
@@ -0,0 +1,141 @@
+use crate::cli::{LineNumberOptions, LineNumberTarget, TocFormat};
+use crate::config::Config;
+use crate::markdown::{Heading, MarkdownProcessor, headings};
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct OutlineNode {
+    level: u8,
+    text: String,
+    slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    children: Vec<OutlineNode>,
+}
+
+/// Formats the heading outline of `content` for `mdv toc`.
+pub(crate) fn format_outline(content: &str, config: &Config, format: TocFormat) -> Result<String> {
+    let mut config = config.clone();
+    config.line_numbers = Some(LineNumberOptions {
+        target: LineNumberTarget::Source,
+        separator: false,
+    });
+    let document = MarkdownProcessor::new(&config).parse_document(content)?;
+    let headings = headings(&document.events);
+    let min_level = headings
+        .iter()
+        .map(|heading| heading.level)
+        .min()
+        .unwrap_or(1);
+
+    match format {
+        TocFormat::Plain => Ok(headings
+            .iter()
+            .map(|heading| {
+                format!(
+                    "{}{}  #{}\n",
+                    indent(heading, min_level),
+                    heading.text,
+                    heading.slug
+                )
+            })
+            .collect()),
+        TocFormat::Markdown => Ok(headings
+            .iter()
+            .map(|heading| {
+                format!(
+                    "{}- [{}](#{})\n",
+                    indent(heading, min_level),
+                    escape_link_text(&heading.text),
+                    heading.slug
+                )
+            })
+            .collect()),
+        TocFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&outline_tree(headings))?;
+            json.push('\n');
+            Ok(json)
+        }
+    }
+}
+
+fn indent(heading: &Heading, min_level: u8) -> String {
+    "  ".repeat(usize::from(heading.level - min_level))
+}
+
+fn escape_link_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(character, '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+/// Nests each heading under the closest preceding heading of a lower level.
+fn outline_tree(headings: Vec<Heading>) -> Vec<OutlineNode> {
+    let mut roots = Vec::new();
+    let mut open: Vec<OutlineNode> = Vec::new();
+    for heading in headings {
+        close_until(&mut open, &mut roots, heading.level);
+        open.push(OutlineNode {
+            level: heading.level,
+            text: heading.text,
+            slug: heading.slug,
+            line: heading.line,
+            children: Vec::new(),
+        });
+    }
+    close_until(&mut open, &mut roots, 0);
+    roots
+}
+
+fn close_until(open: &mut Vec<OutlineNode>, roots: &mut Vec<OutlineNode>, level: u8) {
+    while open.last().is_some_and(|node| node.level >= level) {
+        let node = open.pop().expect("open node");
+        match open.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str =
+        "---\ntitle: Guide\n---\n# Guide\n\n## Install\n\n### Linux\n\n## [Usage] notes\n";
+
+    #[test]
+    fn plain_outline_indents_headings_and_lists_slugs() {
+        let outline = format_outline(DOCUMENT, &Config::default(), TocFormat::Plain).unwrap();
+
+        assert_eq!(
+            outline,
+            "Guide  #guide\n  Install  #install\n    Linux  #linux\n  [Usage] notes  #usage-notes\n"
+        );
+    }
+
+    #[test]
+    fn markdown_outline_escapes_link_text() {
+        let outline = format_outline(DOCUMENT, &Config::default(), TocFormat::Markdown).unwrap();
+
+        assert!(outline.starts_with("- [Guide](#guide)\n  - [Install](#install)\n"));
+        assert!(outline.ends_with("  - [\\[Usage\\] notes](#usage-notes)\n"));
+    }
+
+    #[test]
+    fn json_outline_nests_headings_with_source_lines() {
+        let outline = format_outline(DOCUMENT, &Config::default(), TocFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&outline).unwrap();
+
+        assert_eq!(value[0]["line"], 4);
+        assert_eq!(value[0]["children"][0]["slug"], "install");
+        assert_eq!(value[0]["children"][0]["children"][0]["text"], "Linux");
+        assert_eq!(value[0]["children"][1]["line"], 10);
+    }
+}
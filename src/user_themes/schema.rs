@@ -60,6 +60,17 @@ pub(crate) struct ThemeFile {
     pub table_border: Option<ColorYaml>,
     pub error: Option<ColorYaml>,
     pub warning: Option<ColorYaml>,
+    pub diff_added_background: Option<ColorYaml>,
+    pub diff_deleted_background: Option<ColorYaml>,
+    pub diff_modified_background: Option<ColorYaml>,
+    pub pager_footer_foreground: Option<ColorYaml>,
+    pub pager_footer_background: Option<ColorYaml>,
+    pub pager_footer_accent: Option<ColorYaml>,
+    pub pager_progress: Option<ColorYaml>,
+    pub pager_help_foreground: Option<ColorYaml>,
+    pub pager_help_background: Option<ColorYaml>,
+    pub pager_search_current_background: Option<ColorYaml>,
+    pub pager_search_match_background: Option<ColorYaml>,
 
     pub inline_style: InlineStyleOverrides,
 
@@ -170,6 +181,35 @@ impl ThemeFile {
             table_border: pick(&self.table_border, &base.table_border),
             error: pick(&self.error, &base.error),
             warning: pick(&self.warning, &base.warning),
+            diff_added_background: pick(&self.diff_added_background, &base.diff_added_background),
+            diff_deleted_background: pick(
+                &self.diff_deleted_background,
+                &base.diff_deleted_background,
+            ),
+            diff_modified_background: pick(
+                &self.diff_modified_background,
+                &base.diff_modified_background,
+            ),
+            pager_footer_foreground: pick(
+                &self.pager_footer_foreground,
+                &base.pager_footer_foreground,
+            ),
+            pager_footer_background: pick(
+                &self.pager_footer_background,
+                &base.pager_footer_background,
+            ),
+            pager_footer_accent: pick(&self.pager_footer_accent, &base.pager_footer_accent),
+            pager_progress: pick(&self.pager_progress, &base.pager_progress),
+            pager_help_foreground: pick(&self.pager_help_foreground, &base.pager_help_foreground),
+            pager_help_background: pick(&self.pager_help_background, &base.pager_help_background),
+            pager_search_current_background: pick(
+                &self.pager_search_current_background,
+                &base.pager_search_current_background,
+            ),
+            pager_search_match_background: pick(
+                &self.pager_search_match_background,
+                &base.pager_search_match_background,
+            ),
             syntax,
         }
     }
@@ -251,6 +291,17 @@ impl ThemeFile {
             table_border: color!(self, table_border),
             error: color!(self, error),
             warning: color!(self, warning),
+            diff_added_background: color!(self, diff_added_background),
+            diff_deleted_background: color!(self, diff_deleted_background),
+            diff_modified_background: color!(self, diff_modified_background),
+            pager_footer_foreground: color!(self, pager_footer_foreground),
+            pager_footer_background: color!(self, pager_footer_background),
+            pager_footer_accent: color!(self, pager_footer_accent),
+            pager_progress: color!(self, pager_progress),
+            pager_help_foreground: color!(self, pager_help_foreground),
+            pager_help_background: color!(self, pager_help_background),
+            pager_search_current_background: color!(self, pager_search_current_background),
+            pager_search_match_background: color!(self, pager_search_match_background),
             syntax: SyntaxTheme {
                 keyword: color!(syntax, keyword),
                 string: color!(syntax, string),
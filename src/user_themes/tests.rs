@@ -108,6 +108,35 @@ fn user_theme_can_enable_transparent_pager_status_bar() {
     );
 }
 
+#[test]
+fn user_theme_inherits_pager_colors_it_does_not_set() {
+    let tmp = TempDir::new().unwrap();
+    let themes = tmp.path().join(THEMES_DIR);
+    fs::create_dir(&themes).unwrap();
+    fs::write(
+        themes.join("light-nord.yaml"),
+        "name: light-nord\nextends: nord\npager_footer_background: \"#eceff4\"\n",
+    )
+    .unwrap();
+
+    let manager = ThemeManager::new();
+    let loaded = load_user_themes(tmp.path(), &manager).unwrap();
+    let nord = manager.get_theme("nord").unwrap();
+    assert_eq!(
+        loaded[0].pager_footer_background,
+        Color::Rgb {
+            r: 236,
+            g: 239,
+            b: 244
+        }
+    );
+    assert_eq!(loaded[0].pager_footer_accent, nord.pager_footer_accent);
+    assert_eq!(
+        loaded[0].pager_search_current_background,
+        nord.pager_search_current_background
+    );
+}
+
 #[test]
 fn extends_builtin_theme() {
     let tmp = TempDir::new().unwrap();
@@ -1,8 +1,11 @@
 use regex::regex;
 use std::iter::Peekable;
+use std::path::Path;
 use std::str::Chars;
 use unicode_width::UnicodeWidthStr;
 
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "mdown", "mkdn", "mkd", "markdown"];
+
 /// Calculate the display width of a string, accounting for Unicode characters
 pub fn display_width(s: &str) -> usize {
     UnicodeWidthStr::width(s)
@@ -20,6 +23,24 @@ pub fn strip_ansi(s: &str) -> String {
         .to_string()
 }
 
+/// Removes every terminal escape sequence and control character except newlines and
+/// tabs, including sequences `strip_ansi` leaves alone.
+pub(crate) fn strip_terminal_sequences(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => {
+                consume_escape_sequence(&mut chars);
+            }
+            '\n' | '\t' => plain.push(ch),
+            ch if ch.is_control() => {}
+            ch => plain.push(ch),
+        }
+    }
+    plain
+}
+
 pub(crate) fn escape_html_text(text: &str) -> String {
     let mut escaped = String::with_capacity(text.len());
     for ch in text.chars() {
@@ -35,6 +56,48 @@ pub(crate) fn escape_html_text(text: &str) -> String {
     escaped
 }
 
+/// Returns the scheme of an absolute URL such as `https:` or `file:`.
+pub(crate) fn url_scheme(location: &str) -> Option<&str> {
+    let (scheme, _) = location.split_once(':')?;
+    let mut characters = scheme.chars();
+    // A single letter is a Windows drive, not a scheme.
+    (scheme.len() > 1
+        && characters.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && characters.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.')))
+    .then_some(scheme)
+}
+
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(byte) = escaped {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Whether the path has one of the Markdown file extensions.
+pub(crate) fn is_markdown_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            MARKDOWN_EXTENSIONS
+                .iter()
+                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+        })
+}
+
 /// Text wrapping mode
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WrapMode {
@@ -85,7 +148,8 @@ fn consume_escape_sequence(chars: &mut Peekable<Chars<'_>>) -> String {
                     }
                 }
             }
-            ']' => {
+            // OSC, DCS, APC, PM, and SOS run to BEL or the string terminator.
+            ']' | 'P' | '_' | '^' | 'X' => {
                 sequence.push(chars.next().unwrap());
                 while let Some(ch) = chars.next() {
                     sequence.push(ch);
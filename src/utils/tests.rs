@@ -21,6 +21,13 @@ fn test_strip_ansi() {
     assert_eq!(strip_ansi(combined), "red link");
 }
 
+#[test]
+fn strip_terminal_sequences_removes_every_escape_and_control() {
+    let text = "\x1b[1;31mBold\x1b[0m \x1b]8;;https://a.test\x07link\x1b]8;;\x07\x1b_Ga=T;AA\x1b\\\x1b[2K\r\x08\tend\n";
+
+    assert_eq!(strip_terminal_sequences(text), "Bold link\tend\n");
+}
+
 #[test]
 fn test_wrap_text() {
     let text = "This is a long line that should be wrapped at a specific width to test the wrapping functionality.";
@@ -134,3 +141,11 @@ fn test_word_wrap_with_leading_ansi_and_indent_has_no_blank_first_line() {
         wrapped
     );
 }
+
+#[test]
+fn test_is_markdown_path_ignores_extension_case() {
+    assert!(is_markdown_path(std::path::Path::new("docs/guide.md")));
+    assert!(is_markdown_path(std::path::Path::new("NOTES.Markdown")));
+    assert!(!is_markdown_path(std::path::Path::new("image.png")));
+    assert!(!is_markdown_path(std::path::Path::new("README")));
+}
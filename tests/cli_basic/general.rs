@@ -24,6 +24,37 @@ fn test_help_subcommand_prints_long_help_when_output_is_not_a_terminal() {
     assert!(stdout.contains("--block-spacing <SPACING>"));
 }
 
+#[test]
+fn test_toc_subcommand_applies_front_matter_and_from_options() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(
+        file.path(),
+        "---\ntitle: Guide\n---\n# Guide\n\n## Install\n\n## Usage\n",
+    )
+    .unwrap();
+
+    mdv_cmd()
+        .arg("toc")
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout("Guide  #guide\n  Install  #install\n  Usage  #usage\n");
+    mdv_cmd()
+        .args(["toc", "--format", "markdown", "--from", "## Install"])
+        .arg(file.path())
+        .assert()
+        .success()
+        .stdout("- [Install](#install)\n- [Usage](#usage)\n");
+
+    mdv_cmd()
+        .args(["toc", "--format", "json", "-"])
+        .write_stdin("# Title\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"slug\": \"title\""))
+        .stdout(predicate::str::contains("\"line\": 1"));
+}
+
 #[test]
 fn test_pretty_marker_help_documents_font_behavior() {
     let mut cmd = mdv_cmd();
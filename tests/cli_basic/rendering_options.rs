@@ -169,6 +169,43 @@ fn test_from_text_option() {
         .stdout(predicate::str::contains("Target Section"));
 }
 
+#[test]
+fn test_section_option_renders_one_section_with_source_lines() {
+    let temp_file = NamedTempFile::new().unwrap();
+    fs::write(
+        &temp_file,
+        "# Guide\n\n## Install\n\n### Linux\n\nRun it.[^1]\n\n```sh\n# not a heading\n```\n\n### macOS\n\nMac only.\n\n## Usage\n\nUse it.\n\n[^1]: Linux note.\n",
+    )
+    .unwrap();
+
+    let output = mdv_cmd()
+        .args([
+            "--no-colors",
+            "--line-numbers",
+            "source",
+            "--section",
+            "Install > Linux",
+        ])
+        .arg(temp_file.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with(" 5"));
+    assert!(stdout.contains("# not a heading"));
+    assert!(stdout.contains("21 [^1] Linux note."));
+    assert!(!stdout.contains("Mac only"));
+    assert!(!stdout.contains("Use it"));
+
+    mdv_cmd()
+        .args(["--section", "missing"])
+        .arg(temp_file.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("section not found: missing"));
+}
+
 #[test]
 fn test_tab_length_option() {
     let temp_file = NamedTempFile::new().unwrap();
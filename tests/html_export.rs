@@ -0,0 +1,108 @@
+use crate::mdv_stdout;
+
+#[test]
+fn test_html_without_mode_keeps_fragment_output() {
+    let html = mdv_stdout("# Guide\n\nText.\n", &["--html"]);
+
+    assert_eq!(html, "<h1>Guide</h1>\n<p>Text.</p>\n");
+}
+
+#[test]
+fn test_html_standalone_wraps_document_in_themed_page() {
+    let html = mdv_stdout(
+        "# Guide\n\nText.\n",
+        &["--html=standalone", "--theme", "monokai"],
+    );
+
+    assert!(html.starts_with("<!DOCTYPE html>\n"), "html: {html}");
+    assert!(html.contains("<title>Guide</title>"));
+    assert!(html.contains("<style>\n:root {\n"));
+    assert!(html.contains("  --bg: #272822;"), "html: {html}");
+    assert!(html.contains("<main>\n<h1 id=\"guide\">Guide</h1>\n<p>Text.</p>\n</main>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn test_html_standalone_accepts_separate_mode_argument() {
+    let html = mdv_stdout("# Guide\n", &["--html", "standalone"]);
+
+    assert!(html.starts_with("<!DOCTYPE html>\n"), "html: {html}");
+}
+
+#[test]
+fn test_html_standalone_builds_nested_table_of_contents() {
+    let html = mdv_stdout(
+        "# Guide\n\n## Install\n\n### Linux\n\n## Usage\n\n# Guide\n",
+        &["--html=standalone"],
+    );
+
+    assert!(
+        html.contains(
+            "<ul>\n<li><a href=\"#guide\">Guide</a>\n<ul>\n<li><a href=\"#install\">Install</a>\n<ul>\n<li><a href=\"#linux\">Linux</a></li>\n</ul>\n</li>\n<li><a href=\"#usage\">Usage</a></li>\n</ul>\n</li>\n<li><a href=\"#guide-1\">Guide</a></li>\n</ul>\n</nav>"
+        ),
+        "html: {html}"
+    );
+    assert!(html.contains("<h1 id=\"guide-1\">Guide</h1>"));
+}
+
+#[test]
+fn test_html_standalone_turns_callouts_into_colored_boxes() {
+    let html = mdv_stdout(
+        "> [!WARNING] Mind the gap\n> Body text\n\n> [!tip]-\n> Hidden\n\n> Plain quote\n",
+        &["--html=standalone"],
+    );
+
+    assert!(
+        html.contains("<div class=\"callout callout-warning\" style=\"--callout: #"),
+        "html: {html}"
+    );
+    assert!(html.contains("<p class=\"callout-title\">Mind the gap</p>\n<p>Body text</p>\n</div>"));
+    assert!(html.contains("<details class=\"callout callout-tip\""));
+    assert!(
+        html.contains("<summary class=\"callout-title\">Tip</summary>\n<p>Hidden</p>\n</details>")
+    );
+    assert!(html.contains("<blockquote>\n<p>Plain quote</p>\n</blockquote>"));
+    assert!(!html.contains("[!WARNING]"));
+}
+
+#[test]
+fn test_html_standalone_highlights_code_with_code_theme() {
+    let markdown = "```rust\nfn main() {}\n```\n";
+    let html = mdv_stdout(
+        markdown,
+        &["--html=standalone", "--code-theme", "base16-ocean.dark"],
+    );
+
+    assert!(
+        html.contains("<figure class=\"code-block\"><pre><code>"),
+        "html: {html}"
+    );
+    assert!(
+        html.contains("<span style=\"color:#b48ead;\">fn</span>"),
+        "html: {html}"
+    );
+    assert!(html.contains("  --code-block-bg: #2b303b;"));
+
+    let named = mdv_stdout(
+        markdown,
+        &[
+            "--html=standalone",
+            "--code-block-style",
+            "pretty:show-name",
+        ],
+    );
+    assert!(
+        named.contains("<figcaption>Rust</figcaption>"),
+        "html: {named}"
+    );
+}
+
+#[test]
+fn test_html_standalone_renders_math_as_text() {
+    let html = mdv_stdout("Inline $x^2$.\n", &["--html=standalone"]);
+
+    assert!(
+        html.contains("<span class=\"math math-inline\">x²</span>"),
+        "html: {html}"
+    );
+}
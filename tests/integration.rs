@@ -12,10 +12,14 @@ mod definition_lists;
 mod footnotes;
 #[path = "front_matter.rs"]
 mod front_matter;
+#[path = "html_export.rs"]
+mod html_export;
 #[path = "html_table_content.rs"]
 mod html_table_content;
 #[path = "inline_styles.rs"]
 mod inline_styles;
+#[path = "json_output.rs"]
+mod json_output;
 #[path = "layout.rs"]
 mod layout;
 #[path = "line_numbers.rs"]
@@ -26,7 +30,39 @@ mod links_tables;
 mod math;
 #[path = "media.rs"]
 mod media;
+#[path = "mermaid.rs"]
+mod mermaid;
+#[path = "screen_export.rs"]
+mod screen_export;
 #[path = "syntax_palette.rs"]
 mod syntax_palette;
+#[path = "text_output.rs"]
+mod text_output;
 #[path = "visibility.rs"]
 mod visibility;
+
+use assert_cmd::Command;
+use std::process::Output;
+use tempfile::NamedTempFile;
+
+/// Runs mdv with `args` on a temporary file that holds `markdown`.
+fn run_mdv(markdown: &str, args: &[&str]) -> Output {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(&file, markdown).unwrap();
+    Command::new(assert_cmd::cargo::cargo_bin!("mdv"))
+        .args(args)
+        .arg(file.path())
+        .output()
+        .unwrap()
+}
+
+/// Standard output of a successful mdv run with `args` on `markdown`.
+fn mdv_stdout(markdown: &str, args: &[&str]) -> String {
+    let output = run_mdv(markdown, args);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -0,0 +1,97 @@
+use crate::mdv_stdout;
+use serde_json::{Value, json};
+
+fn json_tree(markdown: &str, args: &[&str]) -> Value {
+    serde_json::from_str(&mdv_stdout(
+        markdown,
+        &[&["--format", "json"], args].concat(),
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_json_output_includes_front_matter_and_source_lines() {
+    let tree = json_tree(
+        "---\ntitle: Guide\ntags: [cli, docs]\n---\n# Guide\n\nFirst line\nsecond *line*.\n",
+        &[],
+    );
+
+    assert_eq!(tree["version"], 1);
+    assert_eq!(
+        tree["front_matter"]["properties"],
+        json!({"title": "Guide", "tags": ["cli", "docs"]})
+    );
+    let heading = &tree["children"][0];
+    assert_eq!(heading["type"], "heading");
+    assert_eq!(heading["level"], 1);
+    assert_eq!(heading["id"], "guide");
+    assert_eq!(heading["lines"], json!({"start": 5, "end": 5}));
+
+    let paragraph = &tree["children"][1];
+    assert_eq!(paragraph["lines"], json!({"start": 7, "end": 8}));
+    assert_eq!(paragraph["children"][0]["value"], "First line");
+    assert_eq!(paragraph["children"][1]["type"], "soft_break");
+    assert_eq!(paragraph["children"][3]["type"], "emphasis");
+    assert_eq!(paragraph["children"][3]["children"][0]["value"], "line");
+}
+
+#[test]
+fn test_json_output_turns_admonitions_into_callouts() {
+    let tree = json_tree(
+        "!!! warning \"Mind the gap\"\n    Body text\n\n> [!tip]- Folded\n> Hidden\n",
+        &[],
+    );
+
+    let warning = &tree["children"][0];
+    assert_eq!(warning["type"], "callout");
+    assert_eq!(warning["callout"], "warning");
+    assert_eq!(warning["fold"], Value::Null);
+    assert_eq!(warning["children"][0]["children"][0]["value"], "Body text");
+
+    let tip = &tree["children"][1];
+    assert_eq!(tip["callout"], "tip");
+    assert_eq!(tip["title"], "Folded");
+    assert_eq!(tip["fold"], "collapsed");
+    assert_eq!(
+        tip["children"][0]["children"],
+        json!([{
+            "type": "text",
+            "lines": {"start": 5, "end": 5},
+            "value": "Hidden"
+        }])
+    );
+}
+
+#[test]
+fn test_json_output_resolves_task_markers_and_footnotes() {
+    let tree = json_tree(
+        "- [x] done\n- [-] dropped\n\nSee[^note].\n\n[^note]: Details.\n",
+        &["--pretty-checkbox", "square", "--custom-checkbox=-:~"],
+    );
+
+    let items = &tree["children"][0]["children"];
+    assert_eq!(items[0]["children"][0]["type"], "task_marker");
+    assert_eq!(items[0]["children"][0]["checked"], true);
+    assert_eq!(items[1]["children"][0]["state"], "-");
+    assert_eq!(items[1]["children"][0]["icon"], "~");
+    assert_eq!(items[1]["children"][1]["value"], "dropped");
+
+    let reference = &tree["children"][1]["children"][1];
+    assert_eq!(reference["type"], "footnote_reference");
+    assert_eq!(reference["label"], "note");
+    assert_eq!(reference["number"], 1);
+    assert_eq!(reference["defined"], true);
+    assert_eq!(tree["children"][2]["type"], "footnote_definition");
+}
+
+#[test]
+fn test_json_output_keeps_code_block_text() {
+    let tree = json_tree("```rust title=main\nfn main() {}\n```\n", &[]);
+
+    let block = &tree["children"][0];
+    assert_eq!(block["type"], "code_block");
+    assert_eq!(block["info"], "rust title=main");
+    assert_eq!(block["language"], "rust");
+    assert_eq!(block["value"], "fn main() {}\n");
+    assert_eq!(block["lines"], json!({"start": 2, "end": 2}));
+}
@@ -249,3 +249,98 @@ fn test_render_html_inline_table_references_for_split_inline_html_link() {
         stdout
     );
 }
+
+fn write_png(directory: &std::path::Path, name: &str, width: u32, height: u32) {
+    image::RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]))
+        .save(directory.join(name))
+        .unwrap();
+}
+
+#[test]
+fn test_images_blocks_draw_local_images_inside_blockquotes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    write_png(temp_dir.path(), "diagram.png", 64, 16);
+    let document = temp_dir.path().join("doc.md");
+    fs::write(
+        &document,
+        "> ![architecture](diagram.png)\n> after the image\n\n![remote](https://example.com/a.png)\n",
+    )
+    .unwrap();
+
+    let output = mdv_cmd()
+        .args(["--images", "blocks", "-c", "24"])
+        .arg(&document)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let clean = mdv::utils::strip_ansi(&stdout);
+    let image_rows: Vec<&str> = clean.lines().filter(|line| line.contains('▀')).collect();
+
+    assert_eq!(image_rows.len(), 3, "stdout:\n{clean}");
+    for row in &image_rows {
+        assert!(row.trim_start().starts_with("│ ▀"), "stdout:\n{clean}");
+        assert!(mdv::utils::display_width(row) <= 24, "stdout:\n{clean}");
+    }
+    assert!(!clean.contains("architecture"), "stdout:\n{clean}");
+    assert!(clean.contains("│ after the image"), "stdout:\n{clean}");
+    assert!(clean.contains("[IMAGE] remote"), "stdout:\n{clean}");
+}
+
+#[test]
+fn test_images_kitty_draws_over_reserved_rows_and_keeps_markers_for_missing_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    write_png(temp_dir.path(), "diagram.png", 40, 40);
+    let document = temp_dir.path().join("doc.md");
+    fs::write(
+        &document,
+        "![diagram](diagram.png)\n\n![gone](missing.png)\n",
+    )
+    .unwrap();
+
+    let output = mdv_cmd()
+        .args(["--images", "kitty", "--no-colors"])
+        .arg(&document)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[IMAGE] gone"), "stdout:\n{stdout}");
+    assert!(!stdout.contains("\x1b_G"), "stdout:\n{stdout}");
+
+    let output = mdv_cmd()
+        .args(["--images", "kitty"])
+        .arg(&document)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    let drawn = lines
+        .iter()
+        .position(|line| line.contains("\x1b_Ga=T,f=100,q=2,C=1,c=4,r=2,"))
+        .unwrap_or_else(|| panic!("stdout:\n{stdout}"));
+
+    assert!(lines[drawn].contains("\x1b7\r\x1b[1A"), "stdout:\n{stdout}");
+    assert_eq!(lines[drawn - 1].trim(), "");
+    assert!(
+        mdv::utils::strip_ansi(&stdout).contains("[IMAGE] gone"),
+        "stdout:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_images_flag_without_mode_accepts_following_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    fs::write(&temp_file, "![remote](https://example.com/a.png)\n").unwrap();
+
+    let output = mdv_cmd()
+        .arg("--images")
+        .arg(temp_file.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(mdv::utils::strip_ansi(&stdout).contains("[IMAGE] remote"));
+}
@@ -0,0 +1,71 @@
+use assert_cmd::Command;
+use mdv::utils::strip_ansi;
+use std::fs;
+use tempfile::NamedTempFile;
+
+fn mdv_cmd() -> Command {
+    Command::new(assert_cmd::cargo::cargo_bin!("mdv"))
+}
+
+fn render(markdown: &str, args: &[&str]) -> String {
+    let temp_file = NamedTempFile::new().unwrap();
+    fs::write(&temp_file, markdown).unwrap();
+
+    let output = mdv_cmd()
+        .arg("--no-colors")
+        .args(args)
+        .arg(temp_file.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    strip_ansi(&String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn test_mermaid_flowchart_renders_as_diagram() {
+    let clean = render(
+        "```mermaid\ngraph TD\n    A[Start] --> B[Done]\n```\n",
+        &["--code-block-style", "simple"],
+    );
+
+    assert!(clean.contains("│ Start │"), "output was: {clean}");
+    assert!(clean.contains("▼"));
+    assert!(clean.contains("│ Done │"));
+    assert!(!clean.contains("-->"));
+}
+
+#[test]
+fn test_mermaid_sequence_diagram_renders_as_diagram() {
+    let clean = render(
+        "```mermaid\nsequenceDiagram\n    Alice->>Bob: Hello\n```\n",
+        &["--code-block-style", "pretty"],
+    );
+
+    assert!(clean.contains("│ Alice │"), "output was: {clean}");
+    assert!(clean.contains("Hello"));
+    assert!(clean.contains("►"));
+    assert!(!clean.contains("->>"));
+}
+
+#[test]
+fn test_mermaid_unsupported_diagram_falls_back_to_code() {
+    let clean = render(
+        "```mermaid\npie title Pets\n    \"Dogs\" : 386\n```\n",
+        &["--code-block-style", "simple"],
+    );
+
+    assert!(clean.contains("pie title Pets"), "output was: {clean}");
+    assert!(clean.contains("\"Dogs\" : 386"));
+}
+
+#[test]
+fn test_mermaid_wide_diagram_falls_back_to_code() {
+    let clean = render(
+        "```mermaid\nflowchart LR\n    A[First step] --> B[Second step] --> C[Third step]\n```\n",
+        &["--cols", "40", "--code-block-style", "simple"],
+    );
+
+    assert!(clean.contains("A[First step]"), "output was: {clean}");
+    assert!(!clean.contains("│ First step │"));
+}
@@ -0,0 +1,46 @@
+use crate::{mdv_stdout, run_mdv};
+
+#[test]
+fn test_export_svg_draws_output_at_render_width() {
+    let svg = mdv_stdout(
+        "# Guide\n\nText with **bold**.\n",
+        &["--export", "svg", "--cols", "30", "--theme", "monokai"],
+    );
+
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"294\""),
+        "svg: {svg}"
+    );
+    assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#272822\"/>"));
+    assert!(
+        svg.contains("font-weight=\"bold\">Guide</tspan>"),
+        "svg: {svg}"
+    );
+    assert!(svg.contains(">bold</tspan>"));
+    assert!(!svg.contains('\x1b'));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn test_export_ansi_html_keeps_colors_and_links() {
+    let html = mdv_stdout(
+        "See [docs](https://example.com/docs).\n",
+        &["--export", "ansi-html", "--cols", "40"],
+    );
+
+    assert!(html.starts_with("<!DOCTYPE html>\n"), "html: {html}");
+    assert!(html.contains("width: 40ch;"));
+    assert!(html.contains("<pre class=\"terminal\">"));
+    assert!(
+        html.contains("<a href=\"https://example.com/docs\""),
+        "html: {html}"
+    );
+    assert!(!html.contains('\x1b'));
+}
+
+#[test]
+fn test_export_conflicts_with_html() {
+    let output = run_mdv("# Guide\n", &["--export", "svg", "--html"]);
+
+    assert!(!output.status.success());
+}
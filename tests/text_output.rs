@@ -0,0 +1,54 @@
+use crate::mdv_stdout;
+
+#[test]
+fn test_text_format_has_no_escape_sequences() {
+    let text = mdv_stdout(
+        "# Title\n\n**Bold** and [docs](https://example.com/docs).\n\n```rust\nfn main() {}\n```\n",
+        &[
+            "--format",
+            "text",
+            "--cols",
+            "60",
+            "--link-style",
+            "clickable",
+            "--theme",
+            "monokai",
+        ],
+    );
+
+    assert!(!text.contains('\x1b'), "text: {text:?}");
+    assert!(text.contains("Bold and docs[1]."), "text: {text}");
+    assert!(
+        text.contains("[1] https://example.com/docs"),
+        "text: {text}"
+    );
+    assert!(text.contains("fn main() {}"));
+}
+
+#[test]
+fn test_text_format_keeps_layout() {
+    let text = mdv_stdout(
+        "Intro[^n] paragraph that is long enough to wrap at the configured sixty column width.\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n- one\n- two\n\n[^n]: Footnote text.\n",
+        &["--format", "text", "--cols", "60"],
+    );
+
+    assert!(
+        text.lines().all(|line| line.chars().count() <= 60),
+        "text: {text}"
+    );
+    assert!(text.contains("a │ b"), "text: {text}");
+    assert!(text.contains("- one\n- two\n"), "text: {text}");
+    assert!(text.contains("Footnote text."));
+    assert!(text.lines().all(|line| line == line.trim_end()));
+}
+
+#[test]
+fn test_text_format_keeps_image_markers() {
+    let text = mdv_stdout(
+        "![Logo](logo.png)\n",
+        &["--format", "text", "--cols", "60", "--images", "blocks"],
+    );
+
+    assert!(!text.contains('\x1b'));
+    assert!(text.contains("Logo"), "text: {text}");
+}